@@ -5,6 +5,11 @@
 
 use secrecy::{ExposeSecret, SecretString};
 use std::env;
+use url::Url;
+
+/// Query parameter that carries the API key in [`AuthMode::QueryParam`]
+/// URLs and in `next_url` links.
+pub(crate) const API_KEY_PARAM: &str = "apiKey";
 
 /// Wrapper for API key with secure handling.
 ///
@@ -100,6 +105,70 @@ pub enum AuthMode {
     QueryParam,
 }
 
+/// Copy of `url` with the value of its `apiKey` query parameter replaced by
+/// `***`.
+///
+/// Use this before logging or displaying a URL that may carry an API key,
+/// such as a `next_url` from a paginated response.
+///
+/// # Example
+///
+/// ```
+/// use massive_rs::auth::redact_url;
+/// use url::Url;
+///
+/// let url = Url::parse("https://api.massive.com/v3/trades?cursor=abc&apiKey=secret").unwrap();
+/// assert_eq!(
+///     redact_url(&url).as_str(),
+///     "https://api.massive.com/v3/trades?cursor=abc&apiKey=***"
+/// );
+/// ```
+pub fn redact_url(url: &Url) -> Url {
+    let mut url = url.clone();
+    replace_api_key(&mut url, Some("***"));
+    url
+}
+
+/// Remove the `apiKey` query parameter from `url`.
+pub(crate) fn strip_api_key(url: &mut Url) {
+    replace_api_key(url, None);
+}
+
+/// Redact the API key from the URL attached to a transport error.
+pub(crate) fn redact_error(mut error: reqwest::Error) -> reqwest::Error {
+    if let Some(url) = error.url_mut() {
+        replace_api_key(url, Some("***"));
+    }
+    error
+}
+
+/// Replace the value of every `apiKey` parameter, or remove the parameter.
+///
+/// URLs without the parameter are left untouched, so their encoding is
+/// preserved.
+fn replace_api_key(url: &mut Url, replacement: Option<&str>) {
+    let is_key = |name: &str| name.eq_ignore_ascii_case(API_KEY_PARAM);
+    if !url.query_pairs().any(|(name, _)| is_key(&name)) {
+        return;
+    }
+
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter_map(|(name, value)| {
+            if is_key(&name) {
+                replacement.map(|r| (name.into_owned(), r.to_string()))
+            } else {
+                Some((name.into_owned(), value.into_owned()))
+            }
+        })
+        .collect();
+    if pairs.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,6 +203,40 @@ mod tests {
         assert_eq!(key1.expose(), key2.expose());
     }
 
+    #[test]
+    fn test_redact_url() {
+        let url = Url::parse(
+            "https://api.massive.com/v3/trades/AAPL?cursor=abc%2B1&apiKey=secret&limit=10",
+        )
+        .unwrap();
+        let redacted = redact_url(&url);
+        assert!(!redacted.as_str().contains("secret"));
+        assert_eq!(
+            redacted.query_pairs().collect::<Vec<_>>(),
+            vec![
+                ("cursor".into(), "abc+1".into()),
+                ("apiKey".into(), "***".into()),
+                ("limit".into(), "10".into())
+            ]
+        );
+
+        // URLs without a key keep their exact encoding
+        let plain = Url::parse("https://api.massive.com/v3/trades?cursor=a%2Fb").unwrap();
+        assert_eq!(redact_url(&plain), plain);
+    }
+
+    #[test]
+    fn test_strip_api_key() {
+        let mut url =
+            Url::parse("https://api.massive.com/v3/trades?cursor=abc&APIKEY=secret").unwrap();
+        strip_api_key(&mut url);
+        assert_eq!(url.as_str(), "https://api.massive.com/v3/trades?cursor=abc");
+
+        let mut only_key = Url::parse("https://api.massive.com/v3/trades?apiKey=secret").unwrap();
+        strip_api_key(&mut only_key);
+        assert_eq!(only_key.as_str(), "https://api.massive.com/v3/trades");
+    }
+
     #[test]
     fn test_auth_mode_default() {
         let mode = AuthMode::default();
@@ -0,0 +1,314 @@
+//! Blocking (synchronous) REST client.
+//!
+//! This module wraps the async [`rest::RestClient`](crate::rest::RestClient)
+//! for use from non-async code such as scripts, notebooks, and CLIs. Each
+//! client owns a small tokio runtime and blocks the calling thread until a
+//! request completes.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use massive_rs::blocking::RestClient;
+//! use massive_rs::rest::endpoints::{GetAggsRequest, Timespan};
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = RestClient::from_api_key(std::env::var("MASSIVE_API_KEY")?)?;
+//!
+//!     let request = GetAggsRequest::new("AAPL")
+//!         .multiplier(1)
+//!         .timespan(Timespan::Day)
+//!         .from("2024-01-01")
+//!         .to("2024-01-31");
+//!
+//!     for bar in client.stream(request) {
+//!         let bar = bar?;
+//!         println!("{}: {}", bar.timestamp, bar.close);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! # Panics
+//!
+//! Like other blocking wrappers over async code, these methods must not be
+//! called from within an async runtime: doing so panics. Use the async
+//! client there instead.
+
+use crate::config::{PaginationMode, RestConfig};
+use crate::error::MassiveError;
+use crate::metrics::StatsSnapshot;
+use crate::rest::{
+    self, Page, PageCheckpoint, PageStream, PaginatableRequest, RequestPriority, ResponseStream,
+    RestRequest,
+};
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// Blocking client for the Massive REST API.
+///
+/// The client is cheap to clone; clones share the connection pool, rate
+/// limiter, statistics, and runtime.
+#[derive(Clone)]
+pub struct RestClient {
+    inner: rest::RestClient,
+    runtime: Arc<Runtime>,
+}
+
+impl RestClient {
+    /// Create a new blocking client with the given configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client or the runtime cannot be created.
+    pub fn new(config: RestConfig) -> Result<Self, MassiveError> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("massive-blocking")
+            .enable_all()
+            .build()
+            .map_err(MassiveError::Runtime)?;
+
+        Ok(Self {
+            inner: rest::RestClient::new(config)?,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Create a new blocking client with just an API key.
+    ///
+    /// Uses default configuration for all other settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client or the runtime cannot be created.
+    pub fn from_api_key(key: impl Into<String>) -> Result<Self, MassiveError> {
+        Self::new(RestConfig::new(key))
+    }
+
+    /// Get a reference to the underlying configuration.
+    pub fn config(&self) -> &RestConfig {
+        self.inner.config()
+    }
+
+    /// Get a snapshot of request statistics.
+    pub fn stats(&self) -> StatsSnapshot {
+        self.inner.stats()
+    }
+
+    /// Get the async client this client wraps.
+    pub fn as_async(&self) -> &rest::RestClient {
+        &self.inner
+    }
+
+    /// Execute a typed request, blocking until the response is parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the response cannot be parsed.
+    pub fn execute<R>(&self, req: R) -> Result<R::Response, MassiveError>
+    where
+        R: RestRequest,
+    {
+        self.runtime.block_on(self.inner.execute(req))
+    }
+
+    /// Execute a typed request with a scheduling priority.
+    ///
+    /// See [`rest::RestClient::execute_with_priority`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the response cannot be parsed.
+    pub fn execute_with_priority<R>(
+        &self,
+        req: R,
+        priority: RequestPriority,
+    ) -> Result<R::Response, MassiveError>
+    where
+        R: RestRequest,
+    {
+        self.runtime
+            .block_on(self.inner.execute_with_priority(req, priority))
+    }
+
+    /// Iterate over paginated results.
+    ///
+    /// Pages are fetched lazily as the iterator advances, using the
+    /// configured pagination mode.
+    pub fn stream<R>(&self, req: R) -> PageIter<R>
+    where
+        R: PaginatableRequest + Unpin + Send + 'static,
+        R::Response: DeserializeOwned + Send + 'static,
+        R::Item: Unpin,
+    {
+        self.stream_with_mode(req, self.config().pagination)
+    }
+
+    /// Iterate over paginated results with a custom pagination mode.
+    pub fn stream_with_mode<R>(&self, req: R, mode: PaginationMode) -> PageIter<R>
+    where
+        R: PaginatableRequest + Unpin + Send + 'static,
+        R::Response: DeserializeOwned + Send + 'static,
+        R::Item: Unpin,
+    {
+        PageIter {
+            stream: PageStream::new(self.inner.clone(), req, mode),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Iterate over paginated results from a saved [`PageCheckpoint`].
+    ///
+    /// See [`rest::RestClient::resume_stream`].
+    pub fn resume_stream<R>(&self, req: R, checkpoint: PageCheckpoint) -> PageIter<R>
+    where
+        R: PaginatableRequest + Unpin + Send + 'static,
+        R::Response: DeserializeOwned + Send + 'static,
+        R::Item: Unpin,
+    {
+        PageIter {
+            stream: self.inner.resume_stream(req, checkpoint),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Iterate over whole pages of a paginated request.
+    ///
+    /// See [`rest::RestClient::stream_pages`].
+    pub fn stream_pages<R>(&self, req: R) -> ResponseIter<R>
+    where
+        R: PaginatableRequest + Unpin + Send + 'static,
+        R::Response: DeserializeOwned + Send + 'static,
+    {
+        self.stream_pages_with_mode(req, self.config().pagination)
+    }
+
+    /// Iterate over whole pages of a paginated request with a custom
+    /// pagination mode.
+    pub fn stream_pages_with_mode<R>(&self, req: R, mode: PaginationMode) -> ResponseIter<R>
+    where
+        R: PaginatableRequest + Unpin + Send + 'static,
+        R::Response: DeserializeOwned + Send + 'static,
+    {
+        ResponseIter {
+            stream: self.inner.stream_pages_with_mode(req, mode),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+impl std::fmt::Debug for RestClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RestClient")
+            .field("config", self.config())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Iterator over paginated results.
+///
+/// The blocking counterpart of [`PageStream`]: each call to `next` blocks
+/// until the next item is available, fetching a new page when needed.
+pub struct PageIter<R>
+where
+    R: PaginatableRequest,
+    R::Response: DeserializeOwned + Send + 'static,
+{
+    stream: PageStream<R>,
+    runtime: Arc<Runtime>,
+}
+
+impl<R> PageIter<R>
+where
+    R: PaginatableRequest + Unpin + Send + 'static,
+    R::Response: DeserializeOwned + Send + 'static,
+    R::Item: Unpin,
+{
+    /// Collect all remaining items into a vector.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered.
+    ///
+    /// # Warning
+    ///
+    /// This loads all data into memory. For large result sets,
+    /// prefer iterating directly.
+    pub fn collect_all(self) -> Result<Vec<R::Item>, MassiveError> {
+        self.collect()
+    }
+
+    /// Position of the iterator after the last item it returned.
+    ///
+    /// See [`PageStream::checkpoint`].
+    pub fn checkpoint(&self) -> PageCheckpoint {
+        self.stream.checkpoint()
+    }
+}
+
+impl<R> Iterator for PageIter<R>
+where
+    R: PaginatableRequest + Unpin + Send + 'static,
+    R::Response: DeserializeOwned + Send + 'static,
+    R::Item: Unpin,
+{
+    type Item = Result<R::Item, MassiveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+/// Iterator over whole pages of a paginated request.
+///
+/// The blocking counterpart of [`ResponseStream`].
+pub struct ResponseIter<R>
+where
+    R: PaginatableRequest,
+    R::Response: DeserializeOwned + Send + 'static,
+{
+    stream: ResponseStream<R>,
+    runtime: Arc<Runtime>,
+}
+
+impl<R> Iterator for ResponseIter<R>
+where
+    R: PaginatableRequest + Unpin + Send + 'static,
+    R::Response: DeserializeOwned + Send + 'static,
+{
+    type Item = Result<Page<R::Response>, MassiveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_creation() {
+        let client = RestClient::from_api_key("test-key").unwrap();
+        assert_eq!(client.config().api_key.expose(), "test-key");
+        assert_eq!(client.stats().requests_sent, 0);
+    }
+
+    #[test]
+    fn test_clones_share_runtime() {
+        let client = RestClient::from_api_key("test-key").unwrap();
+        let clone = client.clone();
+        assert!(Arc::ptr_eq(&client.runtime, &clone.runtime));
+    }
+
+    #[test]
+    fn test_invalid_config_rejected() {
+        let config = RestConfig::new("test-key").with_max_in_flight(0);
+        assert!(matches!(
+            RestClient::new(config),
+            Err(MassiveError::InvalidArgument(_))
+        ));
+    }
+}
@@ -5,6 +5,10 @@
 //! mode, and pagination behavior.
 
 use crate::auth::{ApiKey, AuthMode};
+use crate::metrics::{MetricsSink, NoopMetrics};
+#[cfg(feature = "ws")]
+use crate::rest::RestClient;
+use std::sync::Arc;
 use std::time::Duration;
 use url::Url;
 
@@ -35,7 +39,7 @@ pub const DEFAULT_WS_DELAYED_URL: &str = "wss://delayed.massive.com";
 ///     ..Default::default()
 /// };
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct RestConfig {
     /// Base URL for REST API (default: <https://api.massive.com>).
     pub base_url: Url,
@@ -61,11 +65,41 @@ pub struct RestConfig {
     /// Custom User-Agent string.
     pub user_agent: Option<String>,
 
-    /// Maximum retry attempts for transient errors.
+    /// Retry behavior for transient errors and rate limits.
+    pub retry_policy: RetryPolicy,
+
+    /// Maximum attempts per request.
+    ///
+    /// If changed from its default of 3, it overrides
+    /// [`RetryPolicy::max_attempts`] when the client is created.
+    #[deprecated(note = "use `retry_policy.max_attempts` instead")]
     pub max_retries: u32,
+
+    /// Client-side rate limit (None = unlimited).
+    ///
+    /// Requests wait for the limiter instead of failing, and all clones of
+    /// a client share it.
+    pub rate_limit: Option<RateLimitConfig>,
+
+    /// Maximum concurrent requests (None = unlimited).
+    ///
+    /// Requests beyond the limit queue by
+    /// [`RequestPriority`](crate::rest::RequestPriority).
+    pub max_in_flight: Option<usize>,
+
+    /// Page prefetching for paginated streams (None = fetch each page only
+    /// once the previous one has been consumed).
+    pub prefetch: Option<PrefetchConfig>,
+
+    /// Metrics sink for request latency, retries, and errors.
+    pub metrics: Arc<dyn MetricsSink>,
 }
 
+/// Default of the deprecated [`RestConfig::max_retries`].
+pub(crate) const DEFAULT_MAX_RETRIES: u32 = 3;
+
 impl Default for RestConfig {
+    #[allow(deprecated)]
     fn default() -> Self {
         Self {
             base_url: Url::parse(DEFAULT_REST_URL).expect("default URL is valid"),
@@ -76,11 +110,35 @@ impl Default for RestConfig {
             pagination: PaginationMode::Auto,
             trace: false,
             user_agent: None,
-            max_retries: 3,
+            retry_policy: RetryPolicy::default(),
+            max_retries: DEFAULT_MAX_RETRIES,
+            rate_limit: None,
+            max_in_flight: None,
+            prefetch: None,
+            metrics: Arc::new(NoopMetrics),
         }
     }
 }
 
+impl std::fmt::Debug for RestConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RestConfig")
+            .field("base_url", &self.base_url)
+            .field("api_key", &self.api_key)
+            .field("auth_mode", &self.auth_mode)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("request_timeout", &self.request_timeout)
+            .field("pagination", &self.pagination)
+            .field("trace", &self.trace)
+            .field("user_agent", &self.user_agent)
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limit", &self.rate_limit)
+            .field("max_in_flight", &self.max_in_flight)
+            .field("prefetch", &self.prefetch)
+            .finish_non_exhaustive()
+    }
+}
+
 impl RestConfig {
     /// Create a new configuration with the given API key.
     pub fn new(api_key: impl Into<String>) -> Self {
@@ -131,13 +189,43 @@ impl RestConfig {
         self.user_agent = Some(user_agent.into());
         self
     }
+
+    /// Set the retry policy.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Set the client-side rate limit.
+    pub fn with_rate_limit(mut self, rate_limit: RateLimitConfig) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Set the maximum number of concurrent requests.
+    pub fn with_max_in_flight(mut self, max: usize) -> Self {
+        self.max_in_flight = Some(max);
+        self
+    }
+
+    /// Set page prefetching for paginated streams.
+    pub fn with_prefetch(mut self, prefetch: PrefetchConfig) -> Self {
+        self.prefetch = Some(prefetch);
+        self
+    }
+
+    /// Set the metrics sink.
+    pub fn with_metrics(mut self, metrics: Arc<dyn MetricsSink>) -> Self {
+        self.metrics = metrics;
+        self
+    }
 }
 
 /// WebSocket client configuration.
 ///
 /// This structure contains all settings for the WebSocket client,
 /// including feed type, market selection, timeouts, and reconnection behavior.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 #[cfg(feature = "ws")]
 pub struct WsConfig {
     /// Feed type (real-time vs delayed).
@@ -146,23 +234,43 @@ pub struct WsConfig {
     /// Market/asset class.
     pub market: Market,
 
+    /// Base URL override (default: derived from `feed`).
+    ///
+    /// Accepts `ws://` or `wss://` URLs, e.g. a staging proxy or a local
+    /// mock server. The market path is appended to this URL.
+    pub base_url: Option<Url>,
+
     /// API key for authentication.
     pub api_key: ApiKey,
 
-    /// Connection timeout.
+    /// Time allowed to open the socket and authenticate.
     pub connect_timeout: Duration,
 
-    /// Idle timeout before ping.
+    /// Close and reconnect if nothing is received for this long.
     pub idle_timeout: Duration,
 
     /// Ping interval for keepalive.
+    ///
+    /// A ping that is still unanswered when the next one is due closes the
+    /// connection and triggers a reconnect.
     pub ping_interval: Duration,
 
+    /// Time to wait for the server to confirm each topic of a subscribe or
+    /// unsubscribe request.
+    pub subscribe_timeout: Duration,
+
     /// Reconnection configuration.
     pub reconnect: ReconnectConfig,
 
     /// Dispatch/backpressure configuration.
     pub dispatch: DispatchConfig,
+
+    /// Metrics sink for message, drop, and reconnect counters.
+    pub metrics: Arc<dyn MetricsSink>,
+
+    /// Recover trades and aggregates missed during reconnects over REST
+    /// (disabled by default).
+    pub backfill: Option<BackfillConfig>,
 }
 
 #[cfg(feature = "ws")]
@@ -171,16 +279,39 @@ impl Default for WsConfig {
         Self {
             feed: Feed::RealTime,
             market: Market::Stocks,
+            base_url: None,
             api_key: ApiKey::from_env().unwrap_or_default(),
             connect_timeout: Duration::from_secs(10),
             idle_timeout: Duration::from_secs(30),
             ping_interval: Duration::from_secs(15),
+            subscribe_timeout: Duration::from_secs(10),
             reconnect: ReconnectConfig::default(),
             dispatch: DispatchConfig::default(),
+            metrics: Arc::new(NoopMetrics),
+            backfill: None,
         }
     }
 }
 
+#[cfg(feature = "ws")]
+impl std::fmt::Debug for WsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WsConfig")
+            .field("feed", &self.feed)
+            .field("market", &self.market)
+            .field("base_url", &self.base_url)
+            .field("api_key", &self.api_key)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("ping_interval", &self.ping_interval)
+            .field("subscribe_timeout", &self.subscribe_timeout)
+            .field("reconnect", &self.reconnect)
+            .field("dispatch", &self.dispatch)
+            .field("backfill", &self.backfill)
+            .finish_non_exhaustive()
+    }
+}
+
 #[cfg(feature = "ws")]
 impl WsConfig {
     /// Create a new configuration with the given API key.
@@ -203,13 +334,34 @@ impl WsConfig {
         self
     }
 
+    /// Set the base URL, overriding the feed's default host.
+    pub fn with_base_url(mut self, url: Url) -> Self {
+        self.base_url = Some(url);
+        self
+    }
+
+    /// Set the metrics sink.
+    pub fn with_metrics(mut self, metrics: Arc<dyn MetricsSink>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Enable gap recovery after reconnects.
+    pub fn with_backfill(mut self, backfill: BackfillConfig) -> Self {
+        self.backfill = Some(backfill);
+        self
+    }
+
     /// Build the WebSocket URL for this configuration.
     pub fn build_url(&self) -> String {
-        let host = match self.feed {
-            Feed::RealTime => "socket.massive.com",
-            Feed::Delayed => "delayed.massive.com",
+        let base = match &self.base_url {
+            Some(url) => url.as_str(),
+            None => match self.feed {
+                Feed::RealTime => DEFAULT_WS_REALTIME_URL,
+                Feed::Delayed => DEFAULT_WS_DELAYED_URL,
+            },
         };
-        format!("wss://{}/{}", host, self.market.as_path())
+        format!("{}/{}", base.trim_end_matches('/'), self.market.as_path())
     }
 }
 
@@ -276,6 +428,291 @@ pub enum PaginationMode {
     /// The client will stop fetching after the specified number of items
     /// have been retrieved, even if more pages are available.
     MaxItems(u64),
+
+    /// Stop after N pages.
+    ///
+    /// The client will fetch at most the specified number of pages,
+    /// following `next_url` links between them.
+    MaxPages(u64),
+
+    /// Stop requesting new pages once the duration has elapsed.
+    ///
+    /// The duration is measured from when the stream was created. A page
+    /// already requested when the deadline passes is still returned.
+    Deadline(Duration),
+}
+
+/// Retry policy for REST requests.
+///
+/// Only idempotent requests are retried. The delay before each retry is an
+/// exponential backoff with jitter, unless the server sent a `Retry-After`
+/// header (seconds or HTTP-date) and [`respect_retry_after`](Self::respect_retry_after)
+/// is set. Retrying stops once [`max_attempts`](Self::max_attempts) is
+/// reached or the next delay would exceed [`max_elapsed`](Self::max_elapsed).
+///
+/// # Example
+///
+/// ```
+/// use massive_rs::config::{RestConfig, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let config = RestConfig::new("your-api-key").with_retry_policy(RetryPolicy {
+///     max_attempts: 5,
+///     max_elapsed: Some(Duration::from_secs(120)),
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum attempts per request, including the first (1 = no retries).
+    pub max_attempts: u32,
+
+    /// HTTP status codes that are retried.
+    pub retry_statuses: Vec<u16>,
+
+    /// Retry when the connection cannot be established.
+    pub retry_connect_errors: bool,
+
+    /// Retry when the request times out.
+    pub retry_timeouts: bool,
+
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+
+    /// Maximum delay between retries.
+    pub max_backoff: Duration,
+
+    /// Backoff multiplier.
+    pub backoff_multiplier: f64,
+
+    /// Fraction of each backoff delay that is randomized (0.0 - 1.0).
+    ///
+    /// A delay `d` becomes a random value in `[d * (1 - jitter), d]`.
+    pub jitter: f64,
+
+    /// Maximum total time spent on a request including retries (None = unlimited).
+    pub max_elapsed: Option<Duration>,
+
+    /// Wait for the server's `Retry-After` instead of the backoff delay.
+    ///
+    /// The wait still counts toward `max_elapsed`; without a `max_elapsed`
+    /// it is capped at `max_backoff`.
+    pub respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            retry_statuses: vec![429, 502, 503, 504],
+            retry_connect_errors: true,
+            retry_timeouts: false,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            backoff_multiplier: 2.0,
+            jitter: 0.5,
+            max_elapsed: Some(Duration::from_secs(60)),
+            respect_retry_after: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create a policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Set the maximum number of attempts.
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// Set the maximum total time spent on a request.
+    pub fn with_max_elapsed(mut self, max_elapsed: Option<Duration>) -> Self {
+        self.max_elapsed = max_elapsed;
+        self
+    }
+
+    /// Check if a status code is retryable.
+    pub fn retries_status(&self, status: u16) -> bool {
+        self.retry_statuses.contains(&status)
+    }
+
+    /// Calculate the backoff delay (before jitter) for the given retry.
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let delay_ms = self.initial_backoff.as_millis() as f64
+            * self
+                .backoff_multiplier
+                .powi(attempt.saturating_sub(1) as i32);
+
+        Duration::from_millis(delay_ms.min(self.max_backoff.as_millis() as f64) as u64)
+    }
+
+    /// Decide the delay before the next attempt, or `None` to stop.
+    ///
+    /// `attempt` is the number of attempts made so far, `elapsed` the time
+    /// since the first one, and `sample` a random value in `[0, 1)` used
+    /// for jitter.
+    pub(crate) fn next_delay(
+        &self,
+        attempt: u32,
+        elapsed: Duration,
+        retry_after: Option<Duration>,
+        sample: f64,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        let delay = match retry_after {
+            Some(retry_after) if self.respect_retry_after => match self.max_elapsed {
+                Some(max) => retry_after.min(max),
+                None => retry_after.min(self.max_backoff),
+            },
+            _ => {
+                let base = self.delay_for_attempt(attempt);
+                base.mul_f64(1.0 - self.jitter.clamp(0.0, 1.0) * sample.clamp(0.0, 1.0))
+            }
+        };
+
+        match (self.max_elapsed, elapsed.checked_add(delay)) {
+            // Give up if the wait would overflow or exceed the budget
+            (_, None) => None,
+            (Some(max), Some(total)) if total > max => None,
+            _ => Some(delay),
+        }
+    }
+}
+
+/// Client-side rate limit for REST requests.
+///
+/// Implemented as a token bucket: up to `burst` requests can be sent at
+/// once, and tokens refill at `requests` per `interval`.
+///
+/// # Example
+///
+/// ```
+/// use massive_rs::config::{PlanTier, RateLimitConfig, RestConfig};
+///
+/// let config = RestConfig::new("your-api-key")
+///     .with_rate_limit(RateLimitConfig::for_plan(PlanTier::Basic));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    /// Requests allowed per interval.
+    pub requests: u32,
+
+    /// Refill interval.
+    pub interval: Duration,
+
+    /// Maximum requests sent back-to-back (bucket size).
+    pub burst: u32,
+}
+
+impl RateLimitConfig {
+    /// Create a limit of `requests` per `interval`, with a burst of the
+    /// same size.
+    pub fn new(requests: u32, interval: Duration) -> Self {
+        Self {
+            requests,
+            interval,
+            burst: requests,
+        }
+    }
+
+    /// Create a limit of `requests` per second.
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    /// Create a limit of `requests` per minute.
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(60))
+    }
+
+    /// Get the preset for a plan tier.
+    pub fn for_plan(tier: PlanTier) -> Self {
+        match tier {
+            // A burst of 1 keeps any 60s window within the plan limit
+            PlanTier::Basic | PlanTier::Starter => Self::per_minute(5).with_burst(1),
+            PlanTier::Developer | PlanTier::Advanced => Self::per_second(100),
+        }
+    }
+
+    /// Set the burst size.
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = burst;
+        self
+    }
+}
+
+/// Page prefetching for paginated streams.
+///
+/// With prefetching, a stream requests the next pages in the background
+/// while the consumer works through the current one, so the download
+/// overlaps with processing. Pages are still delivered in order, and an
+/// error is delivered after the pages fetched before it.
+///
+/// # Example
+///
+/// ```
+/// use massive_rs::config::{PrefetchConfig, RestConfig};
+///
+/// // Keep up to two pages, and at most 100,000 items, ready ahead
+/// let config = RestConfig::new("your-api-key")
+///     .with_prefetch(PrefetchConfig::new(2).with_max_buffered_items(100_000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrefetchConfig {
+    /// Pages fetched ahead of the page being consumed.
+    ///
+    /// Page requests run on their own tasks, but each one needs the
+    /// `next_url` of the page before it, so a further page is requested
+    /// the next time the stream is polled after its predecessor arrives.
+    pub pages: usize,
+
+    /// Maximum items held by the stream before prefetching pauses.
+    ///
+    /// Counts the items of prefetched pages plus those of the current page
+    /// not yet yielded. A page is always fetched when the consumer needs
+    /// one, so a single large page can exceed the limit.
+    pub max_buffered_items: usize,
+}
+
+impl PrefetchConfig {
+    /// Prefetch up to `pages` pages ahead, holding at most 50,000 items.
+    pub fn new(pages: usize) -> Self {
+        Self {
+            pages,
+            max_buffered_items: 50_000,
+        }
+    }
+
+    /// Set the maximum number of buffered items.
+    pub fn with_max_buffered_items(mut self, max: usize) -> Self {
+        self.max_buffered_items = max;
+        self
+    }
+}
+
+/// Massive.com subscription plan tier.
+///
+/// Used to pick a [`RateLimitConfig`] preset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanTier {
+    /// Free plan: 5 requests per minute
+    Basic,
+    /// Starter plan: 5 requests per minute
+    Starter,
+    /// Developer plan: unlimited (kept under 100 requests per second)
+    Developer,
+    /// Advanced plan: unlimited (kept under 100 requests per second)
+    Advanced,
 }
 
 /// Reconnection configuration for WebSocket clients.
@@ -291,7 +728,8 @@ pub struct ReconnectConfig {
     /// Maximum delay between retries.
     pub max_delay: Duration,
 
-    /// Maximum retry attempts (None = unlimited).
+    /// Maximum consecutive retry attempts (None = unlimited). The count
+    /// restarts once a reconnected session authenticates.
     pub max_retries: Option<u32>,
 
     /// Backoff multiplier.
@@ -337,6 +775,107 @@ impl ReconnectConfig {
     }
 }
 
+/// Gap recovery configuration for WebSocket clients.
+///
+/// While enabled, the client remembers the timestamp of the last trade and
+/// aggregate seen for each symbol, plus the trade sequence number. After a
+/// reconnect it fetches what was published in between through
+/// `GetTradesRequest` and `GetAggsRequest`, and delivers it on the event
+/// stream in batches with [`WsMessageBatch::backfilled`](crate::ws::WsMessageBatch::backfilled)
+/// set.
+///
+/// # Example
+///
+/// ```
+/// use massive_rs::config::{BackfillConfig, WsConfig};
+/// use massive_rs::rest::RestClient;
+///
+/// let rest = RestClient::from_api_key("your-api-key").unwrap();
+/// let config = WsConfig::new("your-api-key").with_backfill(BackfillConfig::new(rest));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg(feature = "ws")]
+pub struct BackfillConfig {
+    /// REST client used to fetch missed events.
+    pub rest: RestClient,
+
+    /// Longest gap to recover; events older than this before the reconnect
+    /// are not fetched.
+    pub max_gap: Duration,
+
+    /// Maximum events fetched per symbol and event type.
+    ///
+    /// Larger gaps recover only their oldest events and are reported in
+    /// [`ConnectionEvent::Backfilled`](crate::ws::ConnectionEvent::Backfilled).
+    pub max_events: u64,
+}
+
+#[cfg(feature = "ws")]
+impl BackfillConfig {
+    /// Create a configuration using `rest` with default limits.
+    pub fn new(rest: RestClient) -> Self {
+        Self {
+            rest,
+            max_gap: Duration::from_secs(300),
+            max_events: 50_000,
+        }
+    }
+}
+
+/// Journal configuration for recording WebSocket sessions.
+///
+/// Every text frame received by the client is appended, with its receive
+/// timestamp, to gzip-compressed JSON-lines files in `dir`. A new file is
+/// started once the current one holds `max_file_bytes` of uncompressed
+/// frames. Journals can be replayed with
+/// [`ReplayClient`](crate::ws::ReplayClient).
+///
+/// # Example
+///
+/// ```no_run
+/// use massive_rs::config::{RecorderConfig, WsConfig};
+/// use massive_rs::ws::WsClient;
+///
+/// let client = WsClient::new(WsConfig::new("your-api-key"))?
+///     .with_recorder(RecorderConfig::new("journals"));
+/// # Ok::<(), massive_rs::MassiveError>(())
+/// ```
+#[derive(Debug, Clone)]
+#[cfg(feature = "record")]
+pub struct RecorderConfig {
+    /// Directory for journal files, created if missing.
+    pub dir: std::path::PathBuf,
+
+    /// File name prefix.
+    pub prefix: String,
+
+    /// Uncompressed bytes per file before rotating.
+    pub max_file_bytes: u64,
+
+    /// Number of files to keep; the oldest are deleted on rotation
+    /// (None = keep all).
+    pub max_files: Option<usize>,
+
+    /// Frames that may wait for the writer thread. Frames received while
+    /// the queue is full are not recorded, and counted in
+    /// [`ClientStats::journal_dropped`](crate::metrics::ClientStats::journal_dropped).
+    pub queue_capacity: usize,
+}
+
+#[cfg(feature = "record")]
+impl RecorderConfig {
+    /// Create a configuration that writes to `dir` with default rotation.
+    pub fn new(dir: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            prefix: "massive-ws".into(),
+            max_file_bytes: 256 * 1024 * 1024,
+            max_files: None,
+            queue_capacity: 10_000,
+        }
+    }
+}
+
 /// Dispatch configuration for backpressure handling.
 #[derive(Debug, Clone)]
 #[cfg(feature = "ws")]
@@ -375,6 +914,15 @@ pub enum OverflowPolicy {
 
     /// Treat overflow as fatal error.
     ErrorAndClose,
+
+    /// Keep only the latest quote and aggregate per symbol.
+    ///
+    /// When the buffer is full, queued quotes and aggregates are replaced by
+    /// newer updates for the same event type and symbol. Trades and other
+    /// events are never dropped; if nothing can be conflated they are
+    /// appended to the newest queued batch, so the buffer may grow beyond
+    /// its capacity while the consumer lags.
+    ConflateLatest,
 }
 
 /// Fanout mode for multiple consumers.
@@ -420,6 +968,109 @@ mod tests {
         assert_eq!(config.user_agent, Some("my-app/1.0".to_string()));
     }
 
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy {
+            jitter: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(policy.delay_for_attempt(1), Duration::from_millis(100));
+        assert_eq!(policy.delay_for_attempt(2), Duration::from_millis(200));
+        assert_eq!(policy.delay_for_attempt(3), Duration::from_millis(400));
+        assert_eq!(policy.delay_for_attempt(20), Duration::from_secs(10));
+
+        let elapsed = Duration::ZERO;
+        assert_eq!(
+            policy.next_delay(1, elapsed, None, 0.9),
+            Some(Duration::from_millis(100))
+        );
+        // Attempts exhausted
+        assert_eq!(policy.next_delay(3, elapsed, None, 0.0), None);
+        assert_eq!(RetryPolicy::none().next_delay(1, elapsed, None, 0.0), None);
+    }
+
+    #[test]
+    fn test_retry_policy_jitter_and_budget() {
+        let policy = RetryPolicy {
+            jitter: 1.0,
+            max_elapsed: Some(Duration::from_secs(5)),
+            ..Default::default()
+        };
+
+        let delay = policy.next_delay(2, Duration::ZERO, None, 0.5).unwrap();
+        assert_eq!(delay, Duration::from_millis(100));
+
+        // Retry-After replaces the backoff delay
+        let retry_after = Some(Duration::from_secs(2));
+        assert_eq!(
+            policy.next_delay(1, Duration::ZERO, retry_after, 0.5),
+            retry_after
+        );
+        // ...unless it would exceed the total budget
+        assert_eq!(
+            policy.next_delay(1, Duration::from_secs(4), retry_after, 0.5),
+            None
+        );
+
+        let ignore = RetryPolicy {
+            respect_retry_after: false,
+            jitter: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            ignore.next_delay(1, Duration::ZERO, retry_after, 0.5),
+            Some(Duration::from_millis(100))
+        );
+    }
+
+    #[test]
+    fn test_retry_policy_huge_retry_after() {
+        let huge = Some(Duration::from_secs(u64::MAX));
+
+        // Capped by the budget, and never overflowing
+        let policy = RetryPolicy::default();
+        assert_eq!(
+            policy.next_delay(1, Duration::ZERO, huge, 0.5),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            policy.next_delay(1, Duration::from_secs(1), huge, 0.5),
+            None
+        );
+
+        // Without a budget, capped at the maximum backoff
+        let unbounded = RetryPolicy::default().with_max_elapsed(None);
+        assert_eq!(
+            unbounded.next_delay(1, Duration::from_secs(1), huge, 0.5),
+            Some(Duration::from_secs(10))
+        );
+        let overflow = RetryPolicy {
+            max_backoff: Duration::MAX,
+            max_elapsed: None,
+            ..Default::default()
+        };
+        assert_eq!(
+            overflow.next_delay(1, Duration::from_secs(1), huge, 0.5),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_presets() {
+        let basic = RateLimitConfig::for_plan(PlanTier::Basic);
+        assert_eq!(basic.requests, 5);
+        assert_eq!(basic.interval, Duration::from_secs(60));
+        assert_eq!(basic.burst, 1);
+
+        let developer = RateLimitConfig::for_plan(PlanTier::Developer);
+        assert_eq!(developer, RateLimitConfig::per_second(100));
+        assert_eq!(developer.burst, 100);
+
+        let config = RestConfig::new("key").with_rate_limit(basic);
+        assert_eq!(config.rate_limit, Some(basic));
+        assert_eq!(RestConfig::default().rate_limit, None);
+    }
+
     #[test]
     fn test_market_as_path() {
         assert_eq!(Market::Stocks.as_path(), "stocks");
@@ -450,6 +1101,24 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "ws")]
+    #[test]
+    fn test_ws_config_base_url_override() {
+        let config = WsConfig::default()
+            .with_base_url(Url::parse("ws://127.0.0.1:9001").unwrap())
+            .with_market(Market::Options);
+        assert_eq!(config.build_url(), "ws://127.0.0.1:9001/options");
+
+        // Feed is ignored when a base URL is set
+        let proxied = WsConfig::default()
+            .with_feed(Feed::Delayed)
+            .with_base_url(Url::parse("wss://staging.example.com/proxy/").unwrap());
+        assert_eq!(
+            proxied.build_url(),
+            "wss://staging.example.com/proxy/stocks"
+        );
+    }
+
     #[cfg(feature = "ws")]
     #[test]
     fn test_reconnect_delay_calculation() {
@@ -18,8 +18,10 @@ use thiserror::Error;
 #[derive(Debug, Error)]
 pub enum MassiveError {
     /// HTTP transport error from reqwest.
+    ///
+    /// Any API key in the error's URL is redacted.
     #[error("Transport error: {0}")]
-    Transport(#[from] reqwest::Error),
+    Transport(reqwest::Error),
 
     /// Request timed out.
     #[error("Request timed out")]
@@ -88,6 +90,22 @@ pub enum MassiveError {
     /// URL parsing error.
     #[error("URL error: {0}")]
     Url(#[from] url::ParseError),
+
+    /// The blocking client's runtime could not be created.
+    #[cfg(feature = "blocking")]
+    #[error("Runtime error: {0}")]
+    Runtime(#[source] std::io::Error),
+
+    /// A session journal could not be created or read.
+    #[cfg(feature = "record")]
+    #[error("Journal error: {0}")]
+    Journal(#[source] std::io::Error),
+}
+
+impl From<reqwest::Error> for MassiveError {
+    fn from(error: reqwest::Error) -> Self {
+        MassiveError::Transport(crate::auth::redact_error(error))
+    }
 }
 
 /// WebSocket-specific errors.
@@ -110,6 +128,14 @@ pub enum WsError {
     #[error("Server disconnected")]
     Disconnected,
 
+    /// Nothing was received within the configured idle timeout.
+    #[error("Connection idle timeout")]
+    IdleTimeout,
+
+    /// A keepalive ping was not answered in time.
+    #[error("Pong not received before next ping")]
+    PongTimeout,
+
     /// Backpressure overflow (buffer full).
     ///
     /// This error occurs when the message consumer cannot keep up
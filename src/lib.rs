@@ -22,6 +22,7 @@
 //! - `ws`: Enable WebSocket client support
 //! - `simd-json`: Use SIMD-accelerated JSON parsing
 //! - `decimal`: Use exact decimal arithmetic for prices
+//! - `blocking`: Enable the synchronous `blocking::RestClient`
 //!
 //! # Quick Start
 //!
@@ -104,21 +105,36 @@ pub mod util;
 
 pub mod rest;
 
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub mod blocking;
+
 #[cfg(feature = "ws")]
 #[cfg_attr(docsrs, doc(cfg(feature = "ws")))]
 pub mod ws;
 
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+
 pub mod models;
 
 // Re-export commonly used types at crate root
 pub use auth::{ApiKey, AuthMode};
-pub use config::{PaginationMode, RestConfig};
+pub use config::{PaginationMode, PlanTier, RateLimitConfig, RestConfig, RetryPolicy};
 pub use error::{MassiveError, Result};
 pub use metrics::{ClientStats, MetricsSink, NoopMetrics, StatsSnapshot, TracingMetrics};
+pub use models::Price;
 pub use parse::parse_ws_events;
 
 #[cfg(feature = "ws")]
-pub use config::{DispatchConfig, Feed, Market, OverflowPolicy, ReconnectConfig, WsConfig};
+pub use config::{
+    BackfillConfig, DispatchConfig, FanoutMode, Feed, Market, OverflowPolicy, ReconnectConfig,
+    WsConfig,
+};
+
+#[cfg(feature = "record")]
+pub use config::RecorderConfig;
 
 #[cfg(feature = "ws")]
 pub use error::WsError;
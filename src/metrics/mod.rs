@@ -28,9 +28,52 @@
 //! }
 //! ```
 
+#[cfg(feature = "prometheus")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prometheus")))]
+pub mod prometheus;
+
+#[cfg(feature = "prometheus")]
+pub use prometheus::{MetricsEndpoint, PrometheusMetrics};
+
+use std::borrow::Cow;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Instant;
 
+/// Metric names emitted by the REST and WebSocket clients.
+pub mod names {
+    /// REST request latency in microseconds (tags: `endpoint`).
+    pub const REST_REQUEST_LATENCY: &str = "massive_rest_request_latency_us";
+    /// REST responses received (tags: `endpoint`, `status`).
+    pub const REST_REQUESTS: &str = "massive_rest_requests_total";
+    /// REST requests retried after a transient error (tags: `endpoint`).
+    pub const REST_RETRIES: &str = "massive_rest_retries_total";
+    /// REST responses with status 429 (tags: `endpoint`).
+    pub const REST_RATE_LIMITED: &str = "massive_rest_rate_limited_total";
+    /// REST requests that failed at the transport level or with a non-2xx status (tags: `endpoint`).
+    pub const REST_REQUEST_ERRORS: &str = "massive_rest_request_errors_total";
+    /// Time spent waiting for the client-side rate limiter, in microseconds (tags: `endpoint`).
+    pub const REST_RATE_LIMIT_WAIT: &str = "massive_rest_rate_limit_wait_us";
+    /// Time spent queued for an in-flight slot, in microseconds (tags: `endpoint`, `priority`).
+    pub const REST_QUEUE_WAIT: &str = "massive_rest_queue_wait_us";
+    /// REST response body bytes received (tags: `endpoint`).
+    pub const REST_BYTES_RECEIVED: &str = "massive_rest_bytes_received_total";
+    /// REST responses that failed to deserialize (tags: `endpoint`).
+    pub const REST_PARSE_ERRORS: &str = "massive_rest_parse_errors_total";
+
+    /// WebSocket events received (tags: `market`).
+    pub const WS_MESSAGES_RECEIVED: &str = "massive_ws_messages_received_total";
+    /// WebSocket text frame bytes received (tags: `market`).
+    pub const WS_BYTES_RECEIVED: &str = "massive_ws_bytes_received_total";
+    /// WebSocket frames and events that could not be interpreted (tags: `market`).
+    pub const WS_PARSE_ERRORS: &str = "massive_ws_parse_errors_total";
+    /// WebSocket events dropped due to backpressure (tags: `market`).
+    pub const WS_MESSAGES_DROPPED: &str = "massive_ws_messages_dropped_total";
+    /// Successful WebSocket reconnections (tags: `market`).
+    pub const WS_RECONNECTS: &str = "massive_ws_reconnects_total";
+    /// Batches waiting in the deepest subscriber queue (tags: `market`).
+    pub const WS_QUEUE_DEPTH: &str = "massive_ws_queue_depth";
+}
+
 /// Trait for custom metrics collection.
 ///
 /// Implement this trait to integrate with your metrics system
@@ -115,6 +158,10 @@ pub struct ClientStats {
     pub request_errors: AtomicU64,
     /// Total rate limit hits
     pub rate_limits: AtomicU64,
+    /// Total request retries
+    pub retries: AtomicU64,
+    /// Total frames not recorded because the journal writer fell behind
+    pub journal_dropped: AtomicU64,
 }
 
 impl ClientStats {
@@ -171,6 +218,18 @@ impl ClientStats {
         self.rate_limits.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Increment retries counter.
+    #[inline]
+    pub fn inc_retries(&self) {
+        self.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increment journal dropped frames counter.
+    #[inline]
+    pub fn inc_journal_dropped(&self) {
+        self.journal_dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
     /// Get current snapshot of all statistics.
     pub fn snapshot(&self) -> StatsSnapshot {
         StatsSnapshot {
@@ -182,6 +241,8 @@ impl ClientStats {
             requests_sent: self.requests_sent.load(Ordering::Relaxed),
             request_errors: self.request_errors.load(Ordering::Relaxed),
             rate_limits: self.rate_limits.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            journal_dropped: self.journal_dropped.load(Ordering::Relaxed),
         }
     }
 
@@ -195,6 +256,8 @@ impl ClientStats {
         self.requests_sent.store(0, Ordering::Relaxed);
         self.request_errors.store(0, Ordering::Relaxed);
         self.rate_limits.store(0, Ordering::Relaxed);
+        self.retries.store(0, Ordering::Relaxed);
+        self.journal_dropped.store(0, Ordering::Relaxed);
     }
 }
 
@@ -217,19 +280,23 @@ pub struct StatsSnapshot {
     pub request_errors: u64,
     /// Total rate limit hits
     pub rate_limits: u64,
+    /// Total request retries
+    pub retries: u64,
+    /// Total frames not recorded because the journal writer fell behind
+    pub journal_dropped: u64,
 }
 
 /// Timer for measuring operation latencies.
 ///
-/// Records the duration between creation and drop.
-pub struct LatencyTimer<'a, M: MetricsSink> {
+/// Records the duration between creation and [`stop`](Self::stop).
+pub struct LatencyTimer<'a, M: MetricsSink + ?Sized> {
     metrics: &'a M,
     name: &'static str,
-    tags: Vec<(&'static str, &'static str)>,
+    tags: Vec<(&'static str, Cow<'static, str>)>,
     start: Instant,
 }
 
-impl<'a, M: MetricsSink> LatencyTimer<'a, M> {
+impl<'a, M: MetricsSink + ?Sized> LatencyTimer<'a, M> {
     /// Create a new latency timer.
     pub fn new(metrics: &'a M, name: &'static str) -> Self {
         Self {
@@ -241,8 +308,8 @@ impl<'a, M: MetricsSink> LatencyTimer<'a, M> {
     }
 
     /// Add a tag to the timer.
-    pub fn tag(mut self, key: &'static str, value: &'static str) -> Self {
-        self.tags.push((key, value));
+    pub fn tag(mut self, key: &'static str, value: impl Into<Cow<'static, str>>) -> Self {
+        self.tags.push((key, value.into()));
         self
     }
 
@@ -250,8 +317,7 @@ impl<'a, M: MetricsSink> LatencyTimer<'a, M> {
     pub fn stop(self) {
         let elapsed = self.start.elapsed();
         let micros = elapsed.as_micros() as u64;
-        let tags: Vec<(&'static str, &str)> =
-            self.tags.iter().map(|(k, v)| (*k, *v as &str)).collect();
+        let tags: Vec<(&'static str, &str)> = self.tags.iter().map(|(k, v)| (*k, &**v)).collect();
         self.metrics.timing(self.name, micros, &tags);
     }
 }
@@ -301,6 +367,35 @@ mod tests {
         assert_eq!(snapshot.messages_dropped, 0);
     }
 
+    #[test]
+    fn test_latency_timer_dyn_sink() {
+        use std::sync::Mutex;
+
+        #[derive(Default)]
+        struct Recorder(Mutex<Vec<(&'static str, String)>>);
+
+        impl MetricsSink for Recorder {
+            fn counter(&self, _: &'static str, _: u64, _: &[(&'static str, &str)]) {}
+            fn gauge(&self, _: &'static str, _: i64, _: &[(&'static str, &str)]) {}
+            fn histogram(&self, name: &'static str, _: f64, tags: &[(&'static str, &str)]) {
+                self.0.lock().unwrap().push((name, tags[0].1.to_string()));
+            }
+        }
+
+        let recorder = Arc::new(Recorder::default());
+        let sink: Arc<dyn MetricsSink> = recorder.clone();
+        let path = String::from("/v2/aggs");
+        LatencyTimer::new(&*sink, names::REST_REQUEST_LATENCY)
+            .tag("path", path)
+            .stop();
+
+        let recorded = recorder.0.lock().unwrap();
+        assert_eq!(
+            recorded.as_slice(),
+            &[(names::REST_REQUEST_LATENCY, "/v2/aggs".to_string())]
+        );
+    }
+
     #[test]
     fn test_client_stats_thread_safe() {
         let stats = Arc::new(ClientStats::new());
@@ -0,0 +1,491 @@
+//! Prometheus text exposition.
+//!
+//! [`PrometheusMetrics`] is a [`MetricsSink`] that aggregates everything the
+//! clients emit and renders it in the Prometheus text format. It can also
+//! serve the output on a small local HTTP endpoint.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use massive_rs::config::RestConfig;
+//! use massive_rs::metrics::PrometheusMetrics;
+//! use massive_rs::rest::RestClient;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let prometheus = Arc::new(PrometheusMetrics::new());
+//! let client = RestClient::new(RestConfig::new("your-api-key").with_metrics(prometheus.clone()))?;
+//!
+//! // Include the client's ClientStats counters in the output
+//! let stats_client = client.clone();
+//! prometheus.register_stats("rest", move || stats_client.stats());
+//!
+//! // Scrape http://127.0.0.1:9898/metrics
+//! let endpoint = prometheus.serve("127.0.0.1:9898".parse()?).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{MetricsSink, StatsSnapshot};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+/// Default histogram buckets, suited to latencies in microseconds.
+pub const DEFAULT_BUCKETS: &[f64] = &[
+    100.0,
+    250.0,
+    500.0,
+    1_000.0,
+    2_500.0,
+    5_000.0,
+    10_000.0,
+    25_000.0,
+    50_000.0,
+    100_000.0,
+    250_000.0,
+    500_000.0,
+    1_000_000.0,
+    2_500_000.0,
+    5_000_000.0,
+    10_000_000.0,
+];
+
+/// Content type of the Prometheus text format.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Pause before accepting again after the metrics listener fails.
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Series labels, in the order they were emitted.
+type Labels = Vec<(&'static str, String)>;
+
+/// Callback producing a [`StatsSnapshot`] at render time.
+type StatsSource = Box<dyn Fn() -> StatsSnapshot + Send + Sync>;
+
+/// Reads one counter from a [`StatsSnapshot`].
+type StatsField = fn(&StatsSnapshot) -> u64;
+
+/// Metrics sink rendering in Prometheus text format.
+///
+/// Counters and gauges are stored per name and label set. Histograms (and
+/// timings from [`LatencyTimer`](super::LatencyTimer)) are bucketed using
+/// [`DEFAULT_BUCKETS`] unless overridden with
+/// [`with_buckets`](Self::with_buckets).
+pub struct PrometheusMetrics {
+    buckets: Vec<f64>,
+    registry: Mutex<Registry>,
+    stats: Mutex<Vec<(String, StatsSource)>>,
+}
+
+#[derive(Default)]
+struct Registry {
+    counters: BTreeMap<&'static str, BTreeMap<Labels, u64>>,
+    gauges: BTreeMap<&'static str, BTreeMap<Labels, i64>>,
+    histograms: BTreeMap<&'static str, BTreeMap<Labels, Histogram>>,
+}
+
+#[derive(Debug, Clone)]
+struct Histogram {
+    /// Per-bucket (non-cumulative) counts
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl PrometheusMetrics {
+    /// Create an empty sink with the default buckets.
+    pub fn new() -> Self {
+        Self::with_buckets(DEFAULT_BUCKETS.to_vec())
+    }
+
+    /// Create an empty sink with custom histogram bucket bounds.
+    ///
+    /// Bounds are sorted; the `+Inf` bucket is always added.
+    pub fn with_buckets(mut buckets: Vec<f64>) -> Self {
+        buckets.retain(|b| b.is_finite());
+        buckets.sort_by(|a, b| a.total_cmp(b));
+        buckets.dedup();
+        Self {
+            buckets,
+            registry: Mutex::new(Registry::default()),
+            stats: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Include a client's [`ClientStats`](super::ClientStats) in the output.
+    ///
+    /// `source` is called on every [`render`](Self::render), and each
+    /// counter is exported as `massive_client_<counter>_total` with a
+    /// `client` label set to `client`.
+    pub fn register_stats<F>(&self, client: impl Into<String>, source: F)
+    where
+        F: Fn() -> StatsSnapshot + Send + Sync + 'static,
+    {
+        lock(&self.stats).push((client.into(), Box::new(source)));
+    }
+
+    /// Render all metrics in Prometheus text format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        {
+            let registry = lock(&self.registry);
+            for (name, series) in &registry.counters {
+                write_series(&mut out, name, "counter", series);
+            }
+            for (name, series) in &registry.gauges {
+                write_series(&mut out, name, "gauge", series);
+            }
+            for (name, series) in &registry.histograms {
+                let _ = writeln!(out, "# TYPE {} histogram", name);
+                for (labels, hist) in series {
+                    self.write_histogram(&mut out, name, labels, hist);
+                }
+            }
+        }
+
+        let stats = lock(&self.stats);
+        if !stats.is_empty() {
+            let snapshots: Vec<_> = stats.iter().map(|(c, f)| (c.as_str(), f())).collect();
+            for (name, field) in STATS_COUNTERS {
+                let _ = writeln!(out, "# TYPE {} counter", name);
+                for (client, snapshot) in &snapshots {
+                    let _ = writeln!(
+                        out,
+                        "{}{{client=\"{}\"}} {}",
+                        name,
+                        escape(client),
+                        field(snapshot)
+                    );
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Serve [`render`](Self::render) over HTTP on `addr`.
+    ///
+    /// Every `GET` request is answered with the current metrics; the path is
+    /// not checked, so `/metrics` works as usual. The endpoint stops when
+    /// the returned [`MetricsEndpoint`] is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address cannot be bound.
+    pub async fn serve(self: &Arc<Self>, addr: SocketAddr) -> std::io::Result<MetricsEndpoint> {
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        let metrics = self.clone();
+
+        let task = tokio::spawn(async move {
+            loop {
+                let mut socket = match listener.accept().await {
+                    Ok((socket, _)) => socket,
+                    Err(e) => {
+                        // Errors such as running out of file descriptors
+                        // persist for a while; don't spin on them
+                        warn!(error = %e, "Metrics endpoint failed to accept a connection");
+                        tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                        continue;
+                    }
+                };
+                let metrics = metrics.clone();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let n = match socket.read(&mut buf).await {
+                        Ok(n) => n,
+                        Err(_) => return,
+                    };
+                    let response = if buf[..n].starts_with(b"GET ") {
+                        let body = metrics.render();
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            CONTENT_TYPE,
+                            body.len(),
+                            body
+                        )
+                    } else {
+                        "HTTP/1.1 405 Method Not Allowed\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string()
+                    };
+                    let _ = socket.write_all(response.as_bytes()).await;
+                    let _ = socket.shutdown().await;
+                });
+            }
+        });
+
+        Ok(MetricsEndpoint { local_addr, task })
+    }
+
+    fn write_histogram(&self, out: &mut String, name: &str, labels: &Labels, hist: &Histogram) {
+        let mut cumulative = 0;
+        for (bound, count) in self.buckets.iter().zip(&hist.counts) {
+            cumulative += count;
+            let _ = writeln!(
+                out,
+                "{}_bucket{} {}",
+                name,
+                format_labels(labels, Some(&bound.to_string())),
+                cumulative
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{}_bucket{} {}",
+            name,
+            format_labels(labels, Some("+Inf")),
+            hist.count
+        );
+        let _ = writeln!(
+            out,
+            "{}_sum{} {}",
+            name,
+            format_labels(labels, None),
+            hist.sum
+        );
+        let _ = writeln!(
+            out,
+            "{}_count{} {}",
+            name,
+            format_labels(labels, None),
+            hist.count
+        );
+    }
+}
+
+impl Default for PrometheusMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for PrometheusMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrometheusMetrics")
+            .field("buckets", &self.buckets)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MetricsSink for PrometheusMetrics {
+    fn counter(&self, name: &'static str, value: u64, tags: &[(&'static str, &str)]) {
+        let mut registry = lock(&self.registry);
+        *registry
+            .counters
+            .entry(name)
+            .or_default()
+            .entry(labels(tags))
+            .or_default() += value;
+    }
+
+    fn gauge(&self, name: &'static str, value: i64, tags: &[(&'static str, &str)]) {
+        let mut registry = lock(&self.registry);
+        registry
+            .gauges
+            .entry(name)
+            .or_default()
+            .insert(labels(tags), value);
+    }
+
+    fn histogram(&self, name: &'static str, value: f64, tags: &[(&'static str, &str)]) {
+        let mut registry = lock(&self.registry);
+        let hist = registry
+            .histograms
+            .entry(name)
+            .or_default()
+            .entry(labels(tags))
+            .or_insert_with(|| Histogram {
+                counts: vec![0; self.buckets.len()],
+                sum: 0.0,
+                count: 0,
+            });
+
+        if let Some(idx) = self.buckets.iter().position(|b| value <= *b) {
+            hist.counts[idx] += 1;
+        }
+        hist.sum += value;
+        hist.count += 1;
+    }
+}
+
+/// Handle to a running metrics HTTP endpoint.
+///
+/// The endpoint is shut down when this handle is dropped.
+#[derive(Debug)]
+pub struct MetricsEndpoint {
+    local_addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl MetricsEndpoint {
+    /// Get the address the endpoint is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for MetricsEndpoint {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// `ClientStats` counters exported by `register_stats`.
+const STATS_COUNTERS: &[(&str, StatsField)] = &[
+    ("massive_client_messages_received_total", |s| {
+        s.messages_received
+    }),
+    ("massive_client_messages_dropped_total", |s| {
+        s.messages_dropped
+    }),
+    ("massive_client_bytes_received_total", |s| s.bytes_received),
+    ("massive_client_parse_errors_total", |s| s.parse_errors),
+    ("massive_client_reconnections_total", |s| s.reconnections),
+    ("massive_client_requests_sent_total", |s| s.requests_sent),
+    ("massive_client_request_errors_total", |s| s.request_errors),
+    ("massive_client_rate_limits_total", |s| s.rate_limits),
+    ("massive_client_retries_total", |s| s.retries),
+    ("massive_client_journal_dropped_total", |s| {
+        s.journal_dropped
+    }),
+];
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn labels(tags: &[(&'static str, &str)]) -> Labels {
+    tags.iter().map(|(k, v)| (*k, (*v).to_string())).collect()
+}
+
+fn write_series<V: std::fmt::Display>(
+    out: &mut String,
+    name: &str,
+    kind: &str,
+    series: &BTreeMap<Labels, V>,
+) {
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    for (labels, value) in series {
+        let _ = writeln!(out, "{}{} {}", name, format_labels(labels, None), value);
+    }
+}
+
+/// Format a label set, optionally with a trailing `le` bucket label.
+fn format_labels(labels: &Labels, le: Option<&str>) -> String {
+    if labels.is_empty() && le.is_none() {
+        return String::new();
+    }
+
+    let mut parts: Vec<String> = labels
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", k, escape(v)))
+        .collect();
+    if let Some(le) = le {
+        parts.push(format!("le=\"{}\"", le));
+    }
+    format!("{{{}}}", parts.join(","))
+}
+
+/// Escape a label value per the text format.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::{names, ClientStats, LatencyTimer};
+
+    #[test]
+    fn test_render_counters_and_gauges() {
+        let metrics = PrometheusMetrics::new();
+        metrics.counter(names::REST_RETRIES, 1, &[("path", "/v2/aggs")]);
+        metrics.counter(names::REST_RETRIES, 2, &[("path", "/v2/aggs")]);
+        metrics.counter(names::REST_RETRIES, 1, &[("path", "/v3/trades")]);
+        metrics.gauge(names::WS_QUEUE_DEPTH, 7, &[("market", "stocks")]);
+        metrics.gauge(names::WS_QUEUE_DEPTH, 3, &[("market", "stocks")]);
+
+        let text = metrics.render();
+        assert!(text.contains("# TYPE massive_rest_retries_total counter\n"));
+        assert!(text.contains("massive_rest_retries_total{path=\"/v2/aggs\"} 3\n"));
+        assert!(text.contains("massive_rest_retries_total{path=\"/v3/trades\"} 1\n"));
+        assert!(text.contains("# TYPE massive_ws_queue_depth gauge\n"));
+        assert!(text.contains("massive_ws_queue_depth{market=\"stocks\"} 3\n"));
+    }
+
+    #[test]
+    fn test_render_histogram() {
+        let metrics = PrometheusMetrics::with_buckets(vec![10.0, 100.0]);
+        metrics.histogram("latency", 5.0, &[]);
+        metrics.histogram("latency", 50.0, &[]);
+        metrics.histogram("latency", 500.0, &[]);
+        LatencyTimer::new(&metrics, "timer")
+            .tag("path", "/x")
+            .stop();
+
+        let text = metrics.render();
+        assert!(text.contains("# TYPE latency histogram\n"));
+        assert!(text.contains("latency_bucket{le=\"10\"} 1\n"));
+        assert!(text.contains("latency_bucket{le=\"100\"} 2\n"));
+        assert!(text.contains("latency_bucket{le=\"+Inf\"} 3\n"));
+        assert!(text.contains("latency_sum 555\n"));
+        assert!(text.contains("latency_count 3\n"));
+        assert!(text.contains("timer_count{path=\"/x\"} 1\n"));
+    }
+
+    #[test]
+    fn test_render_client_stats() {
+        let metrics = PrometheusMetrics::new();
+        let stats = Arc::new(ClientStats::new());
+        stats.inc_requests_sent();
+        stats.inc_rate_limits();
+        stats.inc_journal_dropped();
+
+        let source = stats.clone();
+        metrics.register_stats("rest", move || source.snapshot());
+        stats.inc_requests_sent();
+
+        let text = metrics.render();
+        assert!(text.contains("massive_client_requests_sent_total{client=\"rest\"} 2\n"));
+        assert!(text.contains("massive_client_rate_limits_total{client=\"rest\"} 1\n"));
+        assert!(text.contains("massive_client_journal_dropped_total{client=\"rest\"} 1\n"));
+    }
+
+    #[test]
+    fn test_escape_label_values() {
+        let metrics = PrometheusMetrics::new();
+        metrics.counter("c", 1, &[("path", "a\"b\\c\nd")]);
+        assert!(metrics.render().contains(r#"c{path="a\"b\\c\nd"} 1"#));
+    }
+
+    #[tokio::test]
+    async fn test_serve_endpoint() {
+        let metrics = Arc::new(PrometheusMetrics::new());
+        metrics.counter("requests_total", 4, &[]);
+
+        let endpoint = metrics.serve("127.0.0.1:0".parse().unwrap()).await.unwrap();
+        let mut socket = tokio::net::TcpStream::connect(endpoint.local_addr())
+            .await
+            .unwrap();
+        socket
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        socket.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(CONTENT_TYPE));
+        assert!(response.ends_with("requests_total 4\n"));
+    }
+}
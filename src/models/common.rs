@@ -3,6 +3,7 @@
 //! These types represent fundamental market data structures that are
 //! used consistently across stocks, options, forex, and crypto.
 
+use crate::models::price::{self, Price};
 use crate::util::Symbol;
 use serde::{Deserialize, Serialize};
 
@@ -21,19 +22,19 @@ pub struct AggregateBar {
 
     /// Open price
     #[serde(rename = "o")]
-    pub open: f64,
+    pub open: Price,
 
     /// High price
     #[serde(rename = "h")]
-    pub high: f64,
+    pub high: Price,
 
     /// Low price
     #[serde(rename = "l")]
-    pub low: f64,
+    pub low: Price,
 
     /// Close price
     #[serde(rename = "c")]
-    pub close: f64,
+    pub close: Price,
 
     /// Volume (optional for index symbols like SPX which don't have volume)
     #[serde(rename = "v", default, skip_serializing_if = "Option::is_none")]
@@ -41,7 +42,7 @@ pub struct AggregateBar {
 
     /// Volume-weighted average price
     #[serde(rename = "vw", skip_serializing_if = "Option::is_none")]
-    pub vwap: Option<f64>,
+    pub vwap: Option<Price>,
 
     /// Timestamp (Unix milliseconds)
     #[serde(rename = "t")]
@@ -67,12 +68,12 @@ impl AggregateBar {
     }
 
     /// Calculate the bar range (high - low).
-    pub fn range(&self) -> f64 {
+    pub fn range(&self) -> Price {
         self.high - self.low
     }
 
     /// Calculate the bar body size (|close - open|).
-    pub fn body(&self) -> f64 {
+    pub fn body(&self) -> Price {
         (self.close - self.open).abs()
     }
 
@@ -87,23 +88,23 @@ impl AggregateBar {
     }
 
     /// Check if this is a doji (open ≈ close).
-    pub fn is_doji(&self, tolerance: f64) -> bool {
+    pub fn is_doji(&self, tolerance: Price) -> bool {
         self.body() <= tolerance
     }
 
     /// Calculate the upper wick size.
-    pub fn upper_wick(&self) -> f64 {
+    pub fn upper_wick(&self) -> Price {
         self.high - self.open.max(self.close)
     }
 
     /// Calculate the lower wick size.
-    pub fn lower_wick(&self) -> f64 {
+    pub fn lower_wick(&self) -> Price {
         self.open.min(self.close) - self.low
     }
 
     /// Get the VWAP or fall back to the midpoint.
-    pub fn vwap_or_mid(&self) -> f64 {
-        self.vwap.unwrap_or((self.high + self.low) / 2.0)
+    pub fn vwap_or_mid(&self) -> Price {
+        self.vwap.unwrap_or_else(|| price::mid(self.high, self.low))
     }
 }
 
@@ -118,7 +119,7 @@ pub struct Trade {
 
     /// Trade price
     #[serde(rename = "p")]
-    pub price: f64,
+    pub price: Price,
 
     /// Trade size (shares)
     #[serde(rename = "s")]
@@ -159,8 +160,8 @@ pub struct Trade {
 
 impl Trade {
     /// Calculate the trade value (price * size).
-    pub fn value(&self) -> f64 {
-        self.price * self.size as f64
+    pub fn value(&self) -> Price {
+        self.price * price::from_u64(self.size)
     }
 }
 
@@ -175,7 +176,7 @@ pub struct Quote {
 
     /// Bid price
     #[serde(rename = "bp")]
-    pub bid_price: f64,
+    pub bid_price: Price,
 
     /// Bid size
     #[serde(rename = "bs")]
@@ -187,7 +188,7 @@ pub struct Quote {
 
     /// Ask price
     #[serde(rename = "ap")]
-    pub ask_price: f64,
+    pub ask_price: Price,
 
     /// Ask size
     #[serde(rename = "as")]
@@ -220,32 +221,34 @@ pub struct Quote {
 
 impl Quote {
     /// Calculate the bid-ask spread.
-    pub fn spread(&self) -> f64 {
+    pub fn spread(&self) -> Price {
         self.ask_price - self.bid_price
     }
 
     /// Calculate the spread in basis points.
     pub fn spread_bps(&self) -> f64 {
-        if self.mid() == 0.0 {
+        let mid = price::to_f64(self.mid());
+        if mid == 0.0 {
             0.0
         } else {
-            (self.spread() / self.mid()) * 10000.0
+            (price::to_f64(self.spread()) / mid) * 10000.0
         }
     }
 
     /// Calculate the mid price.
-    pub fn mid(&self) -> f64 {
-        (self.bid_price + self.ask_price) / 2.0
+    pub fn mid(&self) -> Price {
+        price::mid(self.bid_price, self.ask_price)
     }
 
     /// Calculate the size-weighted mid price.
-    pub fn weighted_mid(&self) -> f64 {
+    pub fn weighted_mid(&self) -> Price {
         let total_size = self.bid_size + self.ask_size;
         if total_size == 0 {
             return self.mid();
         }
-        (self.bid_price * self.ask_size as f64 + self.ask_price * self.bid_size as f64)
-            / total_size as f64
+        (self.bid_price * price::from_u64(self.ask_size)
+            + self.ask_price * price::from_u64(self.bid_size))
+            / price::from_u64(total_size)
     }
 
     /// Check if the quote is crossed (bid > ask).
@@ -255,7 +258,7 @@ impl Quote {
 
     /// Check if the quote is locked (bid == ask).
     pub fn is_locked(&self) -> bool {
-        (self.bid_price - self.ask_price).abs() < f64::EPSILON
+        price::to_f64((self.bid_price - self.ask_price).abs()) < f64::EPSILON
     }
 }
 
@@ -267,19 +270,19 @@ impl Quote {
 pub struct DailyBar {
     /// Open price
     #[serde(rename = "o")]
-    pub open: f64,
+    pub open: Price,
 
     /// High price
     #[serde(rename = "h")]
-    pub high: f64,
+    pub high: Price,
 
     /// Low price
     #[serde(rename = "l")]
-    pub low: f64,
+    pub low: Price,
 
     /// Close price
     #[serde(rename = "c")]
-    pub close: f64,
+    pub close: Price,
 
     /// Volume (optional for index symbols like SPX which don't have volume)
     #[serde(rename = "v", default, skip_serializing_if = "Option::is_none")]
@@ -287,7 +290,7 @@ pub struct DailyBar {
 
     /// Volume-weighted average price
     #[serde(rename = "vw", skip_serializing_if = "Option::is_none")]
-    pub vwap: Option<f64>,
+    pub vwap: Option<Price>,
 }
 
 /// Ticker details.
@@ -337,30 +340,31 @@ pub struct Ticker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::price::px;
 
     #[test]
     fn test_aggregate_bar_calculations() {
         let bar = AggregateBar {
             ticker: Some("AAPL".into()),
-            open: 150.0,
-            high: 155.0,
-            low: 148.0,
-            close: 153.0,
+            open: px(150.0),
+            high: px(155.0),
+            low: px(148.0),
+            close: px(153.0),
             volume: Some(1000000.0),
-            vwap: Some(151.5),
+            vwap: Some(px(151.5)),
             timestamp: 1703001234567,
             transactions: Some(5000),
             otc: false,
         };
 
-        assert_eq!(bar.range(), 7.0);
-        assert_eq!(bar.body(), 3.0);
+        assert_eq!(bar.range(), px(7.0));
+        assert_eq!(bar.body(), px(3.0));
         assert!(bar.is_bullish());
         assert!(!bar.is_bearish());
-        assert!(!bar.is_doji(0.5));
-        assert_eq!(bar.upper_wick(), 2.0); // 155 - 153
-        assert_eq!(bar.lower_wick(), 2.0); // 150 - 148
-        assert_eq!(bar.vwap_or_mid(), 151.5);
+        assert!(!bar.is_doji(px(0.5)));
+        assert_eq!(bar.upper_wick(), px(2.0)); // 155 - 153
+        assert_eq!(bar.lower_wick(), px(2.0)); // 150 - 148
+        assert_eq!(bar.vwap_or_mid(), px(151.5));
         assert_eq!(bar.volume_or_zero(), 1000000.0);
     }
 
@@ -368,10 +372,10 @@ mod tests {
     fn test_aggregate_bar_doji() {
         let bar = AggregateBar {
             ticker: Some("AAPL".into()),
-            open: 150.0,
-            high: 152.0,
-            low: 148.0,
-            close: 150.1,
+            open: px(150.0),
+            high: px(152.0),
+            low: px(148.0),
+            close: px(150.1),
             volume: Some(1000000.0),
             vwap: None,
             timestamp: 1703001234567,
@@ -379,8 +383,8 @@ mod tests {
             otc: false,
         };
 
-        assert!(bar.is_doji(0.5));
-        assert_eq!(bar.vwap_or_mid(), 150.0); // (152 + 148) / 2
+        assert!(bar.is_doji(px(0.5)));
+        assert_eq!(bar.vwap_or_mid(), px(150.0)); // (152 + 148) / 2
     }
 
     #[test]
@@ -388,10 +392,10 @@ mod tests {
         // Index symbols like SPX don't have volume
         let bar = AggregateBar {
             ticker: Some("I:SPX".into()),
-            open: 5000.0,
-            high: 5050.0,
-            low: 4990.0,
-            close: 5020.0,
+            open: px(5000.0),
+            high: px(5050.0),
+            low: px(4990.0),
+            close: px(5020.0),
             volume: None,
             vwap: None,
             timestamp: 1703001234567,
@@ -407,7 +411,7 @@ mod tests {
     fn test_trade_value() {
         let trade = Trade {
             ticker: Some("AAPL".into()),
-            price: 150.0,
+            price: px(150.0),
             size: 100,
             exchange: 4,
             trade_id: "123".into(),
@@ -419,17 +423,17 @@ mod tests {
             tape: Some(3),
         };
 
-        assert_eq!(trade.value(), 15000.0);
+        assert_eq!(trade.value(), px(15000.0));
     }
 
     #[test]
     fn test_quote_calculations() {
         let quote = Quote {
             ticker: Some("AAPL".into()),
-            bid_price: 150.00,
+            bid_price: px(150.00),
             bid_size: 100,
             bid_exchange: 4,
-            ask_price: 150.10,
+            ask_price: px(150.10),
             ask_size: 200,
             ask_exchange: 4,
             sip_timestamp: 1703001234567890123,
@@ -439,8 +443,8 @@ mod tests {
             tape: None,
         };
 
-        assert!((quote.spread() - 0.10).abs() < 0.001);
-        assert!((quote.mid() - 150.05).abs() < 0.001);
+        assert!((quote.spread() - px(0.10)).abs() < px(0.001));
+        assert!((quote.mid() - px(150.05)).abs() < px(0.001));
         assert!(!quote.is_crossed());
         assert!(!quote.is_locked());
 
@@ -453,10 +457,10 @@ mod tests {
     fn test_quote_crossed() {
         let quote = Quote {
             ticker: Some("AAPL".into()),
-            bid_price: 150.10,
+            bid_price: px(150.10),
             bid_size: 100,
             bid_exchange: 4,
-            ask_price: 150.00,
+            ask_price: px(150.00),
             ask_size: 100,
             ask_exchange: 4,
             sip_timestamp: 1703001234567890123,
@@ -469,16 +473,26 @@ mod tests {
         assert!(quote.is_crossed());
     }
 
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_prices_are_exact() {
+        let json = r#"{"bp": 0.1, "bs": 1, "bx": 4, "ap": 0.3, "as": 1, "ax": 4, "t": 1}"#;
+        let quote: Quote = serde_json::from_str(json).unwrap();
+        assert_eq!(quote.spread(), px(0.2));
+        assert_eq!(quote.mid(), px(0.2));
+        assert_eq!(quote.spread().to_string(), "0.2");
+    }
+
     #[test]
     fn test_aggregate_bar_serde() {
         let bar = AggregateBar {
             ticker: Some("AAPL".into()),
-            open: 150.0,
-            high: 155.0,
-            low: 148.0,
-            close: 153.0,
+            open: px(150.0),
+            high: px(155.0),
+            low: px(148.0),
+            close: px(153.0),
             volume: Some(1000000.0),
-            vwap: Some(151.5),
+            vwap: Some(px(151.5)),
             timestamp: 1703001234567,
             transactions: Some(5000),
             otc: false,
@@ -494,10 +508,10 @@ mod tests {
         // Test that bars without volume (index symbols) can be serialized/deserialized
         let bar = AggregateBar {
             ticker: Some("I:SPX".into()),
-            open: 5000.0,
-            high: 5050.0,
-            low: 4990.0,
-            close: 5020.0,
+            open: px(5000.0),
+            high: px(5050.0),
+            low: px(4990.0),
+            close: px(5020.0),
             volume: None,
             vwap: None,
             timestamp: 1703001234567,
@@ -527,8 +541,8 @@ mod tests {
 
         let bar: AggregateBar = serde_json::from_str(json).unwrap();
         assert_eq!(bar.ticker, Some("I:SPX".into()));
-        assert_eq!(bar.open, 5000.0);
-        assert_eq!(bar.close, 5020.0);
+        assert_eq!(bar.open, px(5000.0));
+        assert_eq!(bar.close, px(5020.0));
         assert!(bar.volume.is_none());
         assert_eq!(bar.volume_or_zero(), 0.0);
     }
@@ -550,7 +564,7 @@ mod tests {
 
         let bar: AggregateBar = serde_json::from_str(json).unwrap();
         assert_eq!(bar.ticker, Some("AAPL".into()));
-        assert_eq!(bar.open, 150.0);
+        assert_eq!(bar.open, px(150.0));
         assert_eq!(bar.volume, Some(1000000.0));
         assert_eq!(bar.transactions, Some(5000));
     }
@@ -9,11 +9,15 @@
 //! - **Options**: Options-specific types (OptionContract, Greeks, OptionQuote)
 //! - **Forex**: Foreign exchange types (CurrencyPair, ForexQuote, ForexBar)
 //! - **Crypto**: Cryptocurrency types (CryptoPair, CryptoTrade, CryptoBar)
+//!
+//! Prices use the [`Price`] type, which is `f64` by default and
+//! `rust_decimal::Decimal` with the `decimal` feature.
 
 mod common;
 pub mod crypto;
 pub mod forex;
 pub mod options;
+pub mod price;
 
 pub use common::*;
 pub use crypto::{CryptoBar, CryptoPair, CryptoQuote, CryptoSnapshot, CryptoTrade};
@@ -21,3 +25,4 @@ pub use forex::{
     CurrencyConversion, CurrencyPair, ForexBar, ForexQuote, ForexSnapshot, ForexTrade,
 };
 pub use options::{ContractType, Greeks, OptionContract, OptionQuote, OptionSnapshot};
+pub use price::Price;
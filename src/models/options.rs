@@ -2,6 +2,7 @@
 //!
 //! This module contains types for options market data.
 
+use crate::models::price::{self, Price};
 use serde::{Deserialize, Serialize};
 
 /// Option contract details.
@@ -78,17 +79,17 @@ pub struct OptionQuote {
     /// Option ticker
     pub ticker: String,
     /// Bid price
-    pub bid: f64,
+    pub bid: Price,
     /// Ask price
-    pub ask: f64,
+    pub ask: Price,
     /// Bid size
     pub bid_size: u64,
     /// Ask size
     pub ask_size: u64,
     /// Last price
-    pub last: f64,
+    pub last: Price,
     /// Mid price
-    pub mid: f64,
+    pub mid: Price,
     /// Open interest
     pub open_interest: Option<u64>,
     /// Implied volatility
@@ -101,14 +102,15 @@ pub struct OptionQuote {
 
 impl OptionQuote {
     /// Calculate the bid-ask spread.
-    pub fn spread(&self) -> f64 {
+    pub fn spread(&self) -> Price {
         self.ask - self.bid
     }
 
     /// Calculate the spread as percentage of mid.
     pub fn spread_percent(&self) -> f64 {
-        if self.mid > 0.0 {
-            self.spread() / self.mid * 100.0
+        let mid = price::to_f64(self.mid);
+        if mid > 0.0 {
+            price::to_f64(self.spread()) / mid * 100.0
         } else {
             0.0
         }
@@ -119,7 +121,7 @@ impl OptionQuote {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptionSnapshot {
     /// Break even price
-    pub break_even_price: Option<f64>,
+    pub break_even_price: Option<Price>,
     /// Day information
     pub day: Option<OptionDaySnapshot>,
     /// Underlying asset details
@@ -146,38 +148,38 @@ pub struct OptionDaySnapshot {
     /// Change percent
     pub change_percent: Option<f64>,
     /// Close price
-    pub close: Option<f64>,
+    pub close: Option<Price>,
     /// High price
-    pub high: Option<f64>,
+    pub high: Option<Price>,
     /// Last updated timestamp
     pub last_updated: Option<i64>,
     /// Low price
-    pub low: Option<f64>,
+    pub low: Option<Price>,
     /// Open price
-    pub open: Option<f64>,
+    pub open: Option<Price>,
     /// Previous close
-    pub previous_close: Option<f64>,
+    pub previous_close: Option<Price>,
     /// Volume
     pub volume: Option<u64>,
     /// VWAP
-    pub vwap: Option<f64>,
+    pub vwap: Option<Price>,
 }
 
 /// Option last quote snapshot.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptionLastQuote {
     /// Ask price
-    pub ask: f64,
+    pub ask: Price,
     /// Ask size
     pub ask_size: u64,
     /// Bid price
-    pub bid: f64,
+    pub bid: Price,
     /// Bid size
     pub bid_size: u64,
     /// Last updated timestamp
     pub last_updated: Option<i64>,
     /// Midpoint
-    pub midpoint: f64,
+    pub midpoint: Price,
     /// Timeframe
     pub timeframe: Option<String>,
 }
@@ -191,7 +193,7 @@ pub struct OptionLastTrade {
     /// Exchange
     pub exchange: Option<u8>,
     /// Price
-    pub price: f64,
+    pub price: Price,
     /// SIP timestamp
     pub sip_timestamp: Option<i64>,
     /// Size
@@ -208,7 +210,7 @@ pub struct UnderlyingAsset {
     /// Last updated timestamp
     pub last_updated: Option<i64>,
     /// Current price
-    pub price: Option<f64>,
+    pub price: Option<Price>,
     /// Ticker
     pub ticker: String,
     /// Timeframe
@@ -218,6 +220,7 @@ pub struct UnderlyingAsset {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::price::px;
 
     #[test]
     fn test_contract_type_display() {
@@ -229,12 +232,12 @@ mod tests {
     fn test_option_quote_spread() {
         let quote = OptionQuote {
             ticker: "O:AAPL230120C00150000".into(),
-            bid: 5.00,
-            ask: 5.20,
+            bid: px(5.00),
+            ask: px(5.20),
             bid_size: 100,
             ask_size: 50,
-            last: 5.10,
-            mid: 5.10,
+            last: px(5.10),
+            mid: px(5.10),
             open_interest: Some(1000),
             implied_volatility: Some(0.30),
             greeks: Some(Greeks {
@@ -247,7 +250,7 @@ mod tests {
             timestamp: Some(1703001234567),
         };
 
-        assert!((quote.spread() - 0.20).abs() < 0.001);
+        assert!((quote.spread() - px(0.20)).abs() < px(0.001));
         assert!((quote.spread_percent() - 3.92).abs() < 0.1);
     }
 
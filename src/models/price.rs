@@ -0,0 +1,105 @@
+//! Price representation.
+//!
+//! [`Price`] is `f64` by default. With the `decimal` feature it becomes
+//! [`rust_decimal::Decimal`], so prices are parsed exactly as the API sends
+//! them and arithmetic such as spreads and trade values is exact.
+//!
+//! Only prices switch representation; sizes, volumes, percentages, and
+//! Greeks stay `f64`. Code that needs to work with either representation can
+//! use [`to_f64`] and [`from_u64`], or parse a literal with [`str::parse`]:
+//!
+//! ```
+//! use massive_rs::models::price::{self, Price};
+//!
+//! let bid: Price = "150.10".parse().unwrap();
+//! let ask: Price = "150.12".parse().unwrap();
+//! let notional = ask * price::from_u64(100);
+//! assert!((price::to_f64(notional) - 15012.0).abs() < 1e-9);
+//! # let _ = bid;
+//! ```
+//!
+//! With `decimal`, prices serialize as JSON strings to preserve precision.
+//! Both strings and numbers are accepted when deserializing.
+
+/// Price of an instrument.
+#[cfg(not(feature = "decimal"))]
+pub type Price = f64;
+
+/// Price of an instrument.
+#[cfg(feature = "decimal")]
+pub type Price = rust_decimal::Decimal;
+
+/// Convert a price to `f64`, for ratios and other approximate math.
+#[cfg(not(feature = "decimal"))]
+pub fn to_f64(price: Price) -> f64 {
+    price
+}
+
+/// Convert a price to `f64`, for ratios and other approximate math.
+#[cfg(feature = "decimal")]
+pub fn to_f64(price: Price) -> f64 {
+    use rust_decimal::prelude::ToPrimitive;
+    price.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Convert a whole quantity, such as a trade size, to a [`Price`].
+#[cfg(not(feature = "decimal"))]
+pub fn from_u64(value: u64) -> Price {
+    value as f64
+}
+
+/// Convert a whole quantity, such as a trade size, to a [`Price`].
+#[cfg(feature = "decimal")]
+pub fn from_u64(value: u64) -> Price {
+    Price::from(value)
+}
+
+/// Midpoint of two prices.
+pub(crate) fn mid(a: Price, b: Price) -> Price {
+    (a + b) / from_u64(2)
+}
+
+/// Build a price from a float literal in tests, exactly as written.
+#[cfg(test)]
+pub(crate) fn px(value: f64) -> Price {
+    value.to_string().parse().expect("valid price literal")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(s: &str) -> Price {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_mid() {
+        assert_eq!(mid(price("1"), price("2")), price("1.5"));
+        assert_eq!(mid(price("150.25"), price("150.75")), price("150.5"));
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(from_u64(100) * price("1.5"), price("150"));
+        assert_eq!(to_f64(price("153.4")), 153.4);
+    }
+
+    #[test]
+    fn test_deserialize_number() {
+        let value: Price = serde_json::from_str("153.4").unwrap();
+        assert_eq!(value, price("153.4"));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_is_exact() {
+        let a: Price = serde_json::from_str("0.1").unwrap();
+        let b: Price = serde_json::from_str("0.2").unwrap();
+        assert_eq!(a + b, price("0.3"));
+        assert_eq!(
+            serde_json::from_str::<Price>("\"0.3\"").unwrap(),
+            price("0.3")
+        );
+    }
+}
@@ -20,10 +20,12 @@
 //! assert_eq!(events.len(), 1);
 //! ```
 
+use crate::config::Market;
 use crate::error::MassiveError;
-use crate::ws::models::events::WsEvent;
-#[cfg(not(feature = "simd-json"))]
+use crate::ws::models::events::{parse_ws_message_for, WsEvent};
 use serde::de::Error as _;
+#[cfg(feature = "simd-json")]
+use serde_json::value::RawValue;
 
 /// Parse WebSocket events from a JSON string.
 ///
@@ -36,7 +38,12 @@ use serde::de::Error as _;
 ///
 /// # Returns
 ///
-/// A vector of parsed events, or an error if parsing fails.
+/// A vector of parsed events, or an error if any event fails to parse.
+/// Use [`parse_ws_message`](crate::ws::models::events::parse_ws_message) to
+/// keep the events that did parse.
+///
+/// Event types shared with the futures socket are read as stock events; use
+/// [`parse_ws_events_for`] for messages from [`Market::Futures`].
 ///
 /// # Example
 ///
@@ -54,12 +61,18 @@ use serde::de::Error as _;
 /// assert_eq!(events.len(), 1);
 /// ```
 pub fn parse_ws_events(text: &str) -> Result<Vec<WsEvent>, MassiveError> {
-    let trimmed = text.trim();
+    parse_ws_events_for(text, Market::Stocks)
+}
 
-    if trimmed.starts_with('[') {
-        parse_array(trimmed)
-    } else {
-        parse_single(trimmed)
+/// Parse WebSocket events received from the socket for `market`.
+///
+/// Same as [`parse_ws_events`], with event types resolved as in
+/// [`parse_ws_message_for`].
+pub fn parse_ws_events_for(text: &str, market: Market) -> Result<Vec<WsEvent>, MassiveError> {
+    let parsed = parse_ws_message_for(text, market).map_err(|e| deserialize_error(e, text))?;
+    match parsed.errors.into_iter().next() {
+        Some(failed) => Err(deserialize_error(failed.error, failed.raw.get())),
+        None => Ok(parsed.events),
     }
 }
 
@@ -68,6 +81,9 @@ pub fn parse_ws_events(text: &str) -> Result<Vec<WsEvent>, MassiveError> {
 /// This variant is optimized for SIMD-JSON which requires mutable input.
 /// When SIMD-JSON is not enabled, it converts to string and uses standard parsing.
 ///
+/// Event types shared with the futures socket are read as stock events; use
+/// [`parse_ws_events_bytes_for`] for messages from [`Market::Futures`].
+///
 /// # Arguments
 ///
 /// * `bytes` - Mutable byte slice containing JSON (will be modified by SIMD-JSON)
@@ -75,60 +91,81 @@ pub fn parse_ws_events(text: &str) -> Result<Vec<WsEvent>, MassiveError> {
 /// # Safety
 ///
 /// The input bytes may be modified in place when using SIMD-JSON.
-#[cfg(feature = "simd-json")]
 pub fn parse_ws_events_bytes(bytes: &mut [u8]) -> Result<Vec<WsEvent>, MassiveError> {
+    parse_ws_events_bytes_for(bytes, Market::Stocks)
+}
+
+/// Parse WebSocket events received from the socket for `market` from a
+/// mutable byte slice.
+///
+/// Same as [`parse_ws_events_bytes`], with event types resolved as in
+/// [`parse_ws_message_for`].
+#[cfg(feature = "simd-json")]
+pub fn parse_ws_events_bytes_for(
+    bytes: &mut [u8],
+    market: Market,
+) -> Result<Vec<WsEvent>, MassiveError> {
+    use simd_json::prelude::*;
+    use simd_json::BorrowedValue;
+
     // SIMD-JSON requires mutable input
     let trimmed_len = trim_ascii_bytes(bytes);
     let trimmed = &mut bytes[..trimmed_len];
+    let snippet = String::from_utf8_lossy(&trimmed[..trimmed.len().min(100)]).to_string();
+
+    let value = simd_json::to_borrowed_value(trimmed).map_err(|e| simd_error(e, snippet))?;
+    let elements: Vec<&BorrowedValue<'_>> = match &value {
+        BorrowedValue::Array(items) => items.iter().collect(),
+        single => vec![single],
+    };
 
-    if trimmed.first() == Some(&b'[') {
-        simd_json::from_slice(trimmed).map_err(|e| MassiveError::Deserialize {
-            source: serde_json::from_str::<()>("").unwrap_err(), // Placeholder error
-            body_snippet: format!(
-                "simd-json error: {} | {}",
-                e,
-                String::from_utf8_lossy(&trimmed[..trimmed.len().min(100)])
-            ),
+    elements
+        .into_iter()
+        .map(|element| {
+            let Some(ev) = element.get_str("ev") else {
+                return Err(simd_error("missing field `ev`", element.encode()));
+            };
+            match WsEvent::deserialize_known(market, ev, element) {
+                Ok(Some(event)) => Ok(event),
+                Ok(None) => {
+                    let raw = element.encode();
+                    RawValue::from_string(raw.clone())
+                        .and_then(|value| WsEvent::other(ev.into(), &value))
+                        .map_err(|e| deserialize_error(e, &raw))
+                }
+                Err(e) => Err(simd_error(e, element.encode())),
+            }
         })
-    } else {
-        let event: WsEvent =
-            simd_json::from_slice(trimmed).map_err(|e| MassiveError::Deserialize {
-                source: serde_json::from_str::<()>("").unwrap_err(), // Placeholder error
-                body_snippet: format!(
-                    "simd-json error: {} | {}",
-                    e,
-                    String::from_utf8_lossy(&trimmed[..trimmed.len().min(100)])
-                ),
-            })?;
-        Ok(vec![event])
-    }
+        .collect()
 }
 
-/// Parse WebSocket events from a mutable byte slice (non-SIMD fallback).
+/// Parse WebSocket events received from the socket for `market` from a
+/// mutable byte slice (non-SIMD fallback).
 #[cfg(not(feature = "simd-json"))]
-pub fn parse_ws_events_bytes(bytes: &mut [u8]) -> Result<Vec<WsEvent>, MassiveError> {
+pub fn parse_ws_events_bytes_for(
+    bytes: &mut [u8],
+    market: Market,
+) -> Result<Vec<WsEvent>, MassiveError> {
     let text = std::str::from_utf8(bytes).map_err(|e| MassiveError::Deserialize {
         source: serde_json::Error::custom(e.to_string()),
         body_snippet: String::from_utf8_lossy(&bytes[..bytes.len().min(100)]).to_string(),
     })?;
-    parse_ws_events(text)
+    parse_ws_events_for(text, market)
 }
 
-/// Parse a JSON array of events.
-fn parse_array(text: &str) -> Result<Vec<WsEvent>, MassiveError> {
-    serde_json::from_str(text).map_err(|e| MassiveError::Deserialize {
-        source: e,
-        body_snippet: text[..text.len().min(100)].to_string(),
-    })
+fn deserialize_error(source: serde_json::Error, text: &str) -> MassiveError {
+    MassiveError::Deserialize {
+        source,
+        body_snippet: text.chars().take(100).collect(),
+    }
 }
 
-/// Parse a single JSON event.
-fn parse_single(text: &str) -> Result<Vec<WsEvent>, MassiveError> {
-    let event: WsEvent = serde_json::from_str(text).map_err(|e| MassiveError::Deserialize {
-        source: e,
-        body_snippet: text[..text.len().min(100)].to_string(),
-    })?;
-    Ok(vec![event])
+#[cfg(feature = "simd-json")]
+fn simd_error(error: impl std::fmt::Display, text: String) -> MassiveError {
+    MassiveError::Deserialize {
+        source: serde_json::Error::custom(format!("simd-json error: {error}")),
+        body_snippet: text.chars().take(100).collect(),
+    }
 }
 
 /// Trim ASCII whitespace from a byte slice, returning the trimmed length.
@@ -204,6 +241,7 @@ pub fn extract_event_type(text: &str) -> Option<&str> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::price::px;
 
     #[test]
     fn test_parse_single_event() {
@@ -225,6 +263,36 @@ mod tests {
         assert_eq!(events.len(), 1);
     }
 
+    #[test]
+    fn test_parse_fails_on_bad_event() {
+        let text = r#"[{"ev":"status","status":"connected"},{"ev":"T","sym":"AAPL"}]"#;
+        assert!(matches!(
+            parse_ws_events(text),
+            Err(MassiveError::Deserialize { body_snippet, .. }) if body_snippet.starts_with(r#"{"ev":"T""#)
+        ));
+    }
+
+    #[test]
+    fn test_parse_bytes_keeps_unknown_event() {
+        let mut bytes = br#"[{"ev":"FUT","sym":"ESZ4","p":1.5}]"#.to_vec();
+        let events = parse_ws_events_bytes(&mut bytes).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type(), "FUT");
+        assert_eq!(events[0].symbol(), Some("ESZ4"));
+    }
+
+    #[test]
+    fn test_parse_bytes_for_market() {
+        let json = br#"{"ev":"T","sym":"ESZ4","p":6000.25,"s":2,"t":1703001234567,"q":42}"#;
+
+        let events = parse_ws_events_bytes_for(&mut json.to_vec(), Market::Futures).unwrap();
+        assert!(matches!(&events[0], WsEvent::FuturesTrade(t) if t.p == px(6000.25)));
+
+        // Read as a stock trade, the futures payload is missing fields
+        assert!(parse_ws_events_bytes(&mut json.to_vec()).is_err());
+        assert!(parse_ws_events_for(std::str::from_utf8(json).unwrap(), Market::Futures).is_ok());
+    }
+
     #[test]
     fn test_parse_with_whitespace() {
         let text = r#"  { "ev": "status", "status": "connected" }  "#;
@@ -279,7 +347,7 @@ mod tests {
         match &events[0] {
             WsEvent::Trade(t) => {
                 assert_eq!(t.sym.as_str(), "AAPL");
-                assert_eq!(t.p, 150.25);
+                assert_eq!(t.p, px(150.25));
                 assert_eq!(t.s, 100);
             }
             _ => panic!("Expected trade event"),
@@ -294,8 +362,8 @@ mod tests {
         match &events[0] {
             WsEvent::Quote(q) => {
                 assert_eq!(q.sym.as_str(), "AAPL");
-                assert_eq!(q.bp, 150.0);
-                assert_eq!(q.ap, 150.10);
+                assert_eq!(q.bp, px(150.0));
+                assert_eq!(q.ap, px(150.10));
             }
             _ => panic!("Expected quote event"),
         }
@@ -3,15 +3,19 @@
 //! This module contains the core [`RestClient`] type for making HTTP requests
 //! to the Massive REST API.
 
-use crate::auth::AuthMode;
-use crate::config::{PaginationMode, RestConfig};
+use crate::auth::{self, AuthMode, API_KEY_PARAM};
+use crate::config::{PaginationMode, RestConfig, DEFAULT_MAX_RETRIES};
 use crate::error::{ApiErrorResponse, MassiveError};
-use crate::rest::pagination::PageStream;
-use crate::rest::request::{PaginatableRequest, RestRequest};
-use reqwest::{Client, Response};
+use crate::metrics::{names, ClientStats, LatencyTimer, StatsSnapshot};
+use crate::rest::concurrency::{InFlightLimiter, InFlightPermit};
+use crate::rest::pagination::{Page, PageCheckpoint, PageMeta, PageStream, ResponseStream};
+use crate::rest::rate_limit::RateLimiter;
+use crate::rest::request::{PaginatableRequest, RequestPriority, RestRequest, ShardableRequest};
+use crate::rest::shard::{ShardConfig, ShardedStream};
+use reqwest::{Client, RequestBuilder, Response};
 use serde::de::DeserializeOwned;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tracing::{debug, instrument, warn};
 
 /// REST API client for Massive.com.
@@ -40,6 +44,11 @@ pub struct RestClient {
 struct RestClientInner {
     http: Client,
     config: RestConfig,
+    stats: ClientStats,
+    /// Shared token bucket, if a rate limit is configured
+    limiter: Option<RateLimiter>,
+    /// Shared in-flight slots, if a concurrency limit is configured
+    in_flight: Option<InFlightLimiter>,
 }
 
 impl RestClient {
@@ -51,7 +60,7 @@ impl RestClient {
     /// - The HTTP client cannot be created
     /// - The API key is empty (set `MASSIVE_API_KEY` environment variable or provide a key)
     #[instrument(skip_all)]
-    pub fn new(config: RestConfig) -> Result<Self, MassiveError> {
+    pub fn new(mut config: RestConfig) -> Result<Self, MassiveError> {
         // Validate API key is not empty
         if config.api_key.is_empty() {
             return Err(MassiveError::Auth(
@@ -76,10 +85,44 @@ impl RestClient {
             builder = builder.user_agent(crate::user_agent());
         }
 
+        if config.rate_limit.is_some_and(|r| r.requests == 0) {
+            return Err(MassiveError::InvalidArgument(
+                "Rate limit must allow at least one request per interval",
+            ));
+        }
+
+        if config.max_in_flight == Some(0) {
+            return Err(MassiveError::InvalidArgument(
+                "max_in_flight must be at least 1",
+            ));
+        }
+
+        if config
+            .prefetch
+            .is_some_and(|p| p.pages == 0 || p.max_buffered_items == 0)
+        {
+            return Err(MassiveError::InvalidArgument(
+                "Prefetch must allow at least one page and one item",
+            ));
+        }
+
+        #[allow(deprecated)]
+        if config.max_retries != DEFAULT_MAX_RETRIES {
+            config.retry_policy.max_attempts = config.max_retries;
+        }
+
         let http = builder.build()?;
+        let limiter = config.rate_limit.map(RateLimiter::new);
+        let in_flight = config.max_in_flight.map(InFlightLimiter::new);
 
         Ok(Self {
-            inner: Arc::new(RestClientInner { http, config }),
+            inner: Arc::new(RestClientInner {
+                http,
+                config,
+                stats: ClientStats::new(),
+                limiter,
+                in_flight,
+            }),
         })
     }
 
@@ -102,6 +145,13 @@ impl RestClient {
         &self.inner.config
     }
 
+    /// Get a snapshot of request statistics.
+    ///
+    /// Counters are shared by all clones of this client.
+    pub fn stats(&self) -> StatsSnapshot {
+        self.inner.stats.snapshot()
+    }
+
     /// Execute a typed request.
     ///
     /// # Arguments
@@ -111,15 +161,63 @@ impl RestClient {
     /// # Errors
     ///
     /// Returns an error if the request fails or the response cannot be parsed.
-    #[instrument(skip(self, req), fields(path = %req.path()))]
     pub async fn execute<R>(&self, req: R) -> Result<R::Response, MassiveError>
+    where
+        R: RestRequest,
+    {
+        self.execute_with_priority(req, RequestPriority::Normal)
+            .await
+    }
+
+    /// Execute a typed request with a scheduling priority.
+    ///
+    /// The priority only matters when
+    /// [`RestConfig::max_in_flight`](crate::config::RestConfig::max_in_flight)
+    /// is set: queued requests are started highest priority first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use massive_rs::rest::{RequestPriority, RestClient};
+    /// use massive_rs::rest::endpoints::GetLastQuoteRequest;
+    ///
+    /// # async fn example(client: RestClient) -> Result<(), massive_rs::MassiveError> {
+    /// let quote = client
+    ///     .execute_with_priority(GetLastQuoteRequest::new("AAPL"), RequestPriority::High)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the response cannot be parsed.
+    pub async fn execute_with_priority<R>(
+        &self,
+        req: R,
+        priority: RequestPriority,
+    ) -> Result<R::Response, MassiveError>
+    where
+        R: RestRequest,
+    {
+        Ok(self.execute_page(req, priority).await?.response)
+    }
+
+    /// Execute a typed request and keep the HTTP metadata of the response.
+    #[instrument(skip(self, req), fields(path = %req.path()))]
+    pub(crate) async fn execute_page<R>(
+        &self,
+        req: R,
+        priority: RequestPriority,
+    ) -> Result<Page<R::Response>, MassiveError>
     where
         R: RestRequest,
     {
         let url = self.build_url(&req)?;
         let method = req.method();
+        let redacted = auth::redact_url(&url);
 
-        debug!(method = %method, url = %url, "Executing request");
+        debug!(method = %method, url = %redacted, "Executing request");
 
         let mut request = self.inner.http.request(method.clone(), url);
 
@@ -133,11 +231,14 @@ impl RestClient {
                 .body(body);
         }
 
-        // Execute with retry logic
-        let response = self.execute_with_retry(request, req.idempotent()).await?;
-
-        // Parse response
-        self.parse_response::<R::Response>(response).await
+        self.send_page(
+            request,
+            redacted,
+            req.endpoint(),
+            req.idempotent(),
+            priority,
+        )
+        .await
     }
 
     /// Stream paginated results.
@@ -173,10 +274,7 @@ impl RestClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn stream<R>(
-        &self,
-        req: R,
-    ) -> impl futures::Stream<Item = Result<R::Item, MassiveError>> + Send + 'static
+    pub fn stream<R>(&self, req: R) -> PageStream<R>
     where
         R: PaginatableRequest + Unpin + Send + 'static,
         R::Response: DeserializeOwned + Send + 'static,
@@ -191,11 +289,7 @@ impl RestClient {
     ///
     /// * `req` - The paginated request to execute
     /// * `mode` - The pagination mode to use
-    pub fn stream_with_mode<R>(
-        &self,
-        req: R,
-        mode: PaginationMode,
-    ) -> impl futures::Stream<Item = Result<R::Item, MassiveError>> + Send + 'static
+    pub fn stream_with_mode<R>(&self, req: R, mode: PaginationMode) -> PageStream<R>
     where
         R: PaginatableRequest + Unpin + Send + 'static,
         R::Response: DeserializeOwned + Send + 'static,
@@ -204,6 +298,131 @@ impl RestClient {
         PageStream::new(self.clone(), req, mode)
     }
 
+    /// Stream paginated results from a saved [`PageCheckpoint`].
+    ///
+    /// `req` must be the request the checkpoint was taken from. The stream
+    /// continues after the last item yielded before the checkpoint, using
+    /// the configured pagination mode.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use massive_rs::rest::RestClient;
+    /// use massive_rs::rest::endpoints::GetTradesRequest;
+    ///
+    /// # async fn example(client: RestClient) -> Result<(), massive_rs::MassiveError> {
+    /// let request = GetTradesRequest::new("AAPL").timestamp_gte("2024-01-02");
+    /// let mut stream = client.stream(request.clone());
+    /// stream.next().await;
+    /// let checkpoint = stream.checkpoint();
+    ///
+    /// // Later, possibly in another process
+    /// let mut stream = client.resume_stream(request, checkpoint);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resume_stream<R>(&self, req: R, checkpoint: PageCheckpoint) -> PageStream<R>
+    where
+        R: PaginatableRequest + Unpin + Send + 'static,
+        R::Response: DeserializeOwned + Send + 'static,
+        R::Item: Unpin,
+    {
+        PageStream::resume(self.clone(), req, self.inner.config.pagination, checkpoint)
+    }
+
+    /// Stream whole pages of a paginated request.
+    ///
+    /// Unlike [`stream`](Self::stream), each item is the full response of a
+    /// page (with `status`, `count`, `request_id`, and so on) together with
+    /// its [`PageMeta`]: HTTP status, `X-Request-Id` header, URL, and
+    /// timing. Pages follow the configured pagination mode.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use massive_rs::rest::RestClient;
+    /// use massive_rs::rest::endpoints::GetTradesRequest;
+    ///
+    /// # async fn example(client: RestClient) -> Result<(), massive_rs::MassiveError> {
+    /// let mut pages = client.stream_pages(GetTradesRequest::new("AAPL"));
+    /// while let Some(page) = pages.next().await {
+    ///     let page = page?;
+    ///     println!("{} {:?}", page.meta.url, page.response.request_id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream_pages<R>(&self, req: R) -> ResponseStream<R>
+    where
+        R: PaginatableRequest + Unpin + Send + 'static,
+        R::Response: DeserializeOwned + Send + 'static,
+    {
+        ResponseStream::new(self.clone(), req, self.inner.config.pagination)
+    }
+
+    /// Stream whole pages of a paginated request with a custom pagination
+    /// mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - The paginated request to execute
+    /// * `mode` - The pagination mode to use
+    pub fn stream_pages_with_mode<R>(&self, req: R, mode: PaginationMode) -> ResponseStream<R>
+    where
+        R: PaginatableRequest + Unpin + Send + 'static,
+        R::Response: DeserializeOwned + Send + 'static,
+    {
+        ResponseStream::new(self.clone(), req, mode)
+    }
+
+    /// Stream a large time range by splitting it into sub-ranges that are
+    /// downloaded in parallel.
+    ///
+    /// The request's time window is split into
+    /// [`ShardConfig::shards`] sub-ranges (more if needed to stay under the
+    /// 50,000-bar limit of aggregates), up to
+    /// [`ShardConfig::concurrency`] of which are paginated at once. Items
+    /// are yielded in timestamp order; see [`ShardedStream`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use massive_rs::rest::{RestClient, ShardConfig};
+    /// use massive_rs::rest::endpoints::{GetAggsRequest, Timespan};
+    ///
+    /// # async fn example(client: RestClient) -> Result<(), massive_rs::MassiveError> {
+    /// let request = GetAggsRequest::new("AAPL")
+    ///     .timespan(Timespan::Minute)
+    ///     .from("2020-01-01")
+    ///     .to("2023-12-31");
+    /// let mut bars = client.stream_sharded(request, ShardConfig::new(8))?;
+    /// while let Some(bar) = bars.next().await {
+    ///     println!("{:?}", bar?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request's time window is not bounded at
+    /// both ends or a [`ShardConfig`] value is zero.
+    pub fn stream_sharded<R>(
+        &self,
+        req: R,
+        config: ShardConfig,
+    ) -> Result<ShardedStream<R>, MassiveError>
+    where
+        R: ShardableRequest + Unpin + Send + 'static,
+        R::Response: DeserializeOwned + Send + 'static,
+        R::Item: Unpin,
+    {
+        ShardedStream::new(self.clone(), req, config)
+    }
+
     /// Build the full URL for a request.
     fn build_url<R: RestRequest>(&self, req: &R) -> Result<url::Url, MassiveError> {
         let mut url = self.inner.config.base_url.clone();
@@ -218,7 +437,7 @@ impl RestClient {
         // Add API key as query param if using that auth mode
         if matches!(self.inner.config.auth_mode, AuthMode::QueryParam) {
             url.query_pairs_mut()
-                .append_pair("apiKey", self.inner.config.api_key.expose());
+                .append_pair(API_KEY_PARAM, self.inner.config.api_key.expose());
         }
 
         Ok(url)
@@ -226,26 +445,96 @@ impl RestClient {
 
     /// Fetch a URL directly (used for pagination next_url).
     ///
-    /// The `next_url` from Massive API responses is a complete URL including
-    /// the API key as a query parameter, so we fetch it directly.
-    pub(crate) async fn fetch_url<T>(&self, url: &str) -> Result<T, MassiveError>
+    /// The `next_url` from Massive API responses is a complete URL that may
+    /// carry an `apiKey` query parameter. Any such key is removed and the
+    /// configured authentication is applied instead, so the key is only
+    /// sent in the URL in [`AuthMode::QueryParam`] mode. Metrics are tagged
+    /// with `endpoint`, the route template of the original request.
+    pub(crate) async fn fetch_url<T>(
+        &self,
+        url: &str,
+        endpoint: &'static str,
+        priority: RequestPriority,
+    ) -> Result<Page<T>, MassiveError>
     where
         T: DeserializeOwned,
     {
-        debug!(url = %url, "Fetching URL directly");
+        let mut url = url::Url::parse(url)?;
+        auth::strip_api_key(&mut url);
+        let stripped = url.clone();
+        if matches!(self.inner.config.auth_mode, AuthMode::QueryParam) {
+            url.query_pairs_mut()
+                .append_pair(API_KEY_PARAM, self.inner.config.api_key.expose());
+        }
+        debug!(url = %auth::redact_url(&url), "Fetching URL directly");
 
-        let mut request = self.inner.http.get(url);
+        let request = self.apply_auth(self.inner.http.get(url));
+        self.send_page(request, stripped, endpoint, true, priority)
+            .await
+    }
 
-        // next_url typically includes apiKey, but add auth header for safety
-        if matches!(self.inner.config.auth_mode, AuthMode::HeaderBearer) {
-            request = request.header(
-                "Authorization",
-                format!("Bearer {}", self.inner.config.api_key.expose()),
+    /// Send a request and parse its response into a [`Page`].
+    ///
+    /// `url` is recorded in the page metadata and must not carry the API
+    /// key. The page index is left at 0 for the caller to set.
+    async fn send_page<T>(
+        &self,
+        request: RequestBuilder,
+        url: url::Url,
+        endpoint: &'static str,
+        idempotent: bool,
+        priority: RequestPriority,
+    ) -> Result<Page<T>, MassiveError>
+    where
+        T: DeserializeOwned,
+    {
+        let start = Instant::now();
+
+        // Execute with retry logic, holding the slot until the body is read
+        let (response, _permit) = self
+            .execute_with_retry(request, endpoint, idempotent, priority)
+            .await?;
+        let status = response.status().as_u16();
+        let request_id = extract_request_id(&response);
+
+        // Parse response
+        let response = self.parse_response::<T>(response, endpoint).await?;
+        Ok(Page {
+            response,
+            meta: PageMeta {
+                index: 0,
+                url: url.into(),
+                status,
+                request_id,
+                elapsed: start.elapsed(),
+            },
+        })
+    }
+
+    /// Wait for an in-flight slot, if a concurrency limit is configured.
+    async fn acquire_slot(
+        &self,
+        endpoint: &'static str,
+        priority: RequestPriority,
+    ) -> Option<InFlightPermit<'_>> {
+        let limiter = self.inner.in_flight.as_ref()?;
+        let start = Instant::now();
+        let permit = limiter.acquire(priority).await;
+
+        let waited = start.elapsed();
+        if waited >= Duration::from_millis(1) {
+            let priority = match priority {
+                RequestPriority::High => "high",
+                RequestPriority::Normal => "normal",
+                RequestPriority::Low => "low",
+            };
+            self.inner.config.metrics.timing(
+                names::REST_QUEUE_WAIT,
+                waited.as_micros() as u64,
+                &[("endpoint", endpoint), ("priority", priority)],
             );
         }
-
-        let response = self.execute_with_retry(request, true).await?;
-        self.parse_response(response).await
+        Some(permit)
     }
 
     /// Apply authentication to a request.
@@ -265,17 +554,27 @@ impl RestClient {
     }
 
     /// Execute a request with retry logic.
+    ///
+    /// Each attempt first waits for the client-side rate limiter, if any,
+    /// and then for an in-flight slot, so a request waiting on the rate
+    /// limit never pins a slot. The slot is released during retry sleeps;
+    /// the one used for the final attempt is returned with the response.
+    /// Idempotent requests are retried according to the configured
+    /// [`RetryPolicy`](crate::config::RetryPolicy). A 429 that is not
+    /// retried becomes [`MassiveError::RateLimited`].
     async fn execute_with_retry(
         &self,
         request: reqwest::RequestBuilder,
+        endpoint: &'static str,
         idempotent: bool,
-    ) -> Result<Response, MassiveError> {
+        priority: RequestPriority,
+    ) -> Result<(Response, Option<InFlightPermit<'_>>), MassiveError> {
+        let policy = &self.inner.config.retry_policy;
+        let metrics = &*self.inner.config.metrics;
+        let stats = &self.inner.stats;
+        let tags = [("endpoint", endpoint)];
+        let start = Instant::now();
         let mut attempts = 0;
-        let max_attempts = if idempotent {
-            self.inner.config.max_retries
-        } else {
-            1
-        };
 
         loop {
             attempts += 1;
@@ -285,59 +584,116 @@ impl RestClient {
                 .try_clone()
                 .ok_or(MassiveError::InvalidArgument("Request body not cloneable"))?;
 
-            match req.send().await {
+            if let Some(limiter) = &self.inner.limiter {
+                let waited = limiter.acquire(priority).await;
+                if !waited.is_zero() {
+                    debug!(?waited, "Waited for rate limiter");
+                    metrics.timing(
+                        names::REST_RATE_LIMIT_WAIT,
+                        waited.as_micros() as u64,
+                        &tags,
+                    );
+                }
+            }
+            let permit = self.acquire_slot(endpoint, priority).await;
+
+            stats.inc_requests_sent();
+            let timer =
+                LatencyTimer::new(metrics, names::REST_REQUEST_LATENCY).tag("endpoint", endpoint);
+            let result = req.send().await;
+            timer.stop();
+
+            let error = match result {
                 Ok(resp) => {
                     let status = resp.status();
-
-                    // Handle rate limiting
-                    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                        let retry_after = resp
-                            .headers()
-                            .get("Retry-After")
-                            .and_then(|v| v.to_str().ok())
-                            .and_then(|s| s.parse::<u64>().ok())
-                            .map(Duration::from_secs);
-
-                        return Err(MassiveError::RateLimited {
-                            retry_after,
-                            request_id: extract_request_id(&resp),
-                        });
+                    metrics.counter(
+                        names::REST_REQUESTS,
+                        1,
+                        &[("endpoint", endpoint), ("status", status.as_str())],
+                    );
+
+                    let rate_limited = status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+                    if rate_limited {
+                        stats.inc_rate_limits();
+                        metrics.counter(names::REST_RATE_LIMITED, 1, &tags);
                     }
 
-                    // Retry on 502/503/504 if idempotent
-                    if idempotent && attempts < max_attempts && matches!(status.as_u16(), 502..=504)
+                    let retry_after = resp
+                        .headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(parse_retry_after);
+
+                    if !(idempotent && policy.retries_status(status.as_u16())) {
+                        return finish_response(resp, rate_limited, retry_after)
+                            .map(|resp| (resp, permit));
+                    }
+                    match policy.next_delay(attempts, start.elapsed(), retry_after, jitter_sample())
                     {
-                        warn!(status = %status, attempt = attempts, "Retrying request");
-                        tokio::time::sleep(backoff_delay(attempts)).await;
-                        continue;
+                        Some(delay) => {
+                            warn!(status = %status, attempt = attempts, ?delay, "Retrying request");
+                            stats.inc_retries();
+                            metrics.counter(names::REST_RETRIES, 1, &tags);
+                            drop(permit);
+                            tokio::time::sleep(delay).await;
+                            continue;
+                        }
+                        None => {
+                            return finish_response(resp, rate_limited, retry_after)
+                                .map(|resp| (resp, permit))
+                        }
                     }
-
-                    return Ok(resp);
                 }
-                Err(e) if idempotent && attempts < max_attempts && e.is_connect() => {
-                    warn!(error = %e, attempt = attempts, "Connection error, retrying");
-                    tokio::time::sleep(backoff_delay(attempts)).await;
+                Err(e) => auth::redact_error(e),
+            };
+
+            let retryable = (error.is_connect() && policy.retry_connect_errors)
+                || (error.is_timeout() && policy.retry_timeouts);
+            if idempotent && retryable {
+                if let Some(delay) =
+                    policy.next_delay(attempts, start.elapsed(), None, jitter_sample())
+                {
+                    warn!(error = %error, attempt = attempts, ?delay, "Request failed, retrying");
+                    stats.inc_retries();
+                    metrics.counter(names::REST_RETRIES, 1, &tags);
+                    drop(permit);
+                    tokio::time::sleep(delay).await;
                     continue;
                 }
-                Err(e) if e.is_timeout() => {
-                    return Err(MassiveError::Timeout);
-                }
-                Err(e) => return Err(e.into()),
             }
+
+            stats.inc_request_errors();
+            metrics.counter(names::REST_REQUEST_ERRORS, 1, &tags);
+            if error.is_timeout() {
+                return Err(MassiveError::Timeout);
+            }
+            return Err(error.into());
         }
     }
 
     /// Parse the response body.
-    async fn parse_response<T>(&self, response: Response) -> Result<T, MassiveError>
+    async fn parse_response<T>(
+        &self,
+        response: Response,
+        endpoint: &'static str,
+    ) -> Result<T, MassiveError>
     where
         T: DeserializeOwned,
     {
+        let metrics = &*self.inner.config.metrics;
+        let stats = &self.inner.stats;
+        let tags = [("endpoint", endpoint)];
         let status = response.status();
         let request_id = extract_request_id(&response);
 
         let bytes = response.bytes().await?;
+        stats.inc_bytes_received(bytes.len() as u64);
+        metrics.counter(names::REST_BYTES_RECEIVED, bytes.len() as u64, &tags);
 
         if !status.is_success() {
+            stats.inc_request_errors();
+            metrics.counter(names::REST_REQUEST_ERRORS, 1, &tags);
+
             // Try to parse as API error
             if let Ok(api_error) = serde_json::from_slice::<ApiErrorResponse>(&bytes) {
                 return Err(MassiveError::Api(api_error));
@@ -350,9 +706,13 @@ impl RestClient {
             });
         }
 
-        serde_json::from_slice(&bytes).map_err(|e| MassiveError::Deserialize {
-            source: e,
-            body_snippet: String::from_utf8_lossy(&bytes[..bytes.len().min(500)]).to_string(),
+        serde_json::from_slice(&bytes).map_err(|e| {
+            stats.inc_parse_errors();
+            metrics.counter(names::REST_PARSE_ERRORS, 1, &tags);
+            MassiveError::Deserialize {
+                source: e,
+                body_snippet: String::from_utf8_lossy(&bytes[..bytes.len().min(500)]).to_string(),
+            }
         })
     }
 }
@@ -366,9 +726,42 @@ fn extract_request_id(response: &Response) -> Option<String> {
         .map(String::from)
 }
 
-/// Calculate exponential backoff delay.
-fn backoff_delay(attempt: u32) -> Duration {
-    Duration::from_millis(100 * 2u64.pow(attempt.saturating_sub(1)))
+/// Turn a final (non-retried) response into the request result.
+fn finish_response(
+    resp: Response,
+    rate_limited: bool,
+    retry_after: Option<Duration>,
+) -> Result<Response, MassiveError> {
+    if rate_limited {
+        return Err(MassiveError::RateLimited {
+            retry_after,
+            request_id: extract_request_id(&resp),
+        });
+    }
+    Ok(resp)
+}
+
+/// Parse a `Retry-After` header value (delay in seconds or an HTTP-date).
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(delay.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Random value in `[0, 1)` for backoff jitter.
+fn jitter_sample() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    // RandomState is seeded randomly per thread and per instance
+    let bits = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
 }
 
 impl std::fmt::Debug for RestClient {
@@ -420,10 +813,52 @@ mod tests {
 
     #[test]
     fn test_backoff_delay() {
-        assert_eq!(backoff_delay(1), Duration::from_millis(100));
-        assert_eq!(backoff_delay(2), Duration::from_millis(200));
-        assert_eq!(backoff_delay(3), Duration::from_millis(400));
-        assert_eq!(backoff_delay(4), Duration::from_millis(800));
+        let policy = crate::config::RetryPolicy::default();
+        assert_eq!(policy.delay_for_attempt(1), Duration::from_millis(100));
+        assert_eq!(policy.delay_for_attempt(2), Duration::from_millis(200));
+        assert_eq!(policy.delay_for_attempt(3), Duration::from_millis(400));
+        assert_eq!(policy.delay_for_attempt(4), Duration::from_millis(800));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_max_retries() {
+        let client = RestClient::from_api_key("test-key").unwrap();
+        assert_eq!(client.config().retry_policy.max_attempts, 3);
+
+        let config = RestConfig {
+            max_retries: 5,
+            ..RestConfig::new("test-key")
+        };
+        let client = RestClient::new(config).unwrap();
+        assert_eq!(client.config().retry_policy.max_attempts, 5);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 0 "), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon"), None);
+
+        // HTTP-date in the past clamps to zero
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+
+        let future = (chrono::Utc::now() + chrono::Duration::seconds(30))
+            .format("%a, %d %b %Y %H:%M:%S GMT")
+            .to_string();
+        let delay = parse_retry_after(&future).unwrap();
+        assert!(delay > Duration::from_secs(25) && delay <= Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_jitter_sample_range() {
+        for _ in 0..100 {
+            let sample = jitter_sample();
+            assert!((0.0..1.0).contains(&sample));
+        }
     }
 
     #[test]
@@ -0,0 +1,191 @@
+//! In-flight request limiting with priority lanes.
+//!
+//! When [`RestConfig::max_in_flight`](crate::config::RestConfig::max_in_flight)
+//! is set, each request holds a slot from this limiter until its response
+//! has been read. Waiting requests are granted slots highest
+//! [`RequestPriority`] first, and in FIFO order within a priority.
+
+use crate::rest::request::RequestPriority;
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+use tokio::sync::oneshot;
+
+/// Limits concurrent requests shared by all clones of a client.
+#[derive(Debug)]
+pub(crate) struct InFlightLimiter {
+    max: usize,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    in_flight: usize,
+    /// Waiters per priority, highest priority first
+    queues: [VecDeque<oneshot::Sender<()>>; RequestPriority::COUNT],
+}
+
+/// A held request slot, released on drop.
+#[derive(Debug)]
+pub(crate) struct InFlightPermit<'a> {
+    limiter: &'a InFlightLimiter,
+}
+
+/// Releases a slot that was granted to a waiter that was cancelled.
+struct PendingSlot<'a> {
+    limiter: &'a InFlightLimiter,
+    rx: Option<oneshot::Receiver<()>>,
+}
+
+impl InFlightLimiter {
+    /// Create a limiter allowing `max` concurrent requests.
+    pub fn new(max: usize) -> Self {
+        Self {
+            max: max.max(1),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Wait for a request slot.
+    ///
+    /// Cancel-safe: dropping the future gives up the place in the queue,
+    /// and hands on a slot that was granted but not yet taken.
+    pub async fn acquire(&self, priority: RequestPriority) -> InFlightPermit<'_> {
+        let rx = {
+            let mut state = self.lock();
+            if state.in_flight < self.max {
+                state.in_flight += 1;
+                return InFlightPermit { limiter: self };
+            }
+            let (tx, rx) = oneshot::channel();
+            state.queues[priority.rank()].push_back(tx);
+            rx
+        };
+
+        let mut pending = PendingSlot {
+            limiter: self,
+            rx: Some(rx),
+        };
+        if let Some(rx) = pending.rx.as_mut() {
+            // The sender is only dropped without sending if the limiter is
+            // dropped, which cannot happen while we borrow it
+            let _ = rx.await;
+        }
+        pending.rx = None;
+
+        InFlightPermit { limiter: self }
+    }
+
+    /// Number of requests currently holding a slot.
+    #[cfg(test)]
+    pub fn in_flight(&self) -> usize {
+        self.lock().in_flight
+    }
+
+    /// Hand a finished request's slot to the next waiter, or free it.
+    fn release(&self) {
+        let mut state = self.lock();
+        for queue in state.queues.iter_mut() {
+            while let Some(tx) = queue.pop_front() {
+                if tx.send(()).is_ok() {
+                    return;
+                }
+            }
+        }
+        state.in_flight -= 1;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for InFlightPermit<'_> {
+    fn drop(&mut self) {
+        self.limiter.release();
+    }
+}
+
+impl Drop for PendingSlot<'_> {
+    fn drop(&mut self) {
+        if let Some(mut rx) = self.rx.take() {
+            rx.close();
+            if rx.try_recv().is_ok() {
+                self.limiter.release();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_limits_in_flight() {
+        let limiter = InFlightLimiter::new(2);
+        let a = limiter.acquire(RequestPriority::Normal).await;
+        let _b = limiter.acquire(RequestPriority::Normal).await;
+        assert_eq!(limiter.in_flight(), 2);
+
+        let mut waiting = Box::pin(limiter.acquire(RequestPriority::Normal));
+        assert!(waiting.as_mut().now_or_never().is_none());
+
+        drop(a);
+        let c = waiting.as_mut().now_or_never();
+        assert!(c.is_some());
+        assert_eq!(limiter.in_flight(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_high_priority_overtakes() {
+        let limiter = Arc::new(InFlightLimiter::new(1));
+        let held = limiter.acquire(RequestPriority::Normal).await;
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let mut tasks = Vec::new();
+        for (name, priority) in [
+            ("low-1", RequestPriority::Low),
+            ("low-2", RequestPriority::Low),
+            ("normal", RequestPriority::Normal),
+            ("high", RequestPriority::High),
+        ] {
+            let limiter = limiter.clone();
+            let order = order.clone();
+            tasks.push(tokio::spawn(async move {
+                let _permit = limiter.acquire(priority).await;
+                order.lock().unwrap().push(name);
+            }));
+            // Let each task join the queue in turn
+            tokio::task::yield_now().await;
+            tokio::task::yield_now().await;
+        }
+
+        drop(held);
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec!["high", "normal", "low-1", "low-2"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_waiter_releases_slot() {
+        let limiter = InFlightLimiter::new(1);
+        let held = limiter.acquire(RequestPriority::Normal).await;
+
+        let mut cancelled = Box::pin(limiter.acquire(RequestPriority::High));
+        assert!(cancelled.as_mut().now_or_never().is_none());
+
+        // Slot is granted to the waiter, which is dropped before taking it
+        drop(held);
+        drop(cancelled);
+        assert_eq!(limiter.in_flight(), 0);
+
+        let _next = limiter.acquire(RequestPriority::Low).await;
+        assert_eq!(limiter.in_flight(), 1);
+    }
+}
@@ -352,6 +352,10 @@ impl RestRequest for GetDividendsRequest {
         "/stocks/v1/dividends".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/stocks/v1/dividends"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("ticker", self.ticker.clone());
@@ -381,6 +385,10 @@ impl PaginatableRequest for GetDividendsRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -656,6 +664,10 @@ impl RestRequest for GetSplitsRequest {
         "/stocks/v1/splits".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/stocks/v1/splits"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("ticker", self.ticker.clone());
@@ -684,6 +696,10 @@ impl PaginatableRequest for GetSplitsRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
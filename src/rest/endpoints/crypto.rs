@@ -239,6 +239,10 @@ impl RestRequest for GetCryptoSnapshotRequest {
         "/v2/snapshot/locale/global/markets/crypto/tickers".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v2/snapshot/locale/global/markets/crypto/tickers"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         if let Some(ref tickers) = self.tickers {
@@ -297,6 +301,10 @@ impl RestRequest for GetCryptoTickerSnapshotRequest {
         )
         .into()
     }
+
+    fn endpoint(&self) -> &'static str {
+        "/v2/snapshot/locale/global/markets/crypto/tickers/{ticker}"
+    }
 }
 
 /// Request for crypto gainers/losers.
@@ -345,6 +353,10 @@ impl RestRequest for GetCryptoGainersLosersRequest {
         )
         .into()
     }
+
+    fn endpoint(&self) -> &'static str {
+        "/v2/snapshot/locale/global/markets/crypto/{direction}"
+    }
 }
 
 // ============================================================================
@@ -486,6 +498,10 @@ impl RestRequest for GetCryptoOpenCloseRequest {
         .into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v1/open-close/crypto/{from}/{to}/{date}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("adjusted", self.adjusted);
@@ -631,6 +647,10 @@ impl RestRequest for GetCryptoL2BookRequest {
         )
         .into()
     }
+
+    fn endpoint(&self) -> &'static str {
+        "/v2/snapshot/locale/global/markets/crypto/tickers/{ticker}/book"
+    }
 }
 
 // ============================================================================
@@ -689,6 +709,10 @@ impl RestRequest for GetCryptoExchangesRequest {
     fn path(&self) -> Cow<'static, str> {
         "/v1/meta/crypto-exchanges".into()
     }
+
+    fn endpoint(&self) -> &'static str {
+        "/v1/meta/crypto-exchanges"
+    }
 }
 
 #[cfg(test)]
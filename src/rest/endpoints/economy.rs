@@ -183,6 +183,10 @@ impl RestRequest for GetTreasuryYieldsRequest {
         "/v1/economy/treasury-yields".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v1/economy/treasury-yields"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("date.gte", self.date_from.as_ref());
@@ -200,6 +204,10 @@ impl PaginatableRequest for GetTreasuryYieldsRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -313,6 +321,10 @@ impl RestRequest for GetInflationRequest {
         "/v1/economy/inflation".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v1/economy/inflation"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("date.gte", self.date_from.as_ref());
@@ -330,6 +342,10 @@ impl PaginatableRequest for GetInflationRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -454,6 +470,10 @@ impl RestRequest for GetFedFundsRateRequest {
         "/v1/economy/fed-funds-rate".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v1/economy/fed-funds-rate"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("date.gte", self.date_from.as_ref());
@@ -471,6 +491,10 @@ impl PaginatableRequest for GetFedFundsRateRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
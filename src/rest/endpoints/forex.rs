@@ -131,6 +131,10 @@ impl RestRequest for GetForexQuoteRequest {
     fn path(&self) -> Cow<'static, str> {
         format!("/v1/last_quote/currencies/{}/{}", self.from, self.to).into()
     }
+
+    fn endpoint(&self) -> &'static str {
+        "/v1/last_quote/currencies/{from}/{to}"
+    }
 }
 
 // ============================================================================
@@ -244,6 +248,10 @@ impl RestRequest for GetForexConversionRequest {
         format!("/v1/conversion/{}/{}", self.from, self.to).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v1/conversion/{from}/{to}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("amount", self.amount);
@@ -435,6 +443,10 @@ impl RestRequest for GetForexSnapshotRequest {
         "/v2/snapshot/locale/global/markets/forex/tickers".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v2/snapshot/locale/global/markets/forex/tickers"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         if let Some(ref tickers) = self.tickers {
@@ -490,6 +502,10 @@ impl RestRequest for GetForexGainersLosersRequest {
         )
         .into()
     }
+
+    fn endpoint(&self) -> &'static str {
+        "/v2/snapshot/locale/global/markets/forex/{direction}"
+    }
 }
 
 #[cfg(test)]
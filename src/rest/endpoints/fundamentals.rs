@@ -308,6 +308,10 @@ impl RestRequest for GetBalanceSheetsRequest {
         "/vX/reference/financials".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/vX/reference/financials"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("ticker", self.ticker.as_ref());
@@ -333,6 +337,10 @@ impl PaginatableRequest for GetBalanceSheetsRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -540,6 +548,10 @@ impl RestRequest for GetIncomeStatementsRequest {
         "/vX/reference/financials".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/vX/reference/financials"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("ticker", self.ticker.as_ref());
@@ -560,6 +572,10 @@ impl PaginatableRequest for GetIncomeStatementsRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -723,6 +739,10 @@ impl RestRequest for GetCashFlowStatementsRequest {
         "/vX/reference/financials".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/vX/reference/financials"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("ticker", self.ticker.as_ref());
@@ -743,6 +763,10 @@ impl PaginatableRequest for GetCashFlowStatementsRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -854,6 +878,10 @@ impl RestRequest for GetShortInterestRequest {
         format!("/v3/reference/short-interest/{}", self.ticker).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v3/reference/short-interest/{ticker}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("limit", self.limit);
@@ -869,6 +897,10 @@ impl PaginatableRequest for GetShortInterestRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -990,6 +1022,10 @@ impl RestRequest for GetShortVolumeRequest {
         format!("/v3/reference/short-volume/{}", self.ticker).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v3/reference/short-volume/{ticker}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("limit", self.limit);
@@ -1006,6 +1042,10 @@ impl PaginatableRequest for GetShortVolumeRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
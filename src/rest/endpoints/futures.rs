@@ -270,6 +270,10 @@ impl RestRequest for GetFuturesContractsRequest {
         format!("/futures/{}/contracts", FUTURES_API_VERSION).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/futures/vX/contracts"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("product_code", self.product_code.clone());
@@ -295,6 +299,10 @@ impl PaginatableRequest for GetFuturesContractsRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -338,6 +346,10 @@ impl RestRequest for GetFuturesContractRequest {
     fn path(&self) -> Cow<'static, str> {
         format!("/futures/{}/contracts/{}", FUTURES_API_VERSION, self.ticker).into()
     }
+
+    fn endpoint(&self) -> &'static str {
+        "/futures/vX/contracts/{ticker}"
+    }
 }
 
 // ============================================================================
@@ -450,6 +462,10 @@ impl RestRequest for GetFuturesProductsRequest {
         format!("/futures/{}/products", FUTURES_API_VERSION).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/futures/vX/products"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("asset_class", self.asset_class.clone());
@@ -467,6 +483,10 @@ impl PaginatableRequest for GetFuturesProductsRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -504,6 +524,10 @@ impl RestRequest for GetFuturesProductRequest {
     fn path(&self) -> Cow<'static, str> {
         format!("/futures/{}/products/{}", FUTURES_API_VERSION, self.code).into()
     }
+
+    fn endpoint(&self) -> &'static str {
+        "/futures/vX/products/{code}"
+    }
 }
 
 // ============================================================================
@@ -571,6 +595,10 @@ impl RestRequest for GetFuturesSchedulesRequest {
         format!("/futures/{}/schedules", FUTURES_API_VERSION).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/futures/vX/schedules"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("trading_venue", self.trading_venue.clone());
@@ -614,6 +642,10 @@ impl RestRequest for GetFuturesProductScheduleRequest {
         )
         .into()
     }
+
+    fn endpoint(&self) -> &'static str {
+        "/futures/vX/products/{code}/schedules"
+    }
 }
 
 // ============================================================================
@@ -779,6 +811,10 @@ impl RestRequest for GetFuturesSnapshotRequest {
         format!("/futures/{}/snapshot", FUTURES_API_VERSION).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/futures/vX/snapshot"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         if !self.tickers.is_empty() {
@@ -266,6 +266,10 @@ impl RestRequest for GetRsiRequest {
         format!("/v1/indicators/rsi/{}", self.ticker).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v1/indicators/rsi/{ticker}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("timestamp", self.timestamp.clone());
@@ -291,6 +295,10 @@ impl PaginatableRequest for GetRsiRequest {
         response.results.values
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.values.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -507,6 +515,8 @@ pub trait Indicator: Send + Sync + 'static {
     const PATH_SEGMENT: &'static str;
     /// Human-readable name of the indicator.
     const NAME: &'static str;
+    /// Route template used to tag metrics (e.g., "/v1/indicators/rsi/{ticker}").
+    const ENDPOINT: &'static str = "/v1/indicators/{indicator}/{ticker}";
 }
 
 /// RSI indicator marker.
@@ -516,6 +526,7 @@ pub struct Rsi;
 impl Indicator for Rsi {
     const PATH_SEGMENT: &'static str = "rsi";
     const NAME: &'static str = "Relative Strength Index";
+    const ENDPOINT: &'static str = "/v1/indicators/rsi/{ticker}";
 }
 
 /// Simple Moving Average indicator marker.
@@ -525,6 +536,7 @@ pub struct Sma;
 impl Indicator for Sma {
     const PATH_SEGMENT: &'static str = "sma";
     const NAME: &'static str = "Simple Moving Average";
+    const ENDPOINT: &'static str = "/v1/indicators/sma/{ticker}";
 }
 
 /// Exponential Moving Average indicator marker.
@@ -534,6 +546,7 @@ pub struct Ema;
 impl Indicator for Ema {
     const PATH_SEGMENT: &'static str = "ema";
     const NAME: &'static str = "Exponential Moving Average";
+    const ENDPOINT: &'static str = "/v1/indicators/ema/{ticker}";
 }
 
 /// MACD (Moving Average Convergence/Divergence) indicator marker.
@@ -543,6 +556,7 @@ pub struct Macd;
 impl Indicator for Macd {
     const PATH_SEGMENT: &'static str = "macd";
     const NAME: &'static str = "Moving Average Convergence/Divergence";
+    const ENDPOINT: &'static str = "/v1/indicators/macd/{ticker}";
 }
 
 // =============================================================================
@@ -730,6 +744,10 @@ impl<I: Indicator> RestRequest for GetIndicatorRequest<I> {
         format!("/v1/indicators/{}/{}", I::PATH_SEGMENT, self.ticker).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        I::ENDPOINT
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("timestamp", self.timestamp.clone());
@@ -755,6 +773,10 @@ impl<I: Indicator + Clone> PaginatableRequest for GetIndicatorRequest<I> {
         response.results.values
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.values.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -1007,6 +1029,10 @@ impl RestRequest for GetMacdRequest {
         format!("/v1/indicators/macd/{}", self.ticker).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v1/indicators/macd/{ticker}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("timestamp", self.timestamp.clone());
@@ -1034,6 +1060,10 @@ impl PaginatableRequest for GetMacdRequest {
         response.results.values
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.values.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -1147,6 +1177,7 @@ mod generic_indicator_tests {
     fn test_indicator_trait() {
         assert_eq!(Rsi::PATH_SEGMENT, "rsi");
         assert_eq!(Rsi::NAME, "Relative Strength Index");
+        assert_eq!(Rsi::ENDPOINT, "/v1/indicators/rsi/{ticker}");
         assert_eq!(Sma::PATH_SEGMENT, "sma");
         assert_eq!(Sma::NAME, "Simple Moving Average");
         assert_eq!(Ema::PATH_SEGMENT, "ema");
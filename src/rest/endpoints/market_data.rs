@@ -3,9 +3,10 @@
 //! This module contains request types for fetching market data
 //! from the Massive API.
 
-use crate::models::AggregateBar;
+use crate::models::{AggregateBar, Price};
 use crate::rest::models::ListEnvelope;
-use crate::rest::request::{PaginatableRequest, QueryBuilder, RestRequest};
+use crate::rest::request::{PaginatableRequest, QueryBuilder, RestRequest, ShardableRequest};
+use crate::rest::shard::{self, NANOS_PER_DAY, NANOS_PER_MILLI};
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
@@ -32,6 +33,20 @@ pub enum Timespan {
     Year,
 }
 
+impl Timespan {
+    /// Length of one unit in nanoseconds, or `None` for calendar units of
+    /// varying length (week and longer).
+    fn fixed_nanos(self) -> Option<i64> {
+        match self {
+            Timespan::Second => Some(1_000_000_000),
+            Timespan::Minute => Some(60_000_000_000),
+            Timespan::Hour => Some(3_600_000_000_000),
+            Timespan::Day => Some(NANOS_PER_DAY),
+            Timespan::Week | Timespan::Month | Timespan::Quarter | Timespan::Year => None,
+        }
+    }
+}
+
 impl std::fmt::Display for Timespan {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -197,6 +212,10 @@ impl RestRequest for GetAggsRequest {
         .into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v2/aggs/ticker/{ticker}/range/{multiplier}/{timespan}/{from}/{to}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("adjusted", self.adjusted);
@@ -213,11 +232,77 @@ impl PaginatableRequest for GetAggsRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
 }
 
+/// Maximum number of base aggregates (bars of multiplier 1) covered by one
+/// aggregates request.
+pub const MAX_AGGS_PER_REQUEST: i64 = 50_000;
+
+/// Splits on whole bars, and on whole days when both bounds are
+/// `YYYY-MM-DD` dates so sub-ranges are sent as dates too. Mixing a date
+/// with a Unix millisecond bound sends millisecond bounds, taking the date
+/// as midnight UTC.
+impl ShardableRequest for GetAggsRequest {
+    fn time_range(&self) -> Option<(i64, i64)> {
+        // `from` and `to` are dates or Unix milliseconds; `to` is inclusive
+        let (start, _) = shard::parse_bound(&self.from, NANOS_PER_MILLI)?;
+        let (end, resolution) = shard::parse_bound(&self.to, NANOS_PER_MILLI)?;
+        Some((start, end.checked_add(resolution)?))
+    }
+
+    fn with_time_range(&self, start: i64, end: i64) -> Self {
+        let mut req = self.clone();
+        if self.date_bounds() {
+            req.from = shard::format_date(start);
+            req.to = shard::format_date(end - NANOS_PER_DAY);
+        } else {
+            req.from = (start / NANOS_PER_MILLI).to_string();
+            req.to = (end / NANOS_PER_MILLI - 1).to_string();
+        }
+        req.sort = Some(Sort::Asc);
+        req
+    }
+
+    /// Whole bars, so no bar is split between two requests.
+    fn range_step(&self) -> Option<i64> {
+        let bar = self.bar_nanos()?;
+        if self.date_bounds() {
+            shard::lcm(bar, NANOS_PER_DAY)
+        } else {
+            Some(bar)
+        }
+    }
+
+    /// The span of [`MAX_AGGS_PER_REQUEST`] base aggregates, whatever the
+    /// multiplier.
+    fn max_range(&self) -> Option<i64> {
+        self.timespan
+            .fixed_nanos()?
+            .checked_mul(MAX_AGGS_PER_REQUEST)
+    }
+}
+
+impl GetAggsRequest {
+    /// Length of one bar in nanoseconds, if fixed.
+    fn bar_nanos(&self) -> Option<i64> {
+        self.timespan
+            .fixed_nanos()?
+            .checked_mul(i64::from(self.multiplier.max(1)))
+    }
+
+    /// Whether both bounds are `YYYY-MM-DD` dates.
+    fn date_bounds(&self) -> bool {
+        shard::is_date(&self.from) && shard::is_date(&self.to)
+    }
+}
+
 /// Request for previous day's data.
 ///
 /// Returns the open, high, low, and close (OHLC) for a ticker symbol
@@ -257,6 +342,10 @@ impl RestRequest for GetPreviousCloseRequest {
         format!("/v2/aggs/ticker/{}/prev", self.ticker).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v2/aggs/ticker/{ticker}/prev"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("adjusted", self.adjusted);
@@ -305,21 +394,21 @@ pub struct DailyOpenCloseResponse {
     /// Ticker symbol
     pub symbol: String,
     /// Open price
-    pub open: f64,
+    pub open: Price,
     /// High price
-    pub high: f64,
+    pub high: Price,
     /// Low price
-    pub low: f64,
+    pub low: Price,
     /// Close price
-    pub close: f64,
+    pub close: Price,
     /// Volume
     pub volume: f64,
     /// After hours price
     #[serde(rename = "afterHours")]
-    pub after_hours: Option<f64>,
+    pub after_hours: Option<Price>,
     /// Premarket price
     #[serde(rename = "preMarket")]
-    pub pre_market: Option<f64>,
+    pub pre_market: Option<Price>,
 }
 
 impl RestRequest for GetDailyOpenCloseRequest {
@@ -333,6 +422,10 @@ impl RestRequest for GetDailyOpenCloseRequest {
         format!("/v1/open-close/{}/{}", self.ticker, self.date).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v1/open-close/{ticker}/{date}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("adjusted", self.adjusted);
@@ -343,6 +436,40 @@ impl RestRequest for GetDailyOpenCloseRequest {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::price::px;
+
+    #[test]
+    fn test_aggs_shard_range() {
+        let req = GetAggsRequest::new("AAPL")
+            .multiplier(5)
+            .timespan(Timespan::Minute)
+            .from("2024-01-01")
+            .to("2024-01-02");
+        let day = 1_704_067_200_000_000_000;
+        assert_eq!(req.time_range(), Some((day, day + 2 * NANOS_PER_DAY)));
+        // Date bounds split on whole days
+        assert_eq!(req.range_step(), Some(NANOS_PER_DAY));
+        // The limit counts base aggregates, so the multiplier doesn't matter
+        assert_eq!(req.max_range(), Some(50_000 * 60_000_000_000));
+        assert_eq!(req.clone().multiplier(1).max_range(), req.max_range());
+
+        let shard = req.with_time_range(day, day + NANOS_PER_DAY);
+        assert_eq!(shard.from, "2024-01-01");
+        assert_eq!(shard.to, "2024-01-01");
+        assert_eq!(shard.sort, Some(Sort::Asc));
+        assert_eq!(shard.time_range(), Some((day, day + NANOS_PER_DAY)));
+
+        // A millisecond bound takes the date as midnight UTC
+        let req = req.to("1704153599999");
+        assert_eq!(req.time_range(), Some((day, day + NANOS_PER_DAY)));
+        assert_eq!(req.range_step(), Some(300_000_000_000));
+        let shard = req.with_time_range(day, day + NANOS_PER_DAY);
+        assert_eq!(shard.from, "1704067200000");
+        assert_eq!(shard.to, "1704153599999");
+
+        // Calendar timespans are not split
+        assert_eq!(req.timespan(Timespan::Month).range_step(), None);
+    }
 
     #[test]
     fn test_timespan_display() {
@@ -374,6 +501,10 @@ mod tests {
             req.path(),
             "/v2/aggs/ticker/AAPL/range/1/day/2024-01-01/2024-01-31"
         );
+        assert_eq!(
+            req.endpoint(),
+            "/v2/aggs/ticker/{ticker}/range/{multiplier}/{timespan}/{from}/{to}"
+        );
     }
 
     #[test]
@@ -436,6 +567,6 @@ mod tests {
         assert_eq!(response.status, Some("OK".to_string()));
         assert_eq!(response.ticker, Some("AAPL".to_string()));
         assert_eq!(response.results.len(), 1);
-        assert_eq!(response.results[0].open, 150.0);
+        assert_eq!(response.results[0].open, px(150.0));
     }
 }
@@ -36,6 +36,10 @@ impl RestRequest for GetMarketStatusRequest {
     fn path(&self) -> Cow<'static, str> {
         "/v1/marketstatus/now".into()
     }
+
+    fn endpoint(&self) -> &'static str {
+        "/v1/marketstatus/now"
+    }
 }
 
 /// Response from the market status endpoint.
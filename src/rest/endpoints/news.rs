@@ -230,6 +230,10 @@ impl RestRequest for GetNewsRequest {
         "/v2/reference/news".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v2/reference/news"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("ticker", self.ticker.as_ref());
@@ -252,6 +256,10 @@ impl PaginatableRequest for GetNewsRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -316,6 +324,10 @@ impl RestRequest for GetRelatedCompaniesRequest {
     fn path(&self) -> Cow<'static, str> {
         format!("/v1/related-companies/{}", self.ticker).into()
     }
+
+    fn endpoint(&self) -> &'static str {
+        "/v1/related-companies/{ticker}"
+    }
 }
 
 // ============================================================================
@@ -430,6 +442,10 @@ impl RestRequest for GetTickerEventsRequest {
         format!("/vX/reference/tickers/{}/events", self.ticker).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/vX/reference/tickers/{ticker}/events"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         if let Some(ref types) = self.types {
@@ -24,6 +24,7 @@
 //! let chain = GetOptionsChainRequest::new("AAPL");
 //! ```
 
+use crate::models::price::{self, Price};
 use crate::rest::models::ListEnvelope;
 use crate::rest::request::{PaginatableRequest, QueryBuilder, RestRequest};
 use reqwest::Method;
@@ -325,6 +326,10 @@ impl RestRequest for GetOptionsContractsRequest {
         "/v3/reference/options/contracts".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v3/reference/options/contracts"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("underlying_ticker", self.underlying_ticker.clone());
@@ -356,6 +361,10 @@ impl PaginatableRequest for GetOptionsContractsRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -409,6 +418,10 @@ impl RestRequest for GetOptionsContractRequest {
         format!("/v3/reference/options/contracts/{}", self.ticker).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v3/reference/options/contracts/{ticker}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("as_of", self.as_of.clone());
@@ -454,23 +467,23 @@ impl OptionGreeks {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptionDayStats {
     /// Open price.
-    pub open: Option<f64>,
+    pub open: Option<Price>,
     /// High price.
-    pub high: Option<f64>,
+    pub high: Option<Price>,
     /// Low price.
-    pub low: Option<f64>,
+    pub low: Option<Price>,
     /// Close price.
-    pub close: Option<f64>,
+    pub close: Option<Price>,
     /// Trading volume.
     pub volume: Option<f64>,
     /// Volume-weighted average price.
-    pub vwap: Option<f64>,
+    pub vwap: Option<Price>,
     /// Change from previous close.
     pub change: Option<f64>,
     /// Change percentage.
     pub change_percent: Option<f64>,
     /// Previous close.
-    pub previous_close: Option<f64>,
+    pub previous_close: Option<Price>,
     /// Last updated timestamp.
     pub last_updated: Option<i64>,
 }
@@ -496,34 +509,37 @@ pub struct OptionChainDetails {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptionLastQuote {
     /// Ask price.
-    pub ask: f64,
+    pub ask: Price,
     /// Ask size.
     pub ask_size: Option<f64>,
     /// Ask exchange ID.
     pub ask_exchange: Option<u8>,
     /// Bid price.
-    pub bid: f64,
+    pub bid: Price,
     /// Bid size.
     pub bid_size: Option<f64>,
     /// Bid exchange ID.
     pub bid_exchange: Option<u8>,
     /// Midpoint price.
-    pub midpoint: Option<f64>,
+    pub midpoint: Option<Price>,
     /// Quote timestamp.
     pub last_updated: Option<i64>,
 }
 
 impl OptionLastQuote {
     /// Calculate the bid-ask spread.
-    pub fn spread(&self) -> f64 {
+    pub fn spread(&self) -> Price {
         self.ask - self.bid
     }
 
     /// Calculate the spread as percentage of midpoint.
     pub fn spread_percent(&self) -> Option<f64> {
-        let mid = self.midpoint.unwrap_or((self.bid + self.ask) / 2.0);
+        let mid = price::to_f64(
+            self.midpoint
+                .unwrap_or_else(|| price::mid(self.bid, self.ask)),
+        );
         if mid > 0.0 {
-            Some((self.spread() / mid) * 100.0)
+            Some((price::to_f64(self.spread()) / mid) * 100.0)
         } else {
             None
         }
@@ -534,7 +550,7 @@ impl OptionLastQuote {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptionLastTrade {
     /// Trade price.
-    pub price: f64,
+    pub price: Price,
     /// Trade size.
     pub size: Option<f64>,
     /// Exchange ID.
@@ -554,7 +570,7 @@ pub struct OptionUnderlyingAsset {
     /// Underlying ticker symbol.
     pub ticker: String,
     /// Last price of the underlying.
-    pub price: Option<f64>,
+    pub price: Option<Price>,
     /// Change in underlying price.
     pub change: Option<f64>,
     /// Change percentage of underlying.
@@ -569,7 +585,7 @@ pub struct OptionUnderlyingAsset {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptionChainResult {
     /// Break-even price.
-    pub break_even_price: Option<f64>,
+    pub break_even_price: Option<Price>,
     /// Day statistics.
     pub day: Option<OptionDayStats>,
     /// Contract details.
@@ -615,8 +631,8 @@ impl OptionChainResult {
     }
 
     /// Get the mid price from the last quote.
-    pub fn mid_price(&self) -> Option<f64> {
-        self.last_quote.as_ref().map(|q| (q.bid + q.ask) / 2.0)
+    pub fn mid_price(&self) -> Option<Price> {
+        self.last_quote.as_ref().map(|q| price::mid(q.bid, q.ask))
     }
 
     /// Get the delta value.
@@ -634,7 +650,7 @@ impl OptionChainResult {
 
     /// Calculate the extrinsic (time) value given the mid price and underlying.
     pub fn extrinsic_value(&self, underlying_price: f64) -> Option<f64> {
-        let mid = self.mid_price()?;
+        let mid = price::to_f64(self.mid_price()?);
         Some(mid - self.intrinsic_value(underlying_price))
     }
 }
@@ -843,6 +859,10 @@ impl RestRequest for GetOptionsChainRequest {
         format!("/v3/snapshot/options/{}", self.underlying_ticker).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v3/snapshot/options/{underlying_ticker}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("contract_type", self.contract_type.map(|c| c.to_string()));
@@ -867,6 +887,10 @@ impl PaginatableRequest for GetOptionsChainRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -875,6 +899,7 @@ impl PaginatableRequest for GetOptionsChainRequest {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::price::px;
 
     #[test]
     fn test_contract_type_display() {
@@ -1001,17 +1026,17 @@ mod tests {
     #[test]
     fn test_option_last_quote_helpers() {
         let quote = OptionLastQuote {
-            ask: 5.00,
+            ask: px(5.00),
             ask_size: Some(10.0),
             ask_exchange: None,
-            bid: 4.80,
+            bid: px(4.80),
             bid_size: Some(20.0),
             bid_exchange: None,
-            midpoint: Some(4.90),
+            midpoint: Some(px(4.90)),
             last_updated: None,
         };
 
-        assert!((quote.spread() - 0.20).abs() < 0.001);
+        assert!((quote.spread() - px(0.20)).abs() < px(0.001));
         let spread_pct = quote.spread_percent().unwrap();
         assert!((spread_pct - 4.08).abs() < 0.1);
     }
@@ -1019,7 +1044,7 @@ mod tests {
     #[test]
     fn test_option_chain_result_helpers() {
         let result = OptionChainResult {
-            break_even_price: Some(155.0),
+            break_even_price: Some(px(155.0)),
             day: None,
             details: OptionChainDetails {
                 contract_type: ContractType::Call,
@@ -1038,13 +1063,13 @@ mod tests {
             implied_volatility: Some(0.25),
             open_interest: Some(5000),
             last_quote: Some(OptionLastQuote {
-                ask: 5.10,
+                ask: px(5.10),
                 ask_size: Some(10.0),
                 ask_exchange: None,
-                bid: 4.90,
+                bid: px(4.90),
                 bid_size: Some(20.0),
                 bid_exchange: None,
-                midpoint: Some(5.0),
+                midpoint: Some(px(5.0)),
                 last_updated: None,
             }),
             last_trade: None,
@@ -1055,7 +1080,7 @@ mod tests {
         assert!(!result.is_put());
         assert_eq!(result.strike(), 150.0);
         assert_eq!(result.expiration(), "2025-12-19");
-        assert_eq!(result.mid_price(), Some(5.0));
+        assert_eq!(result.mid_price(), Some(px(5.0)));
         assert_eq!(result.delta(), Some(0.55));
 
         // Intrinsic value when underlying = 160
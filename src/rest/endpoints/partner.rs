@@ -242,6 +242,10 @@ impl RestRequest for GetEarningsRequest {
         "/v2/reference/earnings".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v2/reference/earnings"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("ticker", self.ticker.as_ref());
@@ -262,6 +266,10 @@ impl PaginatableRequest for GetEarningsRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -439,6 +447,10 @@ impl RestRequest for GetAnalystRatingsRequest {
         "/v2/reference/analyst-ratings".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v2/reference/analyst-ratings"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("ticker", self.ticker.as_ref());
@@ -457,6 +469,10 @@ impl PaginatableRequest for GetAnalystRatingsRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -582,6 +598,10 @@ impl RestRequest for GetEtfProfilesRequest {
         "/vX/reference/etfs".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/vX/reference/etfs"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("ticker", self.ticker.as_ref());
@@ -599,6 +619,10 @@ impl PaginatableRequest for GetEtfProfilesRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -691,6 +715,10 @@ impl RestRequest for GetEtfHoldingsRequest {
         format!("/vX/reference/etfs/{}/holdings", self.ticker).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/vX/reference/etfs/{ticker}/holdings"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("limit", self.limit);
@@ -706,6 +734,10 @@ impl PaginatableRequest for GetEtfHoldingsRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -3,7 +3,9 @@
 //! This module contains request types for fetching quote (NBBO) data
 //! from the Massive API.
 
-use crate::rest::request::{PaginatableRequest, QueryBuilder, RestRequest};
+use crate::models::price::{self, Price};
+use crate::rest::request::{PaginatableRequest, QueryBuilder, RestRequest, ShardableRequest};
+use crate::rest::shard;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
@@ -26,7 +28,7 @@ pub struct Quote {
     pub ask_exchange: Option<u8>,
     /// Ask price
     #[serde(rename = "P", alias = "ask_price")]
-    pub ask_price: f64,
+    pub ask_price: Price,
     /// Ask size
     #[serde(rename = "S", alias = "ask_size")]
     pub ask_size: u64,
@@ -35,7 +37,7 @@ pub struct Quote {
     pub bid_exchange: Option<u8>,
     /// Bid price
     #[serde(rename = "p", alias = "bid_price")]
-    pub bid_price: f64,
+    pub bid_price: Price,
     /// Bid size
     #[serde(rename = "s", alias = "bid_size")]
     pub bid_size: u64,
@@ -61,13 +63,13 @@ pub struct Quote {
 
 impl Quote {
     /// Calculate the bid-ask spread.
-    pub fn spread(&self) -> f64 {
+    pub fn spread(&self) -> Price {
         self.ask_price - self.bid_price
     }
 
     /// Calculate the mid price.
-    pub fn mid_price(&self) -> f64 {
-        (self.ask_price + self.bid_price) / 2.0
+    pub fn mid_price(&self) -> Price {
+        price::mid(self.ask_price, self.bid_price)
     }
 
     /// Check if the market is crossed (bid > ask).
@@ -77,7 +79,7 @@ impl Quote {
 
     /// Check if the market is locked (bid == ask).
     pub fn is_locked(&self) -> bool {
-        (self.bid_price - self.ask_price).abs() < f64::EPSILON
+        price::to_f64((self.bid_price - self.ask_price).abs()) < f64::EPSILON
     }
 }
 
@@ -195,6 +197,10 @@ impl RestRequest for GetQuotesRequest {
         format!("/v3/quotes/{}", self.ticker).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v3/quotes/{ticker}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("timestamp.gt", self.timestamp_gt.clone());
@@ -215,11 +221,37 @@ impl PaginatableRequest for GetQuotesRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
 }
 
+impl ShardableRequest for GetQuotesRequest {
+    fn time_range(&self) -> Option<(i64, i64)> {
+        shard::timestamp_range(
+            self.timestamp_gte.as_deref(),
+            self.timestamp_gt.as_deref(),
+            self.timestamp_lt.as_deref(),
+            self.timestamp_lte.as_deref(),
+        )
+    }
+
+    fn with_time_range(&self, start: i64, end: i64) -> Self {
+        let mut req = self.clone();
+        req.timestamp_gt = None;
+        req.timestamp_gte = Some(start.to_string());
+        req.timestamp_lt = Some(end.to_string());
+        req.timestamp_lte = None;
+        req.order = Some("asc".into());
+        req.sort = Some("timestamp".into());
+        req
+    }
+}
+
 /// Request for last quote (NBBO).
 ///
 /// Returns the most recent NBBO for a ticker symbol.
@@ -259,11 +291,16 @@ impl RestRequest for GetLastQuoteRequest {
     fn path(&self) -> Cow<'static, str> {
         format!("/v2/last/nbbo/{}", self.ticker).into()
     }
+
+    fn endpoint(&self) -> &'static str {
+        "/v2/last/nbbo/{ticker}"
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::price::px;
 
     #[test]
     fn test_get_quotes_request_path() {
@@ -293,10 +330,10 @@ mod tests {
         let quote = Quote {
             ticker: Some("AAPL".into()),
             ask_exchange: Some(4),
-            ask_price: 150.50,
+            ask_price: px(150.50),
             ask_size: 100,
             bid_exchange: Some(7),
-            bid_price: 150.40,
+            bid_price: px(150.40),
             bid_size: 200,
             conditions: vec![],
             sip_timestamp: Some(1703001234567),
@@ -306,8 +343,8 @@ mod tests {
             tape: Some(3),
         };
 
-        assert!((quote.spread() - 0.10).abs() < 0.001);
-        assert!((quote.mid_price() - 150.45).abs() < 0.001);
+        assert!((quote.spread() - px(0.10)).abs() < px(0.001));
+        assert!((quote.mid_price() - px(150.45)).abs() < px(0.001));
         assert!(!quote.is_crossed());
         assert!(!quote.is_locked());
     }
@@ -317,10 +354,10 @@ mod tests {
         let quote = Quote {
             ticker: None,
             ask_exchange: Some(4),
-            ask_price: 150.30,
+            ask_price: px(150.30),
             ask_size: 100,
             bid_exchange: Some(7),
-            bid_price: 150.50,
+            bid_price: px(150.50),
             bid_size: 200,
             conditions: vec![],
             sip_timestamp: None,
@@ -355,6 +392,6 @@ mod tests {
         let response: QuotesResponse = serde_json::from_str(json).unwrap();
         assert_eq!(response.status, Some("OK".to_string()));
         assert_eq!(response.results.len(), 1);
-        assert_eq!(response.results[0].ask_price, 150.50);
+        assert_eq!(response.results[0].ask_price, px(150.50));
     }
 }
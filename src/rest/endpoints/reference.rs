@@ -140,6 +140,10 @@ impl RestRequest for GetTickersRequest {
         "/v3/reference/tickers".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v3/reference/tickers"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("ticker", self.ticker.clone());
@@ -163,6 +167,10 @@ impl PaginatableRequest for GetTickersRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
@@ -293,6 +301,10 @@ impl RestRequest for GetTickerDetailsRequest {
         format!("/v3/reference/tickers/{}", self.ticker).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v3/reference/tickers/{ticker}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("date", self.date.clone());
@@ -438,6 +450,10 @@ impl RestRequest for GetExchangesRequest {
         "/v3/reference/exchanges".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v3/reference/exchanges"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("asset_class", self.asset_class.clone());
@@ -648,6 +664,10 @@ impl RestRequest for GetConditionsRequest {
         "/v3/reference/conditions".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v3/reference/conditions"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("asset_class", self.asset_class.clone());
@@ -764,6 +784,10 @@ impl RestRequest for GetTickerTypesRequest {
         "/v3/reference/tickers/types".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v3/reference/tickers/types"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("asset_class", self.asset_class.clone());
@@ -847,6 +871,10 @@ impl RestRequest for GetMarketHolidaysRequest {
         "/v1/marketstatus/upcoming".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v1/marketstatus/upcoming"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         Vec::new()
     }
@@ -9,6 +9,7 @@
 //! - Unified multi-asset snapshots
 //! - Grouped daily market summaries
 
+use crate::models::price::{self, Price};
 use crate::rest::request::{QueryBuilder, RestRequest};
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
@@ -47,22 +48,22 @@ pub struct TickerSnapshot {
 pub struct DaySnapshot {
     /// Open price
     #[serde(rename = "o")]
-    pub open: f64,
+    pub open: Price,
     /// High price
     #[serde(rename = "h")]
-    pub high: f64,
+    pub high: Price,
     /// Low price
     #[serde(rename = "l")]
-    pub low: f64,
+    pub low: Price,
     /// Close price
     #[serde(rename = "c")]
-    pub close: f64,
+    pub close: Price,
     /// Volume
     #[serde(rename = "v")]
     pub volume: f64,
     /// VWAP
     #[serde(rename = "vw")]
-    pub vwap: Option<f64>,
+    pub vwap: Option<Price>,
 }
 
 /// Quote snapshot.
@@ -70,13 +71,13 @@ pub struct DaySnapshot {
 pub struct SnapshotQuote {
     /// Bid price
     #[serde(rename = "p")]
-    pub bid_price: f64,
+    pub bid_price: Price,
     /// Bid size
     #[serde(rename = "s")]
     pub bid_size: u64,
     /// Ask price (uppercase P in some responses)
     #[serde(rename = "P", default)]
-    pub ask_price: Option<f64>,
+    pub ask_price: Option<Price>,
     /// Ask size (uppercase S in some responses)
     #[serde(rename = "S", default)]
     pub ask_size: Option<u64>,
@@ -90,7 +91,7 @@ pub struct SnapshotQuote {
 pub struct SnapshotTrade {
     /// Price
     #[serde(rename = "p")]
-    pub price: f64,
+    pub price: Price,
     /// Size
     #[serde(rename = "s")]
     pub size: u64,
@@ -116,22 +117,22 @@ pub struct MinuteSnapshot {
     pub accumulated_volume: Option<f64>,
     /// Open price
     #[serde(rename = "o")]
-    pub open: f64,
+    pub open: Price,
     /// High price
     #[serde(rename = "h")]
-    pub high: f64,
+    pub high: Price,
     /// Low price
     #[serde(rename = "l")]
-    pub low: f64,
+    pub low: Price,
     /// Close price
     #[serde(rename = "c")]
-    pub close: f64,
+    pub close: Price,
     /// Volume
     #[serde(rename = "v")]
     pub volume: f64,
     /// VWAP
     #[serde(rename = "vw")]
-    pub vwap: Option<f64>,
+    pub vwap: Option<Price>,
     /// Number of trades
     #[serde(rename = "n")]
     pub num_trades: Option<u64>,
@@ -201,6 +202,10 @@ impl RestRequest for GetTickerSnapshotRequest {
         )
         .into()
     }
+
+    fn endpoint(&self) -> &'static str {
+        "/v2/snapshot/locale/{locale}/markets/{market_type}/tickers/{ticker}"
+    }
 }
 
 /// Request for all tickers snapshot.
@@ -284,6 +289,10 @@ impl RestRequest for GetAllTickersSnapshotRequest {
         .into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v2/snapshot/locale/{locale}/markets/{market_type}/tickers"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         if let Some(ref tickers) = self.tickers {
@@ -367,6 +376,10 @@ impl RestRequest for GetGainersLosersRequest {
         .into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v2/snapshot/locale/{locale}/markets/{market_type}/{direction}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         if let Some(include_otc) = self.include_otc {
@@ -402,13 +415,13 @@ pub enum SnapshotMarketStatus {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SnapshotSession {
     /// Session close price.
-    pub close: Option<f64>,
+    pub close: Option<Price>,
     /// Session high price.
-    pub high: Option<f64>,
+    pub high: Option<Price>,
     /// Session low price.
-    pub low: Option<f64>,
+    pub low: Option<Price>,
     /// Session open price.
-    pub open: Option<f64>,
+    pub open: Option<Price>,
     /// Session volume.
     pub volume: Option<f64>,
     /// Session change from previous.
@@ -424,18 +437,18 @@ pub struct SnapshotSession {
     /// Late trading change percentage.
     pub late_trading_change_percent: Option<f64>,
     /// Previous close price.
-    pub previous_close: Option<f64>,
+    pub previous_close: Option<Price>,
 }
 
 /// Last quote in a unified snapshot.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SnapshotLastQuote {
     /// Ask price.
-    pub ask: f64,
+    pub ask: Price,
     /// Ask size (shares or contracts).
     pub ask_size: Option<f64>,
     /// Bid price.
-    pub bid: f64,
+    pub bid: Price,
     /// Bid size (shares or contracts).
     pub bid_size: Option<f64>,
     /// Ask exchange ID.
@@ -445,24 +458,25 @@ pub struct SnapshotLastQuote {
     /// Quote timestamp (nanoseconds).
     pub last_updated: Option<i64>,
     /// Midpoint price.
-    pub midpoint: Option<f64>,
+    pub midpoint: Option<Price>,
     /// Timeframe of the data.
     pub timeframe: Option<String>,
 }
 
 impl SnapshotLastQuote {
     /// Calculate the bid-ask spread.
-    pub fn spread(&self) -> f64 {
+    pub fn spread(&self) -> Price {
         self.ask - self.bid
     }
 
     /// Calculate the spread as a percentage of the midpoint.
     pub fn spread_percent(&self) -> Option<f64> {
-        let mid = self
-            .midpoint
-            .or_else(|| Some((self.bid + self.ask) / 2.0))?;
+        let mid = price::to_f64(
+            self.midpoint
+                .unwrap_or_else(|| price::mid(self.bid, self.ask)),
+        );
         if mid > 0.0 {
-            Some((self.spread() / mid) * 100.0)
+            Some((price::to_f64(self.spread()) / mid) * 100.0)
         } else {
             None
         }
@@ -473,7 +487,7 @@ impl SnapshotLastQuote {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SnapshotLastTrade {
     /// Trade price.
-    pub price: f64,
+    pub price: Price,
     /// Trade size.
     pub size: Option<f64>,
     /// Exchange ID.
@@ -546,9 +560,9 @@ pub struct UnifiedSnapshotResult {
     /// Last trade data.
     pub last_trade: Option<SnapshotLastTrade>,
     /// Fair market value (for indices).
-    pub fmv: Option<f64>,
+    pub fmv: Option<Price>,
     /// Index value (for indices).
-    pub value: Option<f64>,
+    pub value: Option<Price>,
     /// Greeks data (for options).
     pub greeks: Option<SnapshotGreeks>,
     /// Implied volatility (for options).
@@ -556,7 +570,7 @@ pub struct UnifiedSnapshotResult {
     /// Open interest (for options).
     pub open_interest: Option<u64>,
     /// Break-even price (for options).
-    pub break_even_price: Option<f64>,
+    pub break_even_price: Option<Price>,
     /// Underlying ticker (for options).
     pub underlying_ticker: Option<String>,
     /// Error code if request failed for this ticker.
@@ -597,7 +611,7 @@ impl UnifiedSnapshotResult {
     }
 
     /// Get the current price from the last trade.
-    pub fn price(&self) -> Option<f64> {
+    pub fn price(&self) -> Option<Price> {
         self.last_trade.as_ref().map(|t| t.price)
     }
 
@@ -696,6 +710,10 @@ impl RestRequest for GetUnifiedSnapshotRequest {
         "/v3/snapshot".into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v3/snapshot"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("ticker.any_of", Some(self.tickers.join(",")));
@@ -715,22 +733,22 @@ pub struct GroupedDailyBar {
     pub ticker: String,
     /// Open price.
     #[serde(rename = "o")]
-    pub open: f64,
+    pub open: Price,
     /// High price.
     #[serde(rename = "h")]
-    pub high: f64,
+    pub high: Price,
     /// Low price.
     #[serde(rename = "l")]
-    pub low: f64,
+    pub low: Price,
     /// Close price.
     #[serde(rename = "c")]
-    pub close: f64,
+    pub close: Price,
     /// Trading volume.
     #[serde(rename = "v")]
     pub volume: f64,
     /// Volume-weighted average price.
     #[serde(rename = "vw")]
-    pub vwap: Option<f64>,
+    pub vwap: Option<Price>,
     /// Timestamp.
     #[serde(rename = "t")]
     pub timestamp: Option<i64>,
@@ -744,19 +762,20 @@ pub struct GroupedDailyBar {
 
 impl GroupedDailyBar {
     /// Calculate the daily range (high - low).
-    pub fn range(&self) -> f64 {
+    pub fn range(&self) -> Price {
         self.high - self.low
     }
 
     /// Calculate the daily change (close - open).
-    pub fn change(&self) -> f64 {
+    pub fn change(&self) -> Price {
         self.close - self.open
     }
 
     /// Calculate the daily change percentage.
     pub fn change_percent(&self) -> f64 {
-        if self.open > 0.0 {
-            (self.change() / self.open) * 100.0
+        let open = price::to_f64(self.open);
+        if open > 0.0 {
+            (price::to_f64(self.change()) / open) * 100.0
         } else {
             0.0
         }
@@ -774,7 +793,7 @@ impl GroupedDailyBar {
 
     /// Calculate the notional trading value.
     pub fn notional_volume(&self) -> Option<f64> {
-        self.vwap.map(|vw| vw * self.volume)
+        self.vwap.map(|vw| price::to_f64(vw) * self.volume)
     }
 }
 
@@ -926,6 +945,10 @@ impl RestRequest for GetGroupedDailyRequest {
         .into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v2/aggs/grouped/locale/{locale}/market/{market}/{date}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("adjusted", self.adjusted);
@@ -937,6 +960,7 @@ impl RestRequest for GetGroupedDailyRequest {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::price::px;
 
     #[test]
     fn test_get_ticker_snapshot_path() {
@@ -1000,8 +1024,8 @@ mod tests {
         assert_eq!(snapshot.todays_change, Some(2.50));
         assert!(snapshot.day.is_some());
         let day = snapshot.day.unwrap();
-        assert_eq!(day.open, 150.0);
-        assert_eq!(day.close, 153.0);
+        assert_eq!(day.open, px(150.0));
+        assert_eq!(day.close, px(153.0));
     }
 
     // =========================================================================
@@ -1081,18 +1105,18 @@ mod tests {
     #[test]
     fn test_snapshot_last_quote_spread() {
         let quote = SnapshotLastQuote {
-            ask: 150.10,
+            ask: px(150.10),
             ask_size: Some(100.0),
-            bid: 150.00,
+            bid: px(150.00),
             bid_size: Some(200.0),
             ask_exchange: None,
             bid_exchange: None,
             last_updated: None,
-            midpoint: Some(150.05),
+            midpoint: Some(px(150.05)),
             timeframe: None,
         };
 
-        assert!((quote.spread() - 0.10).abs() < 0.001);
+        assert!((quote.spread() - px(0.10)).abs() < px(0.001));
         let spread_pct = quote.spread_percent().unwrap();
         assert!((spread_pct - 0.0666).abs() < 0.01);
     }
@@ -1149,7 +1173,7 @@ mod tests {
         let successes: Vec<_> = response.successes().collect();
         assert_eq!(successes.len(), 1);
         assert_eq!(successes[0].ticker, "AAPL");
-        assert_eq!(successes[0].price(), Some(150.25));
+        assert_eq!(successes[0].price(), Some(px(150.25)));
 
         let failures: Vec<_> = response.failures().collect();
         assert_eq!(failures.len(), 1);
@@ -1205,19 +1229,19 @@ mod tests {
     fn test_grouped_daily_bar_helpers() {
         let bar = GroupedDailyBar {
             ticker: "AAPL".to_string(),
-            open: 150.0,
-            high: 155.0,
-            low: 148.0,
-            close: 153.0,
+            open: px(150.0),
+            high: px(155.0),
+            low: px(148.0),
+            close: px(153.0),
             volume: 1_000_000.0,
-            vwap: Some(152.0),
+            vwap: Some(px(152.0)),
             timestamp: None,
             num_trades: Some(50_000),
             otc: Some(false),
         };
 
-        assert!((bar.range() - 7.0).abs() < 0.001);
-        assert!((bar.change() - 3.0).abs() < 0.001);
+        assert!((bar.range() - px(7.0)).abs() < px(0.001));
+        assert!((bar.change() - px(3.0)).abs() < px(0.001));
         assert!((bar.change_percent() - 2.0).abs() < 0.001);
         assert!(bar.is_up_day());
         assert!(!bar.is_down_day());
@@ -1225,10 +1249,10 @@ mod tests {
 
         let down_bar = GroupedDailyBar {
             ticker: "MSFT".to_string(),
-            open: 300.0,
-            high: 305.0,
-            low: 295.0,
-            close: 297.0,
+            open: px(300.0),
+            high: px(305.0),
+            low: px(295.0),
+            close: px(297.0),
             volume: 500_000.0,
             vwap: None,
             timestamp: None,
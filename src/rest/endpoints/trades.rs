@@ -3,7 +3,9 @@
 //! This module contains request types for fetching trade data
 //! from the Massive API.
 
-use crate::rest::request::{PaginatableRequest, QueryBuilder, RestRequest};
+use crate::models::price::{self, Price};
+use crate::rest::request::{PaginatableRequest, QueryBuilder, RestRequest, ShardableRequest};
+use crate::rest::shard;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
@@ -32,7 +34,7 @@ pub struct Trade {
     pub exchange: Option<u8>,
     /// Price
     #[serde(rename = "p", alias = "price")]
-    pub price: f64,
+    pub price: Price,
     /// SIP timestamp
     #[serde(rename = "t", alias = "sip_timestamp")]
     pub sip_timestamp: Option<i64>,
@@ -58,8 +60,8 @@ pub struct Trade {
 
 impl Trade {
     /// Calculate the trade value (price * size).
-    pub fn value(&self) -> f64 {
-        self.price * self.size as f64
+    pub fn value(&self) -> Price {
+        self.price * price::from_u64(self.size)
     }
 }
 
@@ -177,6 +179,10 @@ impl RestRequest for GetTradesRequest {
         format!("/v3/trades/{}", self.ticker).into()
     }
 
+    fn endpoint(&self) -> &'static str {
+        "/v3/trades/{ticker}"
+    }
+
     fn query(&self) -> Vec<(Cow<'static, str>, String)> {
         let mut params = Vec::new();
         params.push_opt_param("timestamp.gt", self.timestamp_gt.clone());
@@ -197,11 +203,37 @@ impl PaginatableRequest for GetTradesRequest {
         response.results
     }
 
+    fn item_count(response: &Self::Response) -> usize {
+        response.results.len()
+    }
+
     fn extract_next_url(response: &Self::Response) -> Option<&str> {
         response.next_url.as_deref()
     }
 }
 
+impl ShardableRequest for GetTradesRequest {
+    fn time_range(&self) -> Option<(i64, i64)> {
+        shard::timestamp_range(
+            self.timestamp_gte.as_deref(),
+            self.timestamp_gt.as_deref(),
+            self.timestamp_lt.as_deref(),
+            self.timestamp_lte.as_deref(),
+        )
+    }
+
+    fn with_time_range(&self, start: i64, end: i64) -> Self {
+        let mut req = self.clone();
+        req.timestamp_gt = None;
+        req.timestamp_gte = Some(start.to_string());
+        req.timestamp_lt = Some(end.to_string());
+        req.timestamp_lte = None;
+        req.order = Some("asc".into());
+        req.sort = Some("timestamp".into());
+        req
+    }
+}
+
 /// Request for last trade.
 ///
 /// Returns the most recent trade for a ticker symbol.
@@ -241,16 +273,38 @@ impl RestRequest for GetLastTradeRequest {
     fn path(&self) -> Cow<'static, str> {
         format!("/v2/last/trade/{}", self.ticker).into()
     }
+
+    fn endpoint(&self) -> &'static str {
+        "/v2/last/trade/{ticker}"
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::price::px;
+
+    #[test]
+    fn test_get_trades_request_shard_range() {
+        let req = GetTradesRequest::new("AAPL")
+            .timestamp_gt("1000")
+            .timestamp_lte("2000");
+        assert_eq!(req.time_range(), Some((1001, 2001)));
+        assert_eq!(GetTradesRequest::new("AAPL").time_range(), None);
+
+        let shard = req.with_time_range(1001, 1500);
+        assert_eq!(shard.timestamp_gte.as_deref(), Some("1001"));
+        assert_eq!(shard.timestamp_lt.as_deref(), Some("1500"));
+        assert!(shard.timestamp_gt.is_none() && shard.timestamp_lte.is_none());
+        assert_eq!(shard.order.as_deref(), Some("asc"));
+        assert_eq!(shard.sort.as_deref(), Some("timestamp"));
+    }
 
     #[test]
     fn test_get_trades_request_path() {
         let req = GetTradesRequest::new("AAPL");
         assert_eq!(req.path(), "/v3/trades/AAPL");
+        assert_eq!(req.endpoint(), "/v3/trades/{ticker}");
     }
 
     #[test]
@@ -281,7 +335,7 @@ mod tests {
             id: Some("123".into()),
             conditions: vec![],
             exchange: Some(4),
-            price: 150.50,
+            price: px(150.50),
             sip_timestamp: Some(1703001234567),
             participant_timestamp: None,
             trf_timestamp: None,
@@ -291,7 +345,7 @@ mod tests {
             reporting_facility: None,
         };
 
-        assert_eq!(trade.value(), 15050.0);
+        assert_eq!(trade.value(), px(15050.0));
     }
 
     #[test]
@@ -316,6 +370,6 @@ mod tests {
         let response: TradesResponse = serde_json::from_str(json).unwrap();
         assert_eq!(response.status, Some("OK".to_string()));
         assert_eq!(response.results.len(), 1);
-        assert_eq!(response.results[0].price, 150.25);
+        assert_eq!(response.results[0].price, px(150.25));
     }
 }
@@ -21,11 +21,17 @@
 //!
 //! # Pagination
 //!
-//! The REST client supports three pagination modes:
+//! The REST client supports these pagination modes:
 //!
 //! - [`PaginationMode::Auto`](crate::config::PaginationMode::Auto): Automatically fetch all pages
 //! - [`PaginationMode::None`](crate::config::PaginationMode::None): Only fetch the first page
 //! - [`PaginationMode::MaxItems(n)`](crate::config::PaginationMode::MaxItems): Stop after n items
+//! - [`PaginationMode::MaxPages(n)`](crate::config::PaginationMode::MaxPages): Stop after n pages
+//! - [`PaginationMode::Deadline(d)`](crate::config::PaginationMode::Deadline): Stop requesting pages after `d`
+//!
+//! [`RestClient::stream`] yields individual items, while
+//! [`RestClient::stream_pages`] yields each [`Page`] with its full response
+//! and HTTP metadata.
 //!
 //! # Asset Classes
 //!
@@ -52,16 +58,22 @@
 
 pub mod asset_class;
 mod client;
+mod concurrency;
 pub mod endpoints;
 pub mod filters;
 pub mod models;
 mod pagination;
+mod rate_limit;
 pub mod request;
+mod shard;
 
 pub use client::RestClient;
 pub use endpoints::*;
-pub use pagination::PageStream;
-pub use request::{PaginatableRequest, QueryBuilder, RestRequest};
+pub use pagination::{Page, PageCheckpoint, PageMeta, PageStream, ResponseStream};
+pub use request::{
+    PaginatableRequest, QueryBuilder, RequestPriority, RestRequest, ShardableRequest,
+};
+pub use shard::{ShardConfig, ShardedStream};
 
 // Re-export commonly used asset class types
 pub use asset_class::{AssetClass, Crypto, Forex, Futures, Indices, Options, Stocks};
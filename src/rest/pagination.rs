@@ -1,22 +1,52 @@
 //! Pagination support for REST API endpoints.
 //!
 //! This module provides the [`PageStream`] type that automatically
-//! handles pagination by following `next_url` links in API responses.
+//! handles pagination by following `next_url` links in API responses, and
+//! [`ResponseStream`], which yields whole pages with their HTTP metadata.
 
-use crate::config::PaginationMode;
+use crate::auth;
+use crate::config::{PaginationMode, PrefetchConfig};
 use crate::error::MassiveError;
 use crate::rest::client::RestClient;
-use crate::rest::request::PaginatableRequest;
+use crate::rest::request::{PaginatableRequest, RequestPriority};
 use futures::Stream;
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
 
 /// Type alias for the boxed future used in pagination.
 type PageFuture<T> = Pin<Box<dyn Future<Output = Result<T, MassiveError>> + Send + 'static>>;
 
+/// One page of a paginated response.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    /// The parsed response body, with fields such as `status`, `count`, and
+    /// `next_url`
+    pub response: T,
+    /// HTTP metadata of the request that fetched the page
+    pub meta: PageMeta,
+}
+
+/// HTTP metadata of a fetched page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageMeta {
+    /// Position of the page in the stream, starting at 0
+    pub index: u64,
+    /// URL the page was fetched from, without the API key
+    pub url: String,
+    /// HTTP status code
+    pub status: u16,
+    /// Request ID from the `X-Request-Id` header (if available)
+    pub request_id: Option<String>,
+    /// Time taken to fetch and parse the page, including retries
+    pub elapsed: Duration,
+}
+
 /// Stream adapter for paginated requests that handles `next_url` chaining.
 ///
 /// # How next_url Works
@@ -35,7 +65,12 @@ type PageFuture<T> = Pin<Box<dyn Future<Output = Result<T, MassiveError>> + Send
 /// - A cursor/pagination token
 /// - The API key (as query param)
 ///
-/// This stream fetches each page via `next_url` until exhausted.
+/// This stream fetches each page via `next_url` until exhausted. Its
+/// position can be saved with [`checkpoint`](Self::checkpoint) and picked up
+/// later with [`RestClient::resume_stream`]. The API key
+/// is removed from `next_url` as soon as a page arrives, so it never sits
+/// in the stream's state; each page request is authenticated according to
+/// the client's [`AuthMode`](crate::auth::AuthMode).
 ///
 /// # Example
 ///
@@ -67,21 +102,60 @@ where
     R: PaginatableRequest,
     R::Response: DeserializeOwned + Send + 'static,
 {
-    client: RestClient,
-    /// Initial request (only used for first page)
-    initial_request: Option<R>,
-    /// URL for next page (from previous response's next_url field)
-    next_url: Option<String>,
-    /// Pagination mode controlling when to stop
-    mode: PaginationMode,
+    pager: Pager<R, Vec<R::Item>>,
     /// Count of items yielded so far
     items_yielded: u64,
+    /// URL of the current page, `None` for the first page
+    page_url: Option<String>,
+    /// Items yielded from the current page
+    page_offset: usize,
+    /// Items of the next page to skip when resuming mid-page
+    skip: usize,
     /// Buffer of items from current page
     buffer: VecDeque<R::Item>,
-    /// Current async operation in progress
-    in_flight: Option<PageFuture<R::Response>>,
-    /// Whether we've completed pagination
-    done: bool,
+}
+
+/// Saved position of a [`PageStream`].
+///
+/// Serialize a checkpoint to persist progress through a long download, and
+/// pass it to [`RestClient::resume_stream`] with the same request to
+/// continue after the last item that was yielded. Checkpoints never contain
+/// the API key.
+///
+/// # Example
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use massive_rs::rest::{PageCheckpoint, RestClient};
+/// use massive_rs::rest::endpoints::GetTradesRequest;
+///
+/// # async fn example(client: RestClient, saved: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+/// let request = GetTradesRequest::new("AAPL").timestamp_gte("2024-01-02");
+/// let mut stream = match saved {
+///     Some(json) => client.resume_stream(request, serde_json::from_str(&json)?),
+///     None => client.stream(request),
+/// };
+///
+/// while let Some(trade) = stream.next().await {
+///     let trade = trade?;
+///     // ... process the trade, then save progress
+///     let json = serde_json::to_string(&stream.checkpoint())?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PageCheckpoint {
+    /// URL of the page to fetch on resume, without the API key, or `None` to
+    /// start from the original request.
+    pub page_url: Option<String>,
+    /// Number of items of that page already yielded.
+    pub page_offset: usize,
+    /// Total number of items yielded, counted toward
+    /// [`PaginationMode::MaxItems`].
+    pub items_yielded: u64,
+    /// Whether every page had been consumed.
+    pub finished: bool,
 }
 
 impl<R> PageStream<R>
@@ -90,31 +164,92 @@ where
     R::Response: DeserializeOwned + Send + 'static,
 {
     /// Create a new page stream.
+    ///
+    /// Pages are prefetched according to the client's
+    /// [`RestConfig::prefetch`](crate::config::RestConfig::prefetch).
     pub fn new(client: RestClient, request: R, mode: PaginationMode) -> Self {
         Self {
-            client,
-            initial_request: Some(request),
-            next_url: None,
-            mode,
+            pager: Pager::new(client, request, mode, |page| {
+                let items = R::extract_items(page.response);
+                let count = items.len();
+                (items, count)
+            }),
             items_yielded: 0,
+            page_url: None,
+            page_offset: 0,
+            skip: 0,
             buffer: VecDeque::new(),
-            in_flight: None,
-            done: false,
         }
     }
 
-    /// Check if we should continue fetching based on pagination mode.
-    fn should_continue(&self) -> bool {
-        match self.mode {
-            PaginationMode::Auto => true,
-            PaginationMode::None => self.items_yielded == 0, // Only first page
-            PaginationMode::MaxItems(max) => self.items_yielded < max,
+    /// Create a page stream that continues from a checkpoint.
+    ///
+    /// `request` must be the request the checkpoint was taken from; it is
+    /// only sent if the checkpoint is still on the first page.
+    pub fn resume(
+        client: RestClient,
+        request: R,
+        mode: PaginationMode,
+        checkpoint: PageCheckpoint,
+    ) -> Self {
+        let mut stream = Self::new(client, request, mode);
+        stream.items_yielded = checkpoint.items_yielded;
+        stream.skip = checkpoint.page_offset;
+        // Items before the resumed page count toward `MaxItems`
+        stream.pager.items_fetched = checkpoint
+            .items_yielded
+            .saturating_sub(checkpoint.page_offset as u64);
+        if checkpoint.finished {
+            stream.pager.done = true;
+            stream.pager.exhausted = true;
+        } else if let Some(url) = checkpoint.page_url {
+            stream.pager.initial_request = None;
+            stream.pager.next_url = Some(without_api_key(&url));
         }
+        stream
     }
 
-    /// Check if we've hit the item limit.
-    fn at_item_limit(&self) -> bool {
-        matches!(self.mode, PaginationMode::MaxItems(max) if self.items_yielded >= max)
+    /// Set page prefetching for this stream, overriding the client's
+    /// configuration.
+    pub fn with_prefetch(mut self, prefetch: Option<PrefetchConfig>) -> Self {
+        self.pager.prefetch = prefetch;
+        self
+    }
+
+    /// Position of the stream after the last item it yielded.
+    ///
+    /// Resuming from the checkpoint refetches the current page and skips the
+    /// items already yielded from it. After an error, or when the
+    /// [`PaginationMode`] stopped the stream before the last page, the
+    /// checkpoint points at the page that would have been fetched next, so
+    /// resuming continues from there. Prefetched pages that have not been
+    /// reached are fetched again on resume.
+    pub fn checkpoint(&self) -> PageCheckpoint {
+        let pager = &self.pager;
+        let (page_url, page_offset) = if !self.buffer.is_empty() {
+            // Part way through a page
+            (self.page_url.clone(), self.page_offset)
+        } else if let Some(next) = pager.ready.front() {
+            // Next page already fetched
+            (next.url.clone(), self.skip)
+        } else if pager.in_flight.is_some() || pager.failed {
+            (pager.page_url.clone(), self.skip)
+        } else if pager.next_url.is_some() {
+            // Between pages
+            (pager.next_url.clone(), self.skip)
+        } else if pager.initial_request.is_some() {
+            // Not started
+            (None, self.skip)
+        } else {
+            // Last page consumed
+            (self.page_url.clone(), self.page_offset)
+        };
+        PageCheckpoint {
+            page_url,
+            page_offset,
+            items_yielded: self.items_yielded,
+            finished: pager.exhausted && pager.ready.is_empty() && self.buffer.is_empty(),
+        }
     }
 }
 
@@ -130,77 +265,367 @@ where
         let this = self.get_mut();
 
         loop {
-            // 1. Yield buffered items first (fast path)
+            // 1. Yield buffered items first (fast path), keeping any
+            // prefetch going
             if let Some(item) = this.buffer.pop_front() {
                 this.items_yielded += 1;
+                this.page_offset += 1;
+                this.pager.drive(cx, this.buffer.len());
+                return Poll::Ready(Some(Ok(item)));
+            }
 
-                // Stop yielding if we hit MaxItems limit
-                if this.at_item_limit() {
-                    this.done = true;
-                }
+            // 2. Poll the page in flight, or request one
+            this.pager.drive(cx, 0);
 
-                return Poll::Ready(Some(Ok(item)));
+            // 3. Move to the next fetched page, in order
+            match this.pager.pop() {
+                Some(Fetched { result: Err(e), .. }) => return Poll::Ready(Some(Err(e))),
+                Some(Fetched {
+                    url,
+                    result: Ok(items),
+                    ..
+                }) => {
+                    // Skip the items yielded before a resume
+                    this.buffer.extend(items.into_iter().skip(this.skip));
+                    this.page_url = url;
+                    this.page_offset = this.skip;
+                    this.skip = 0;
+                }
+                None if this.pager.in_flight.is_some() => return Poll::Pending,
+                None => return Poll::Ready(None),
             }
+        }
+    }
+}
+
+/// Stream of whole pages of a paginated request.
+///
+/// Each item is a [`Page`]: the complete response, with fields such as
+/// `status`, `count`, and `request_id`, plus the [`PageMeta`] of the HTTP
+/// request. Pages are fetched one at a time by following `next_url`, and the
+/// [`PaginationMode`] decides when to stop. With
+/// [`PaginationMode::MaxItems`], the stream ends after the page on which the
+/// limit is reached; pages are never truncated.
+///
+/// # Example
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use massive_rs::rest::RestClient;
+/// use massive_rs::rest::endpoints::GetTradesRequest;
+///
+/// # async fn example(client: RestClient) -> Result<(), massive_rs::MassiveError> {
+/// let mut pages = client.stream_pages(GetTradesRequest::new("AAPL"));
+///
+/// while let Some(page) = pages.next().await {
+///     let page = page?;
+///     println!(
+///         "page {} ({:?}): {} trades",
+///         page.meta.index,
+///         page.meta.request_id,
+///         page.response.results.len()
+///     );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ResponseStream<R>
+where
+    R: PaginatableRequest,
+    R::Response: DeserializeOwned + Send + 'static,
+{
+    pager: Pager<R, Page<R::Response>>,
+}
+
+impl<R> ResponseStream<R>
+where
+    R: PaginatableRequest + Send + 'static,
+    R::Response: DeserializeOwned + Send + 'static,
+{
+    /// Create a new response stream.
+    ///
+    /// Pages are prefetched according to the client's
+    /// [`RestConfig::prefetch`](crate::config::RestConfig::prefetch).
+    pub fn new(client: RestClient, request: R, mode: PaginationMode) -> Self {
+        Self {
+            pager: Pager::new(client, request, mode, |page| {
+                let count = R::item_count(&page.response);
+                (page, count)
+            }),
+        }
+    }
+
+    /// Set page prefetching for this stream, overriding the client's
+    /// configuration.
+    pub fn with_prefetch(mut self, prefetch: Option<PrefetchConfig>) -> Self {
+        self.pager.prefetch = prefetch;
+        self
+    }
+}
+
+impl<R> Stream for ResponseStream<R>
+where
+    R: PaginatableRequest + Unpin + Send + 'static,
+    R::Response: DeserializeOwned + Send + 'static,
+{
+    type Item = Result<Page<R::Response>, MassiveError>;
 
-            // 2. Check if we're done
-            if this.done {
-                return Poll::Ready(None);
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        this.pager.drive(cx, 0);
+        match this.pager.pop() {
+            Some(fetched) => {
+                // Start the following page while the consumer holds this one
+                this.pager.drive(cx, fetched.items);
+                Poll::Ready(Some(fetched.result))
             }
+            None if this.pager.in_flight.is_some() => Poll::Pending,
+            None => Poll::Ready(None),
+        }
+    }
+}
 
-            // 3. Check if there's an in-flight request
-            if let Some(ref mut fut) = this.in_flight {
-                match fut.as_mut().poll(cx) {
-                    Poll::Pending => return Poll::Pending,
-                    Poll::Ready(Err(e)) => {
-                        this.done = true;
-                        this.in_flight = None;
-                        return Poll::Ready(Some(Err(e)));
-                    }
-                    Poll::Ready(Ok(response)) => {
-                        // Extract next_url BEFORE consuming response
-                        this.next_url = R::extract_next_url(&response).map(String::from);
+/// A fetched page waiting to be delivered.
+struct Fetched<T> {
+    /// URL the page was requested from, `None` for the first page
+    url: Option<String>,
+    /// The page, or the error that stopped fetching
+    result: Result<T, MassiveError>,
+    /// Number of items in the page
+    items: usize,
+}
 
-                        // Extract items into buffer
-                        let items = R::extract_items(response);
-                        this.buffer.extend(items);
+/// Fetches the pages of a request by following `next_url`, one request at
+/// a time, and queues them in order.
+///
+/// `T` is what a stream keeps of each page, produced by `convert` along
+/// with the page's item count.
+struct Pager<R, T>
+where
+    R: PaginatableRequest,
+    R::Response: DeserializeOwned + Send + 'static,
+{
+    client: RestClient,
+    /// Route template of the request, for tagging later pages
+    endpoint: &'static str,
+    /// Initial request (only used for first page)
+    initial_request: Option<R>,
+    /// URL for next page (from previous response's next_url field), without
+    /// its API key
+    next_url: Option<String>,
+    /// URL of the page in flight or last requested, `None` for the first page
+    page_url: Option<String>,
+    /// Pagination mode controlling when to stop
+    mode: PaginationMode,
+    /// How far to fetch ahead of the consumer
+    prefetch: Option<PrefetchConfig>,
+    /// When the stream was created, for `PaginationMode::Deadline`
+    started: Instant,
+    /// Count of pages fetched so far
+    pages_fetched: u64,
+    /// Count of items in the pages fetched so far, for `MaxItems`
+    items_fetched: u64,
+    /// Current async operation in progress
+    in_flight: Option<PageFuture<Page<R::Response>>>,
+    /// Fetched pages not yet taken by the stream
+    ready: VecDeque<Fetched<T>>,
+    /// Items in `ready`
+    ready_items: usize,
+    /// Turns a fetched page into what the stream keeps
+    convert: fn(Page<R::Response>) -> (T, usize),
+    /// Whether we've stopped fetching
+    done: bool,
+    /// Whether the last page has been fetched
+    exhausted: bool,
+    /// Whether fetching stopped on an error
+    failed: bool,
+}
 
-                        this.in_flight = None;
+// Nothing in the pager is pinned; the request future is boxed
+impl<R, T> Unpin for Pager<R, T>
+where
+    R: PaginatableRequest,
+    R::Response: DeserializeOwned + Send + 'static,
+{
+}
 
-                        // If no items and no next_url, we're done
-                        if this.buffer.is_empty() && this.next_url.is_none() {
-                            this.done = true;
-                        }
+impl<R, T> Pager<R, T>
+where
+    R: PaginatableRequest + Send + 'static,
+    R::Response: DeserializeOwned + Send + 'static,
+{
+    fn new(
+        client: RestClient,
+        request: R,
+        mode: PaginationMode,
+        convert: fn(Page<R::Response>) -> (T, usize),
+    ) -> Self {
+        let prefetch = client.config().prefetch;
+        Self {
+            client,
+            endpoint: request.endpoint(),
+            initial_request: Some(request),
+            next_url: None,
+            page_url: None,
+            mode,
+            prefetch,
+            started: Instant::now(),
+            pages_fetched: 0,
+            items_fetched: 0,
+            in_flight: None,
+            ready: VecDeque::new(),
+            ready_items: 0,
+            convert,
+            done: false,
+            exhausted: false,
+            failed: false,
+        }
+    }
 
-                        // Continue loop to yield from buffer
-                        continue;
+    /// Check if we should fetch another page based on pagination mode.
+    fn should_continue(&self) -> bool {
+        match self.mode {
+            PaginationMode::Auto => true,
+            PaginationMode::None => self.pages_fetched == 0, // Only first page
+            PaginationMode::MaxItems(max) => self.items_fetched < max,
+            PaginationMode::MaxPages(max) => self.pages_fetched < max,
+            PaginationMode::Deadline(limit) => self.started.elapsed() < limit,
+        }
+    }
+
+    /// Whether another page should be requested now, given the number of
+    /// items the consumer still holds.
+    fn wants_page(&self, held: usize) -> bool {
+        if held == 0 && self.ready.is_empty() {
+            // The consumer is waiting for a page
+            return true;
+        }
+        self.prefetch.is_some_and(|prefetch| {
+            self.ready.len() < prefetch.pages
+                && held + self.ready_items < prefetch.max_buffered_items
+        })
+    }
+
+    /// Poll the page in flight and start further requests while
+    /// [`wants_page`](Self::wants_page) allows.
+    fn drive(&mut self, cx: &mut Context<'_>, held: usize) {
+        loop {
+            if let Some(fut) = self.in_flight.as_mut() {
+                match fut.as_mut().poll(cx) {
+                    Poll::Ready(result) => {
+                        self.in_flight = None;
+                        self.finish(result);
                     }
+                    Poll::Pending => return,
                 }
             }
-
-            // 4. No in-flight request, decide what to fetch next
-            if !this.should_continue() {
-                this.done = true;
-                continue;
+            if !self.wants_page(held) || !self.start_fetch() {
+                return;
             }
+        }
+    }
+
+    /// Take the next fetched page.
+    fn pop(&mut self) -> Option<Fetched<T>> {
+        let fetched = self.ready.pop_front()?;
+        self.ready_items -= fetched.items;
+        Some(fetched)
+    }
 
-            // 5. Fetch next page via next_url, or initial request
-            if let Some(url) = this.next_url.take() {
-                // Subsequent pages: fetch via next_url directly
-                // The next_url is a complete URL from Massive API
-                let client = this.client.clone();
-                this.in_flight = Some(Box::pin(async move {
-                    client.fetch_url::<R::Response>(&url).await
-                }));
-            } else if let Some(req) = this.initial_request.take() {
-                // First page: execute the initial request
-                let client = this.client.clone();
-                this.in_flight = Some(Box::pin(async move { client.execute(req).await }));
-            } else {
-                // No next_url and no initial request = done
-                this.done = true;
-                continue;
+    /// Queue the result of the request in flight.
+    fn finish(&mut self, result: Result<Page<R::Response>, MassiveError>) {
+        let url = self.page_url.clone();
+        let fetched = match result {
+            Ok(mut page) => {
+                page.meta.index = self.pages_fetched;
+                self.pages_fetched += 1;
+                self.next_url = R::extract_next_url(&page.response).map(without_api_key);
+                if self.next_url.is_none() {
+                    self.exhausted = true;
+                    self.done = true;
+                }
+                let (value, items) = (self.convert)(page);
+                self.items_fetched += items as u64;
+                Fetched {
+                    url,
+                    result: Ok(value),
+                    items,
+                }
+            }
+            Err(e) => {
+                self.done = true;
+                self.failed = true;
+                Fetched {
+                    url,
+                    result: Err(e),
+                    items: 0,
+                }
             }
+        };
+        self.ready_items += fetched.items;
+        self.ready.push_back(fetched);
+    }
+
+    /// Start the request for the next page, via `next_url` or the initial
+    /// request. Returns `false` if there is nothing more to fetch.
+    fn start_fetch(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        if !self.should_continue() {
+            self.done = true;
+            return false;
         }
+
+        let client = self.client.clone();
+        let endpoint = self.endpoint;
+        let fut: PageFuture<Page<R::Response>> = if let Some(url) = self.next_url.take() {
+            // Subsequent pages: fetch via next_url directly
+            // The next_url is a complete URL from Massive API
+            self.page_url = Some(url.clone());
+            Box::pin(async move {
+                client
+                    .fetch_url::<R::Response>(&url, endpoint, RequestPriority::Low)
+                    .await
+            })
+        } else if let Some(req) = self.initial_request.take() {
+            // First page: execute the initial request
+            self.page_url = None;
+            Box::pin(async move { client.execute_page(req, RequestPriority::Low).await })
+        } else {
+            // No next_url and no initial request = done
+            self.done = true;
+            return false;
+        };
+
+        // Prefetched pages run on their own task so they progress while the
+        // consumer is busy
+        self.in_flight = Some(match self.prefetch {
+            Some(_) => Box::pin(Spawned(tokio::spawn(fut))),
+            None => fut,
+        });
+        true
+    }
+}
+
+/// A page request running on its own task, aborted when dropped.
+struct Spawned<T>(JoinHandle<Result<T, MassiveError>>);
+
+impl<T> Future for Spawned<T> {
+    type Output = Result<T, MassiveError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx).map(|joined| match joined {
+            Ok(result) => result,
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(_) => Err(MassiveError::Closed),
+        })
+    }
+}
+
+impl<T> Drop for Spawned<T> {
+    fn drop(&mut self) {
+        self.0.abort();
     }
 }
 
@@ -222,6 +647,17 @@ where
     }
 }
 
+/// `next_url` with any API key removed.
+fn without_api_key(next_url: &str) -> String {
+    match url::Url::parse(next_url) {
+        Ok(mut url) => {
+            auth::strip_api_key(&mut url);
+            url.into()
+        }
+        Err(_) => next_url.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -243,4 +679,13 @@ mod tests {
         let mode = PaginationMode::MaxItems(100);
         assert!(matches!(mode, PaginationMode::MaxItems(100)));
     }
+
+    #[test]
+    fn test_next_url_without_api_key() {
+        assert_eq!(
+            without_api_key("https://api.massive.com/v3/trades/AAPL?cursor=abc&apiKey=secret"),
+            "https://api.massive.com/v3/trades/AAPL?cursor=abc"
+        );
+        assert_eq!(without_api_key("not a url"), "not a url");
+    }
 }
@@ -0,0 +1,287 @@
+//! Client-side rate limiting.
+//!
+//! This module provides the token bucket that [`RestClient`](super::RestClient)
+//! requests wait on when [`RestConfig::rate_limit`](crate::config::RestConfig::rate_limit)
+//! is set. Waiting requests are served by [`RequestPriority`].
+
+use crate::config::RateLimitConfig;
+use crate::rest::request::RequestPriority;
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+use tokio::sync::Notify;
+use tokio::time::{Duration, Instant};
+
+/// Token bucket shared by all clones of a client.
+///
+/// Waiters are served highest [`RequestPriority`] first, and in FIFO order
+/// within a priority. Only the waiter at the head of the queue sleeps for
+/// the next token; the others wait to be notified that the head changed.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    /// Maximum tokens in the bucket
+    capacity: f64,
+    /// Time to refill one token
+    refill: Duration,
+    state: Mutex<State>,
+    /// Signalled when a token is taken or a waiter leaves
+    changed: Notify,
+}
+
+#[derive(Debug)]
+struct State {
+    tokens: f64,
+    updated: Instant,
+    /// Waiting tickets per priority, highest priority first
+    queues: [VecDeque<u64>; RequestPriority::COUNT],
+    next_ticket: u64,
+}
+
+impl State {
+    /// Ticket of the waiter served next.
+    fn head(&self) -> Option<u64> {
+        self.queues.iter().find_map(|queue| queue.front().copied())
+    }
+}
+
+/// A place in the queue, given up on drop.
+struct Ticket<'a> {
+    limiter: &'a RateLimiter,
+    id: u64,
+    rank: usize,
+}
+
+impl Drop for Ticket<'_> {
+    fn drop(&mut self) {
+        let mut state = self.limiter.lock();
+        let queue = &mut state.queues[self.rank];
+        if let Some(pos) = queue.iter().position(|&id| id == self.id) {
+            queue.remove(pos);
+        }
+        self.limiter.changed.notify_waiters();
+    }
+}
+
+impl RateLimiter {
+    /// Create a limiter with a full bucket.
+    pub fn new(config: RateLimitConfig) -> Self {
+        let capacity = config.burst.max(1) as f64;
+        let refill = config.interval / config.requests.max(1);
+        Self {
+            capacity,
+            refill,
+            state: Mutex::new(State {
+                tokens: capacity,
+                updated: Instant::now(),
+                queues: Default::default(),
+                next_ticket: 0,
+            }),
+            changed: Notify::new(),
+        }
+    }
+
+    /// Wait for a token.
+    ///
+    /// Cancel-safe: dropping the future gives up the place in the queue.
+    /// Returns how long the caller waited.
+    pub async fn acquire(&self, priority: RequestPriority) -> Duration {
+        let start = Instant::now();
+        let ticket = {
+            let mut state = self.lock();
+            let id = state.next_ticket;
+            state.next_ticket += 1;
+            state.queues[priority.rank()].push_back(id);
+            Ticket {
+                limiter: self,
+                id,
+                rank: priority.rank(),
+            }
+        };
+
+        loop {
+            let changed = self.changed.notified();
+            tokio::pin!(changed);
+            // Register before checking, so a change can't be missed
+            changed.as_mut().enable();
+
+            let wait = {
+                let mut state = self.lock();
+                if state.head() != Some(ticket.id) {
+                    None
+                } else {
+                    self.refill(&mut state);
+                    if state.tokens >= 1.0 {
+                        state.tokens -= 1.0;
+                        // Dropping the ticket leaves the queue and wakes
+                        // the next waiter
+                        drop(state);
+                        drop(ticket);
+                        return start.elapsed();
+                    }
+                    Some(self.refill.mul_f64(1.0 - state.tokens))
+                }
+            };
+
+            match wait {
+                // A higher priority waiter may arrive meanwhile; the head is
+                // checked again after the sleep
+                Some(wait) => tokio::time::sleep(wait).await,
+                None => changed.await,
+            }
+        }
+    }
+
+    fn refill(&self, state: &mut State) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.updated);
+        state.updated = now;
+        if self.refill.is_zero() {
+            state.tokens = self.capacity;
+        } else {
+            let added = elapsed.as_secs_f64() / self.refill.as_secs_f64();
+            // Guard against float rounding leaving us just short
+            let tokens = state.tokens + added;
+            state.tokens = if (1.0 - tokens).abs() < 1e-9 {
+                1.0
+            } else {
+                tokens.min(self.capacity)
+            };
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_burst_then_refill() {
+        let limiter = RateLimiter::new(RateLimitConfig::per_second(10).with_burst(2));
+
+        assert_eq!(
+            limiter.acquire(RequestPriority::Normal).await,
+            Duration::ZERO
+        );
+        assert_eq!(
+            limiter.acquire(RequestPriority::Normal).await,
+            Duration::ZERO
+        );
+        assert_eq!(
+            limiter.acquire(RequestPriority::Normal).await,
+            Duration::from_millis(100)
+        );
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        // Refill is capped at the burst size
+        assert_eq!(
+            limiter.acquire(RequestPriority::Normal).await,
+            Duration::ZERO
+        );
+        assert_eq!(
+            limiter.acquire(RequestPriority::Normal).await,
+            Duration::ZERO
+        );
+        assert_eq!(
+            limiter.acquire(RequestPriority::Normal).await,
+            Duration::from_millis(100)
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_plan_preset_spacing() {
+        let limiter = RateLimiter::new(RateLimitConfig::for_plan(crate::config::PlanTier::Basic));
+        let start = Instant::now();
+        for _ in 0..5 {
+            limiter.acquire(RequestPriority::Normal).await;
+        }
+        assert_eq!(start.elapsed(), Duration::from_secs(48));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_concurrent_waiters_are_spaced() {
+        let limiter = Arc::new(RateLimiter::new(
+            RateLimitConfig::per_second(4).with_burst(1),
+        ));
+        let start = Instant::now();
+
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let limiter = limiter.clone();
+                tokio::spawn(async move {
+                    limiter.acquire(RequestPriority::Normal).await;
+                    start.elapsed()
+                })
+            })
+            .collect();
+
+        let mut times = Vec::new();
+        for task in tasks {
+            times.push(task.await.unwrap());
+        }
+        times.sort();
+        assert_eq!(
+            times,
+            vec![
+                Duration::ZERO,
+                Duration::from_millis(250),
+                Duration::from_millis(500),
+                Duration::from_millis(750),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_high_priority_overtakes() {
+        let limiter = Arc::new(RateLimiter::new(
+            RateLimitConfig::per_second(10).with_burst(1),
+        ));
+        limiter.acquire(RequestPriority::Low).await;
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let mut tasks = Vec::new();
+        for (name, priority) in [
+            ("low-1", RequestPriority::Low),
+            ("low-2", RequestPriority::Low),
+            ("normal", RequestPriority::Normal),
+            ("high", RequestPriority::High),
+        ] {
+            let limiter = limiter.clone();
+            let order = order.clone();
+            tasks.push(tokio::spawn(async move {
+                limiter.acquire(priority).await;
+                order.lock().unwrap().push(name);
+            }));
+            // Let each task join the queue in turn
+            tokio::task::yield_now().await;
+        }
+
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec!["high", "normal", "low-1", "low-2"]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cancelled_waiter_leaves_queue() {
+        use futures::FutureExt;
+
+        let limiter = RateLimiter::new(RateLimitConfig::per_second(10).with_burst(1));
+        limiter.acquire(RequestPriority::Normal).await;
+
+        let mut cancelled = Box::pin(limiter.acquire(RequestPriority::High));
+        assert!(cancelled.as_mut().now_or_never().is_none());
+        drop(cancelled);
+
+        assert_eq!(
+            limiter.acquire(RequestPriority::Low).await,
+            Duration::from_millis(100)
+        );
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! This module defines the [`RestRequest`] trait that all API requests
 //! must implement, along with the [`PaginatableRequest`] trait for
-//! endpoints that support pagination.
+//! endpoints that support pagination and the [`ShardableRequest`] trait for
+//! those that can be split by time range.
 
 use bytes::Bytes;
 use reqwest::Method;
@@ -58,6 +59,14 @@ pub trait RestRequest: Send + Sync {
     /// The path should start with `/` and not include the base URL.
     fn path(&self) -> Cow<'static, str>;
 
+    /// Route template of the endpoint, such as `/v3/trades/{ticker}`.
+    ///
+    /// Used to tag metrics instead of [`path`](Self::path), so it must not
+    /// interpolate request values. Defaults to `"other"`.
+    fn endpoint(&self) -> &'static str {
+        "other"
+    }
+
     /// Query parameters for the request.
     ///
     /// Returns a vector of key-value pairs that will be appended to the URL.
@@ -125,6 +134,10 @@ pub trait RestRequest: Send + Sync {
 ///         response.results
 ///     }
 ///
+///     fn item_count(response: &Self::Response) -> usize {
+///         response.results.len()
+///     }
+///
 ///     fn extract_next_url(response: &Self::Response) -> Option<&str> {
 ///         response.next_url.as_deref()
 ///     }
@@ -137,12 +150,91 @@ pub trait PaginatableRequest: RestRequest + Clone {
     /// Extract items from the response.
     fn extract_items(response: Self::Response) -> Vec<Self::Item>;
 
+    /// Number of items in the response, as returned by
+    /// [`extract_items`](Self::extract_items).
+    fn item_count(response: &Self::Response) -> usize;
+
     /// Extract the next page URL from the response.
     ///
     /// Returns `None` when there are no more pages.
     fn extract_next_url(response: &Self::Response) -> Option<&str>;
 }
 
+/// Trait for paginated requests over a time window that can be split into
+/// sub-ranges and fetched in parallel.
+///
+/// Used by [`RestClient::stream_sharded`](crate::rest::RestClient::stream_sharded).
+/// All times are Unix nanoseconds.
+pub trait ShardableRequest: PaginatableRequest {
+    /// The request's time window as `[start, end)`, or `None` if it is not
+    /// bounded at both ends.
+    fn time_range(&self) -> Option<(i64, i64)>;
+
+    /// A copy of the request covering `[start, end)`, sorted by ascending
+    /// timestamp.
+    fn with_time_range(&self, start: i64, end: i64) -> Self;
+
+    /// Granularity that sub-range boundaries are aligned to, or `None` if
+    /// the window cannot be split.
+    fn range_step(&self) -> Option<i64> {
+        Some(1)
+    }
+
+    /// Longest window a single request can cover without the server
+    /// truncating its results, if limited.
+    fn max_range(&self) -> Option<i64> {
+        None
+    }
+}
+
+/// Scheduling priority for a request.
+///
+/// When [`RestConfig::rate_limit`](crate::config::RestConfig::rate_limit) or
+/// [`RestConfig::max_in_flight`](crate::config::RestConfig::max_in_flight)
+/// makes requests wait, they are started highest priority first.
+/// [`RestClient::execute`](crate::rest::RestClient::execute) uses
+/// [`Normal`](Self::Normal) and pagination uses [`Low`](Self::Low), so
+/// interactive calls overtake bulk backfills.
+///
+/// Priorities order by urgency: `High > Normal > Low`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RequestPriority {
+    /// Latency-sensitive requests (quotes, snapshots)
+    High,
+    /// Regular requests
+    #[default]
+    Normal,
+    /// Bulk requests and pagination
+    Low,
+}
+
+impl RequestPriority {
+    /// Number of priority levels.
+    pub(crate) const COUNT: usize = 3;
+
+    /// Queue index, highest priority first.
+    pub(crate) fn rank(self) -> usize {
+        match self {
+            RequestPriority::High => 0,
+            RequestPriority::Normal => 1,
+            RequestPriority::Low => 2,
+        }
+    }
+}
+
+impl PartialOrd for RequestPriority {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RequestPriority {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Lower rank is more urgent
+        other.rank().cmp(&self.rank())
+    }
+}
+
 /// Helper trait for building query parameters.
 pub trait QueryBuilder {
     /// Add a required parameter.
@@ -205,6 +297,7 @@ mod tests {
             limit: None,
         };
         assert_eq!(req.path(), "/test/42");
+        assert_eq!(req.endpoint(), "other");
     }
 
     #[test]
@@ -236,4 +329,14 @@ mod tests {
         assert_eq!(params[0], (Cow::Borrowed("key"), "value".to_string()));
         assert_eq!(params[1], (Cow::Borrowed("optional"), "42".to_string()));
     }
+
+    #[test]
+    fn test_priority_order() {
+        assert!(RequestPriority::High > RequestPriority::Normal);
+        assert!(RequestPriority::Normal > RequestPriority::Low);
+        assert_eq!(
+            RequestPriority::Low.max(RequestPriority::High),
+            RequestPriority::High
+        );
+    }
 }
@@ -0,0 +1,438 @@
+//! Parallel time-range sharding for large historical downloads.
+//!
+//! [`ShardedStream`] splits the time window of a [`ShardableRequest`] into
+//! sub-ranges, paginates them concurrently, and yields their items as one
+//! stream in timestamp order.
+
+use crate::config::PaginationMode;
+use crate::error::MassiveError;
+use crate::rest::client::RestClient;
+use crate::rest::request::ShardableRequest;
+use chrono::{DateTime, NaiveDate};
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Nanoseconds in a millisecond.
+pub(crate) const NANOS_PER_MILLI: i64 = 1_000_000;
+
+/// Nanoseconds in a day.
+pub(crate) const NANOS_PER_DAY: i64 = 86_400_000_000_000;
+
+/// Sharding configuration for [`RestClient::stream_sharded`].
+///
+/// # Example
+///
+/// ```
+/// use massive_rs::rest::ShardConfig;
+///
+/// // Split into 16 ranges, downloading 4 at a time
+/// let config = ShardConfig::new(16).with_concurrency(4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardConfig {
+    /// Number of sub-ranges to split the window into.
+    ///
+    /// More are used if a single request could not cover a sub-range, as
+    /// with the 50,000-bar limit of aggregates.
+    pub shards: usize,
+
+    /// Maximum sub-ranges downloaded at once.
+    pub concurrency: usize,
+
+    /// Items each running sub-range may buffer before its download pauses.
+    pub buffered_items: usize,
+}
+
+impl ShardConfig {
+    /// Split into `shards` sub-ranges, downloading up to 4 at a time with
+    /// up to 10,000 buffered items each.
+    pub fn new(shards: usize) -> Self {
+        Self {
+            shards,
+            concurrency: 4,
+            buffered_items: 10_000,
+        }
+    }
+
+    /// Set the maximum sub-ranges downloaded at once.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Set the items each running sub-range may buffer.
+    pub fn with_buffered_items(mut self, buffered_items: usize) -> Self {
+        self.buffered_items = buffered_items;
+        self
+    }
+}
+
+impl Default for ShardConfig {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+/// Stream of items from a request split into time sub-ranges.
+///
+/// Sub-ranges are paginated concurrently, each on its own task, and their
+/// items are yielded one sub-range after another. Since the sub-ranges are
+/// consecutive and each is sorted by ascending timestamp, the stream is in
+/// timestamp order.
+///
+/// Each running sub-range holds up to
+/// [`buffered_items`](ShardConfig::buffered_items) items waiting for the
+/// consumer, plus the rest of the page it is reading from, and pauses its
+/// download once its buffer is full. Memory is therefore bounded by
+/// [`concurrency`](ShardConfig::concurrency) × (`buffered_items` + one
+/// page). Sub-ranges do not prefetch pages, whatever the client's
+/// [`PrefetchConfig`](crate::config::PrefetchConfig).
+///
+/// Each sub-range is downloaded completely, regardless of the client's
+/// [`PaginationMode`]. The stream ends after the first error, stopping the
+/// remaining downloads.
+///
+/// # Example
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use massive_rs::rest::{RestClient, ShardConfig};
+/// use massive_rs::rest::endpoints::GetTradesRequest;
+///
+/// # async fn example(client: RestClient) -> Result<(), massive_rs::MassiveError> {
+/// let request = GetTradesRequest::new("AAPL")
+///     .timestamp_gte("2024-01-02")
+///     .timestamp_lt("2024-01-03");
+/// let mut trades = client.stream_sharded(request, ShardConfig::new(8))?;
+///
+/// while let Some(trade) = trades.next().await {
+///     let trade = trade?;
+///     println!("{:?} {}", trade.sip_timestamp, trade.price);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ShardedStream<R>
+where
+    R: ShardableRequest,
+{
+    client: RestClient,
+    /// Sub-ranges not yet started, in time order
+    pending: VecDeque<R>,
+    /// Running or finished sub-ranges not yet consumed, in time order
+    active: VecDeque<Shard<R::Item>>,
+    concurrency: usize,
+    buffered_items: usize,
+    /// Number of sub-ranges the window was split into
+    shard_count: usize,
+}
+
+/// A sub-range downloading on its own task.
+struct Shard<T> {
+    items: mpsc::Receiver<Result<T, MassiveError>>,
+    task: JoinHandle<()>,
+}
+
+impl<T> Drop for Shard<T> {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl<R> ShardedStream<R>
+where
+    R: ShardableRequest + Unpin + Send + 'static,
+    R::Response: DeserializeOwned + Send + 'static,
+    R::Item: Unpin,
+{
+    /// Create a sharded stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MassiveError::InvalidArgument`] if the request's time
+    /// window is not bounded at both ends, or if any [`ShardConfig`] value
+    /// is zero.
+    pub fn new(client: RestClient, request: R, config: ShardConfig) -> Result<Self, MassiveError> {
+        if config.shards == 0 || config.concurrency == 0 || config.buffered_items == 0 {
+            return Err(MassiveError::InvalidArgument(
+                "Shard count, concurrency, and buffered items must be at least 1",
+            ));
+        }
+        let (start, end) = request.time_range().ok_or(MassiveError::InvalidArgument(
+            "Sharded requests need a time range bounded at both ends",
+        ))?;
+
+        let pending: VecDeque<R> = match request.range_step() {
+            Some(step) => split_range(start, end, config.shards, step, request.max_range())
+                .into_iter()
+                .map(|(start, end)| request.with_time_range(start, end))
+                .collect(),
+            None => [request].into(),
+        };
+
+        Ok(Self {
+            client,
+            shard_count: pending.len(),
+            pending,
+            active: VecDeque::new(),
+            concurrency: config.concurrency,
+            buffered_items: config.buffered_items,
+        })
+    }
+
+    /// Number of sub-ranges the window was split into.
+    pub fn shard_count(&self) -> usize {
+        self.shard_count
+    }
+
+    /// Start downloading a sub-range.
+    fn spawn(&self, request: R) -> Shard<R::Item> {
+        let (tx, items) = mpsc::channel(self.buffered_items);
+        // The channel already buffers ahead, so pages aren't prefetched too
+        let mut stream = self
+            .client
+            .stream_with_mode(request, PaginationMode::Auto)
+            .with_prefetch(None);
+        let task = tokio::spawn(async move {
+            while let Some(item) = stream.next().await {
+                let failed = item.is_err();
+                if tx.send(item).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+        Shard { items, task }
+    }
+}
+
+impl<R> Stream for ShardedStream<R>
+where
+    R: ShardableRequest + Unpin + Send + 'static,
+    R::Response: DeserializeOwned + Send + 'static,
+    R::Item: Unpin,
+{
+    type Item = Result<R::Item, MassiveError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            // Keep up to `concurrency` sub-ranges going
+            while this.active.len() < this.concurrency {
+                match this.pending.pop_front() {
+                    Some(request) => {
+                        let shard = this.spawn(request);
+                        this.active.push_back(shard);
+                    }
+                    None => break,
+                }
+            }
+
+            let Some(shard) = this.active.front_mut() else {
+                return Poll::Ready(None);
+            };
+            match ready!(shard.items.poll_recv(cx)) {
+                Some(Ok(item)) => return Poll::Ready(Some(Ok(item))),
+                Some(Err(e)) => {
+                    // Stop the remaining downloads
+                    this.pending.clear();
+                    this.active.clear();
+                    return Poll::Ready(Some(Err(e)));
+                }
+                None => {
+                    // The sub-range is done; surface a panic instead of
+                    // silently skipping the rest of its items
+                    if let Err(e) = ready!(Pin::new(&mut shard.task).poll(cx)) {
+                        if e.is_panic() {
+                            std::panic::resume_unwind(e.into_panic());
+                        }
+                    }
+                    this.active.pop_front();
+                }
+            }
+        }
+    }
+}
+
+/// Split `[start, end)` into at least `shards` consecutive ranges of at
+/// most `max_len`, with boundaries at multiples of `step` from `start`.
+fn split_range(
+    start: i64,
+    end: i64,
+    shards: usize,
+    step: i64,
+    max_len: Option<i64>,
+) -> Vec<(i64, i64)> {
+    let step = step.max(1);
+    let len = end.saturating_sub(start);
+    if len <= 0 {
+        return vec![(start, end)];
+    }
+
+    let steps = (len + step - 1) / step;
+    let mut count = (shards as i64).clamp(1, steps);
+    if let Some(max_len) = max_len {
+        let max_steps = (max_len / step).max(1);
+        count = count.max((steps + max_steps - 1) / max_steps);
+    }
+    let per_shard = (steps + count - 1) / count;
+
+    let mut ranges = Vec::with_capacity(count as usize);
+    let mut from = start;
+    while from < end {
+        let to = from.saturating_add(per_shard * step).min(end);
+        ranges.push((from, to));
+        from = to;
+    }
+    ranges
+}
+
+/// Parse a time bound given as a `YYYY-MM-DD` date, an RFC 3339 timestamp,
+/// or an integer in units of `unit` nanoseconds.
+///
+/// Returns the time in Unix nanoseconds and the resolution of the value,
+/// which is added to make an inclusive bound exclusive.
+pub(crate) fn parse_bound(value: &str, unit: i64) -> Option<(i64, i64)> {
+    if let Ok(n) = value.parse::<i64>() {
+        return Some((n.checked_mul(unit)?, unit));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let nanos = date.and_hms_opt(0, 0, 0)?.and_utc().timestamp_nanos_opt()?;
+        return Some((nanos, NANOS_PER_DAY));
+    }
+    let time = DateTime::parse_from_rfc3339(value).ok()?;
+    Some((time.timestamp_nanos_opt()?, 1))
+}
+
+/// Whether `value` is a `YYYY-MM-DD` date.
+pub(crate) fn is_date(value: &str) -> bool {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+}
+
+/// Format the UTC date of a Unix nanosecond time as `YYYY-MM-DD`.
+pub(crate) fn format_date(nanos: i64) -> String {
+    DateTime::from_timestamp_nanos(nanos)
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// Least common multiple of two positive lengths, if it fits.
+pub(crate) fn lcm(a: i64, b: i64) -> Option<i64> {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    (a / x).checked_mul(b)
+}
+
+/// Time window of `timestamp.gte/gt/lt/lte` filters, whose integer values
+/// are Unix nanoseconds.
+pub(crate) fn timestamp_range(
+    gte: Option<&str>,
+    gt: Option<&str>,
+    lt: Option<&str>,
+    lte: Option<&str>,
+) -> Option<(i64, i64)> {
+    let start = match (gte, gt) {
+        (Some(gte), _) => parse_bound(gte, 1)?.0,
+        (None, Some(gt)) => parse_bound(gt, 1)?.0.checked_add(1)?,
+        (None, None) => return None,
+    };
+    let end = match (lt, lte) {
+        (Some(lt), _) => parse_bound(lt, 1)?.0,
+        (None, Some(lte)) => {
+            let (time, resolution) = parse_bound(lte, 1)?;
+            time.checked_add(resolution)?
+        }
+        (None, None) => return None,
+    };
+    Some((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_range_even() {
+        assert_eq!(
+            split_range(0, 100, 4, 1, None),
+            vec![(0, 25), (25, 50), (50, 75), (75, 100)]
+        );
+        // Uneven lengths leave a shorter last range
+        assert_eq!(
+            split_range(0, 10, 3, 1, None),
+            vec![(0, 4), (4, 8), (8, 10)]
+        );
+    }
+
+    #[test]
+    fn test_split_range_aligns_to_step() {
+        assert_eq!(
+            split_range(5, 105, 3, 10, None),
+            vec![(5, 45), (45, 85), (85, 105)]
+        );
+        // Never more ranges than steps
+        assert_eq!(split_range(0, 20, 8, 10, None), vec![(0, 10), (10, 20)]);
+    }
+
+    #[test]
+    fn test_split_range_respects_max_len() {
+        let ranges = split_range(0, 1000, 1, 10, Some(300));
+        assert_eq!(ranges, vec![(0, 250), (250, 500), (500, 750), (750, 1000)]);
+        assert!(ranges.iter().all(|(from, to)| to - from <= 300));
+    }
+
+    #[test]
+    fn test_split_range_empty() {
+        assert_eq!(split_range(10, 10, 4, 1, None), vec![(10, 10)]);
+    }
+
+    #[test]
+    fn test_parse_bound() {
+        assert_eq!(
+            parse_bound("1704067200000", NANOS_PER_MILLI),
+            Some((1_704_067_200_000_000_000, NANOS_PER_MILLI))
+        );
+        assert_eq!(
+            parse_bound("2024-01-01", 1),
+            Some((1_704_067_200_000_000_000, NANOS_PER_DAY))
+        );
+        assert_eq!(
+            parse_bound("2024-01-01T00:00:01Z", 1),
+            Some((1_704_067_201_000_000_000, 1))
+        );
+        assert_eq!(parse_bound("yesterday", 1), None);
+    }
+
+    #[test]
+    fn test_date_helpers() {
+        assert!(is_date("2024-01-02"));
+        assert!(!is_date("1704153600000"));
+        assert_eq!(format_date(1_704_153_600_000_000_000), "2024-01-02");
+        assert_eq!(format_date(1_704_153_599_999_999_999), "2024-01-01");
+        assert_eq!(lcm(300, NANOS_PER_DAY), Some(NANOS_PER_DAY));
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(i64::MAX, 2), None);
+    }
+
+    #[test]
+    fn test_timestamp_range() {
+        let day = 1_704_067_200_000_000_000;
+        assert_eq!(
+            timestamp_range(Some("2024-01-01"), None, None, Some("2024-01-01")),
+            Some((day, day + NANOS_PER_DAY))
+        );
+        assert_eq!(
+            timestamp_range(None, Some("100"), Some("200"), None),
+            Some((101, 200))
+        );
+        assert_eq!(timestamp_range(Some("2024-01-01"), None, None, None), None);
+    }
+}
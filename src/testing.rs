@@ -0,0 +1,372 @@
+//! Test utilities.
+//!
+//! [`MockWsServer`] runs an in-process stand-in for the Massive WebSocket
+//! API on a local port. It answers authentication and subscription frames
+//! with the same `status` events as the real server, and lets tests push
+//! scripted events, drop connections, reject authentication, and stall, so
+//! reconnect and backpressure handling can be tested without credentials.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use massive_rs::testing::MockWsServer;
+//! use massive_rs::ws::{Subscription, WsClient};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let server = MockWsServer::start().await?;
+//! let client = WsClient::new(server.config("test-api-key"))?;
+//! let (handle, mut stream) = client.connect().await?;
+//!
+//! handle.subscribe(&[Subscription::trade("AAPL")]).await?;
+//! server.push_events(&[serde_json::json!({
+//!     "ev": "T", "sym": "AAPL", "x": 4, "i": "1", "z": 3,
+//!     "p": 150.25, "s": 100, "t": 1703001234567_i64, "q": 1
+//! })]);
+//!
+//! let batch = stream.next().await.unwrap()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::config::WsConfig;
+use crate::ws::{Subscription, WsAuthMessage, WsSubscribeMessage};
+use futures::{SinkExt, StreamExt};
+use std::collections::{BTreeSet, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Notify};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+use tracing::debug;
+use url::Url;
+
+/// In-process mock of the Massive WebSocket API.
+///
+/// Every connection is greeted with a `connected` status. Any API key is
+/// accepted unless [`reject_auth`](Self::reject_auth) is set. Subscribe and
+/// unsubscribe frames are confirmed topic by topic, except for topics
+/// marked with [`deny_topic`](Self::deny_topic), which get an error status.
+///
+/// Pushed frames go to every open connection. Dropping the server closes
+/// all connections.
+pub struct MockWsServer {
+    url: Url,
+    shared: Arc<Shared>,
+    accept_task: JoinHandle<()>,
+}
+
+/// State shared between the server handle and connection tasks.
+struct Shared {
+    state: Mutex<State>,
+    /// Woken whenever connections, authentications, or subscriptions change
+    changed: Notify,
+}
+
+#[derive(Default)]
+struct State {
+    connections: usize,
+    authentications: usize,
+    /// Topics subscribed on the most recent connection
+    subscriptions: BTreeSet<String>,
+    denied: HashSet<String>,
+    reject_auth: bool,
+    stalled: bool,
+    clients: Vec<mpsc::UnboundedSender<Command>>,
+}
+
+/// Instruction for a connection task.
+#[derive(Clone)]
+enum Command {
+    Send(String),
+    Stall(bool),
+    Close,
+}
+
+impl MockWsServer {
+    /// Start a server on a free local port.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no local port can be bound.
+    pub async fn start() -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = Url::parse(&format!("ws://{}", listener.local_addr()?))
+            .expect("socket address is a valid URL");
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State::default()),
+            changed: Notify::new(),
+        });
+
+        let accept_shared = shared.clone();
+        let accept_task = tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                tokio::spawn(serve(tcp, accept_shared.clone()));
+            }
+        });
+
+        Ok(Self {
+            url,
+            shared,
+            accept_task,
+        })
+    }
+
+    /// Base URL of the server (`ws://127.0.0.1:<port>`).
+    pub fn url(&self) -> Url {
+        self.url.clone()
+    }
+
+    /// WebSocket configuration pointing at this server.
+    pub fn config(&self, api_key: &str) -> WsConfig {
+        WsConfig::new(api_key).with_base_url(self.url())
+    }
+
+    /// Send a raw text frame to every open connection.
+    pub fn push(&self, frame: impl Into<String>) {
+        self.shared.broadcast(Command::Send(frame.into()));
+    }
+
+    /// Send events as one JSON array frame to every open connection.
+    ///
+    /// Each event is a JSON object in the wire format, such as
+    /// `{"ev":"T","sym":"AAPL",...}`.
+    pub fn push_events(&self, events: &[serde_json::Value]) {
+        self.push(serde_json::Value::from(events.to_vec()).to_string());
+    }
+
+    /// Send `count` copies of a frame at once.
+    ///
+    /// The frames arrive faster than a slow consumer drains its stream,
+    /// exercising [`OverflowPolicy`](crate::config::OverflowPolicy).
+    pub fn flood(&self, frame: &str, count: usize) {
+        for _ in 0..count {
+            self.push(frame);
+        }
+    }
+
+    /// Close every open connection with a close frame.
+    ///
+    /// Clients see [`DisconnectReason::ServerClose`](crate::ws::DisconnectReason::ServerClose)
+    /// and reconnect according to their
+    /// [`ReconnectConfig`](crate::config::ReconnectConfig).
+    pub fn disconnect(&self) {
+        self.shared.broadcast(Command::Close);
+    }
+
+    /// Answer authentication with `auth_failed` instead of `auth_success`.
+    pub fn reject_auth(&self, reject: bool) {
+        self.shared.lock().reject_auth = reject;
+    }
+
+    /// Reject subscriptions to `topic` with an error status.
+    pub fn deny_topic(&self, topic: &Subscription) {
+        self.shared.lock().denied.insert(topic.as_str().to_string());
+    }
+
+    /// Stop reading from clients, or resume.
+    ///
+    /// While stalled, pings go unanswered and frames sent by clients are
+    /// not acknowledged, as with a hung server. Pushed frames are still
+    /// delivered.
+    pub fn stall(&self, stalled: bool) {
+        self.shared.lock().stalled = stalled;
+        self.shared.broadcast(Command::Stall(stalled));
+    }
+
+    /// Number of connections accepted so far.
+    pub fn connection_count(&self) -> usize {
+        self.shared.lock().connections
+    }
+
+    /// Topics subscribed on the most recent connection, sorted.
+    pub fn subscriptions(&self) -> Vec<Subscription> {
+        self.shared
+            .lock()
+            .subscriptions
+            .iter()
+            .map(|t| Subscription::raw(t.as_str()))
+            .collect()
+    }
+
+    /// Wait until `count` connections have authenticated successfully.
+    pub async fn wait_for_authentications(&self, count: usize) {
+        self.wait_until(|state| state.authentications >= count)
+            .await;
+    }
+
+    /// Wait until `topic` is subscribed on the most recent connection.
+    pub async fn wait_for_subscription(&self, topic: &Subscription) {
+        self.wait_until(|state| state.subscriptions.contains(topic.as_str()))
+            .await;
+    }
+
+    async fn wait_until(&self, done: impl Fn(&State) -> bool) {
+        loop {
+            let changed = self.shared.changed.notified();
+            if done(&self.shared.lock()) {
+                return;
+            }
+            changed.await;
+        }
+    }
+}
+
+impl Drop for MockWsServer {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+        self.shared.broadcast(Command::Close);
+    }
+}
+
+impl std::fmt::Debug for MockWsServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockWsServer")
+            .field("url", &self.url.as_str())
+            .field("connections", &self.connection_count())
+            .finish()
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Send a command to every open connection, forgetting closed ones.
+    fn broadcast(&self, command: Command) {
+        self.lock()
+            .clients
+            .retain(|client| client.send(command.clone()).is_ok());
+    }
+
+    /// Update the state and wake waiters.
+    fn update(&self, f: impl FnOnce(&mut State)) {
+        f(&mut self.lock());
+        self.changed.notify_waiters();
+    }
+}
+
+/// Serve one client connection until either side closes it.
+async fn serve(tcp: TcpStream, shared: Arc<Shared>) {
+    let Ok(mut ws) = tokio_tungstenite::accept_async(tcp).await else {
+        return;
+    };
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut stalled = false;
+    shared.update(|state| {
+        state.connections += 1;
+        state.subscriptions.clear();
+        state.clients.push(tx);
+        stalled = state.stalled;
+    });
+
+    let mut authenticated = false;
+    if send_status(&mut ws, "connected", "Connected Successfully")
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            command = rx.recv() => match command {
+                Some(Command::Send(frame)) => {
+                    if ws.send(Message::Text(frame)).await.is_err() {
+                        return;
+                    }
+                }
+                Some(Command::Stall(value)) => stalled = value,
+                Some(Command::Close) | None => {
+                    let _ = ws.close(None).await;
+                    return;
+                }
+            },
+            msg = ws.next(), if !stalled => match msg {
+                Some(Ok(Message::Text(text))) => {
+                    if handle_frame(&mut ws, &shared, &mut authenticated, &text).await.is_err() {
+                        return;
+                    }
+                }
+                // Pings are answered by tungstenite while reading
+                Some(Ok(_)) => {}
+                Some(Err(_)) | None => return,
+            },
+        }
+    }
+}
+
+/// Answer one client frame.
+async fn handle_frame(
+    ws: &mut WebSocketStream<TcpStream>,
+    shared: &Shared,
+    authenticated: &mut bool,
+    text: &str,
+) -> Result<(), tokio_tungstenite::tungstenite::Error> {
+    let action = serde_json::from_str::<serde_json::Value>(text)
+        .ok()
+        .and_then(|v| v["action"].as_str().map(str::to_string));
+
+    match action.as_deref() {
+        Some("auth") => {
+            let Ok(_auth) = serde_json::from_str::<WsAuthMessage>(text) else {
+                return Ok(());
+            };
+            if shared.lock().reject_auth {
+                send_status(ws, "auth_failed", "authentication failed").await
+            } else {
+                *authenticated = true;
+                shared.update(|state| state.authentications += 1);
+                send_status(ws, "auth_success", "authenticated").await
+            }
+        }
+        Some(action @ ("subscribe" | "unsubscribe")) => {
+            let Ok(message) = serde_json::from_str::<WsSubscribeMessage>(text) else {
+                return Ok(());
+            };
+            if !*authenticated {
+                return send_status(ws, "error", "not authorized").await;
+            }
+            let mut statuses = Vec::new();
+            shared.update(|state| {
+                for topic in message.params.split(',').map(str::trim) {
+                    if action == "subscribe" && state.denied.contains(topic) {
+                        statuses.push(status_json(
+                            "error",
+                            &format!("not authorized to access {topic}"),
+                        ));
+                        continue;
+                    }
+                    if action == "subscribe" {
+                        state.subscriptions.insert(topic.to_string());
+                    } else {
+                        state.subscriptions.remove(topic);
+                    }
+                    statuses.push(status_json("success", &format!("{action}d to: {topic}")));
+                }
+            });
+            ws.send(Message::Text(serde_json::Value::from(statuses).to_string()))
+                .await
+        }
+        _ => {
+            debug!(frame = %text, "Mock server ignored frame");
+            Ok(())
+        }
+    }
+}
+
+fn status_json(status: &str, message: &str) -> serde_json::Value {
+    serde_json::json!({"ev": "status", "status": status, "message": message})
+}
+
+async fn send_status(
+    ws: &mut WebSocketStream<TcpStream>,
+    status: &str,
+    message: &str,
+) -> Result<(), tokio_tungstenite::tungstenite::Error> {
+    let frame = serde_json::Value::from(vec![status_json(status, message)]).to_string();
+    ws.send(Message::Text(frame)).await
+}
@@ -0,0 +1,460 @@
+//! Subscription acknowledgements.
+//!
+//! The server answers every subscribe and unsubscribe frame with `status`
+//! events, one per topic (`"subscribed to: T.AAPL"`) or an error. The IO
+//! task tracks requests in an [`AckTracker`] until every topic has been
+//! answered or the request times out, then replies with a
+//! [`SubscriptionReport`].
+
+use crate::error::{MassiveError, WsError};
+use crate::ws::models::events::WsStatusEvent;
+use crate::ws::protocol::Subscription;
+use tokio::sync::oneshot;
+use tokio::time::Instant;
+
+/// Server response for one topic of a subscribe or unsubscribe request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TopicStatus {
+    /// The server confirmed the topic.
+    Accepted,
+    /// The server returned an error, such as a missing entitlement.
+    Rejected(String),
+    /// No confirmation arrived within
+    /// [`WsConfig::subscribe_timeout`](crate::config::WsConfig::subscribe_timeout).
+    TimedOut,
+}
+
+/// Per-topic outcome of [`WsHandle::subscribe`](crate::ws::WsHandle::subscribe)
+/// or [`WsHandle::unsubscribe`](crate::ws::WsHandle::unsubscribe).
+///
+/// # Example
+///
+/// ```no_run
+/// # use massive_rs::ws::{Subscription, WsHandle};
+/// # async fn example(handle: &WsHandle) -> Result<(), massive_rs::MassiveError> {
+/// let report = handle.subscribe(&[Subscription::trade("AAPL")]).await?;
+/// for (topic, status) in report.failed() {
+///     eprintln!("{topic}: {status:?}");
+/// }
+///
+/// // Or treat any failed topic as an error
+/// handle
+///     .subscribe(&[Subscription::quote("AAPL")])
+///     .await?
+///     .into_result()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionReport {
+    /// Status of each requested topic, in request order
+    pub results: Vec<(Subscription, TopicStatus)>,
+}
+
+impl SubscriptionReport {
+    /// Check if every topic was accepted.
+    pub fn is_success(&self) -> bool {
+        self.results
+            .iter()
+            .all(|(_, status)| *status == TopicStatus::Accepted)
+    }
+
+    /// Topics the server confirmed.
+    pub fn accepted(&self) -> impl Iterator<Item = &Subscription> {
+        self.results
+            .iter()
+            .filter(|(_, status)| *status == TopicStatus::Accepted)
+            .map(|(topic, _)| topic)
+    }
+
+    /// Topics that were rejected or timed out.
+    pub fn failed(&self) -> impl Iterator<Item = (&Subscription, &TopicStatus)> {
+        self.results
+            .iter()
+            .filter(|(_, status)| *status != TopicStatus::Accepted)
+            .map(|(topic, status)| (topic, status))
+    }
+
+    /// Convert into an error if any topic was not accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WsError::SubscriptionFailed`] listing each failed topic.
+    pub fn into_result(self) -> Result<Self, MassiveError> {
+        if self.is_success() {
+            return Ok(self);
+        }
+        let failures: Vec<String> = self
+            .failed()
+            .map(|(topic, status)| match status {
+                TopicStatus::Rejected(msg) => format!("{topic}: {msg}"),
+                _ => format!("{topic}: timed out"),
+            })
+            .collect();
+        Err(MassiveError::Ws(Box::new(WsError::SubscriptionFailed(
+            failures.join("; "),
+        ))))
+    }
+}
+
+/// Kind of request awaiting acknowledgement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AckAction {
+    Subscribe,
+    Unsubscribe,
+}
+
+/// Reply channel for a subscribe or unsubscribe request.
+pub(crate) type AckReply = oneshot::Sender<Result<SubscriptionReport, MassiveError>>;
+
+/// A request whose topics have all been answered or timed out.
+pub(crate) struct CompletedAck {
+    pub action: AckAction,
+    pub report: SubscriptionReport,
+    /// Caller waiting for the report, or `None` for subscriptions restored
+    /// after a reconnect
+    pub reply: Option<AckReply>,
+}
+
+/// Request waiting for per-topic confirmations.
+struct PendingAck {
+    action: AckAction,
+    results: Vec<(Subscription, Option<TopicStatus>)>,
+    deadline: Instant,
+    reply: Option<AckReply>,
+}
+
+impl PendingAck {
+    fn is_complete(&self) -> bool {
+        self.results.iter().all(|(_, status)| status.is_some())
+    }
+
+    fn complete(self) -> CompletedAck {
+        let results = self
+            .results
+            .into_iter()
+            .map(|(topic, status)| (topic, status.unwrap_or(TopicStatus::TimedOut)))
+            .collect();
+        CompletedAck {
+            action: self.action,
+            report: SubscriptionReport { results },
+            reply: self.reply,
+        }
+    }
+}
+
+/// Matches server `status` events to outstanding requests.
+#[derive(Default)]
+pub(crate) struct AckTracker {
+    pending: Vec<PendingAck>,
+}
+
+impl AckTracker {
+    /// Track a request whose frames have been sent.
+    ///
+    /// Duplicate topics are collapsed. Returns the request immediately if
+    /// there is nothing to wait for.
+    pub fn register(
+        &mut self,
+        action: AckAction,
+        topics: Vec<Subscription>,
+        deadline: Instant,
+        reply: Option<AckReply>,
+    ) -> Option<CompletedAck> {
+        let mut results: Vec<(Subscription, Option<TopicStatus>)> =
+            Vec::with_capacity(topics.len());
+        for topic in topics {
+            if !results.iter().any(|(t, _)| *t == topic) {
+                results.push((topic, None));
+            }
+        }
+        let pending = PendingAck {
+            action,
+            results,
+            deadline,
+            reply,
+        };
+        if pending.is_complete() {
+            return Some(pending.complete());
+        }
+        self.pending.push(pending);
+        None
+    }
+
+    /// Whether any request is waiting for confirmations.
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Earliest deadline among waiting requests.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.pending.iter().map(|p| p.deadline).min()
+    }
+
+    /// Apply a server status event and return requests it completed.
+    ///
+    /// Confirmations resolve the oldest request still waiting on the topic.
+    /// Errors naming waiting topics reject those topics; errors that name
+    /// none reject the unanswered topics of the oldest subscribe request.
+    pub fn on_status(&mut self, status: &WsStatusEvent) -> Vec<CompletedAck> {
+        if self.pending.is_empty() {
+            return Vec::new();
+        }
+        let message = status.message.as_deref().unwrap_or_default();
+
+        if status.status == "success" {
+            let (action, topic) = if let Some(t) = message.strip_prefix("subscribed to: ") {
+                (AckAction::Subscribe, t)
+            } else if let Some(t) = message
+                .strip_prefix("unsubscribed to: ")
+                .or_else(|| message.strip_prefix("unsubscribed from: "))
+            {
+                (AckAction::Unsubscribe, t)
+            } else {
+                return Vec::new();
+            };
+            let slot = self
+                .pending
+                .iter_mut()
+                .filter(|p| p.action == action)
+                .flat_map(|p| p.results.iter_mut())
+                .find(|(t, status)| status.is_none() && t.as_str() == topic.trim());
+            if let Some((_, status)) = slot {
+                *status = Some(TopicStatus::Accepted);
+            }
+        } else if status.status == "error" {
+            let rejected = TopicStatus::Rejected(message.to_string());
+            let named = self
+                .pending
+                .iter()
+                .flat_map(|p| p.results.iter())
+                .any(|(t, status)| status.is_none() && names_topic(message, t));
+            if named {
+                for (topic, status) in self.pending.iter_mut().flat_map(|p| p.results.iter_mut()) {
+                    if status.is_none() && names_topic(message, topic) {
+                        *status = Some(rejected.clone());
+                    }
+                }
+            } else if let Some(pending) = self
+                .pending
+                .iter_mut()
+                .find(|p| p.action == AckAction::Subscribe)
+            {
+                for (_, status) in pending.results.iter_mut().filter(|(_, s)| s.is_none()) {
+                    *status = Some(rejected.clone());
+                }
+            }
+        } else {
+            return Vec::new();
+        }
+
+        self.take_where(|p| p.is_complete())
+    }
+
+    /// Time out requests whose deadline has passed.
+    pub fn expire(&mut self, now: Instant) -> Vec<CompletedAck> {
+        self.take_where(|p| p.deadline <= now)
+    }
+
+    /// Fail every waiting request because the connection dropped.
+    ///
+    /// Callers get [`WsError::Disconnected`] and may retry once the client
+    /// has reconnected.
+    pub fn disconnect(&mut self) {
+        for pending in self.pending.drain(..) {
+            if let Some(reply) = pending.reply {
+                let _ = reply.send(Err(MassiveError::Ws(Box::new(WsError::Disconnected))));
+            }
+        }
+    }
+
+    fn take_where(&mut self, f: impl Fn(&PendingAck) -> bool) -> Vec<CompletedAck> {
+        let mut done = Vec::new();
+        let mut idx = 0;
+        while idx < self.pending.len() {
+            if f(&self.pending[idx]) {
+                done.push(self.pending.remove(idx).complete());
+            } else {
+                idx += 1;
+            }
+        }
+        done
+    }
+}
+
+/// Whether an error message names `topic` as a whole word, such as
+/// `T.AAPL` in `"not authorized to access T.AAPL,Q.AAPL"` but not `T.A`.
+fn names_topic(message: &str, topic: &Subscription) -> bool {
+    let topic = topic.as_str();
+    message
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '"' | '\'' | '(' | ')'))
+        // Topics never end in punctuation, so this only drops sentence ends
+        .map(|word| word.trim_end_matches(['.', ':']))
+        .any(|word| word == topic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::Duration;
+
+    fn status(status: &str, message: &str) -> WsStatusEvent {
+        WsStatusEvent {
+            status: status.into(),
+            message: Some(message.into()),
+        }
+    }
+
+    fn register(
+        tracker: &mut AckTracker,
+        action: AckAction,
+        topics: &[Subscription],
+    ) -> oneshot::Receiver<Result<SubscriptionReport, MassiveError>> {
+        let (tx, rx) = oneshot::channel();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        assert!(tracker
+            .register(action, topics.to_vec(), deadline, Some(tx))
+            .is_none());
+        rx
+    }
+
+    #[test]
+    fn test_ack_per_topic() {
+        let mut tracker = AckTracker::default();
+        let topics = [Subscription::trade("AAPL"), Subscription::trade("MSFT")];
+        let _rx = register(&mut tracker, AckAction::Subscribe, &topics);
+
+        assert!(tracker
+            .on_status(&status("success", "subscribed to: T.AAPL"))
+            .is_empty());
+        let done = tracker.on_status(&status("success", "subscribed to: T.MSFT"));
+        assert_eq!(done.len(), 1);
+        assert!(done[0].report.is_success());
+        assert_eq!(done[0].report.accepted().count(), 2);
+        assert!(!tracker.has_pending());
+    }
+
+    #[test]
+    fn test_error_naming_topic_rejects_only_that_topic() {
+        let mut tracker = AckTracker::default();
+        let topics = [Subscription::trade("AAPL"), Subscription::quote("AAPL")];
+        let _rx = register(&mut tracker, AckAction::Subscribe, &topics);
+
+        tracker.on_status(&status("error", "not authorized to access Q.AAPL"));
+        let done = tracker.on_status(&status("success", "subscribed to: T.AAPL"));
+        let report = &done[0].report;
+        assert_eq!(report.results[0].1, TopicStatus::Accepted);
+        assert!(matches!(report.results[1].1, TopicStatus::Rejected(_)));
+
+        let err = report.clone().into_result().unwrap_err();
+        assert!(err.to_string().contains("Q.AAPL: not authorized"));
+    }
+
+    #[test]
+    fn test_error_naming_topic_matches_whole_topic() {
+        let mut tracker = AckTracker::default();
+        let topics = [Subscription::trade("A"), Subscription::trade("AAPL")];
+        let _rx = register(&mut tracker, AckAction::Subscribe, &topics);
+
+        tracker.on_status(&status("error", "not authorized to access T.AAPL."));
+        let done = tracker.on_status(&status("success", "subscribed to: T.A"));
+        let report = &done[0].report;
+        assert_eq!(report.results[0].1, TopicStatus::Accepted);
+        assert!(matches!(report.results[1].1, TopicStatus::Rejected(_)));
+
+        assert!(names_topic("denied: T.A,Q.A", &Subscription::quote("A")));
+        assert!(!names_topic("denied: T.AAPL", &Subscription::trade("A")));
+    }
+
+    #[test]
+    fn test_error_without_topic_rejects_oldest_subscribe() {
+        let mut tracker = AckTracker::default();
+        let _unsubscribe = register(
+            &mut tracker,
+            AckAction::Unsubscribe,
+            &[Subscription::trade("SPY")],
+        );
+        let _first = register(
+            &mut tracker,
+            AckAction::Subscribe,
+            &[Subscription::trade("AAPL"), Subscription::trade("MSFT")],
+        );
+        let _second = register(
+            &mut tracker,
+            AckAction::Subscribe,
+            &[Subscription::trade("NVDA")],
+        );
+
+        let done = tracker.on_status(&status("error", "plan does not include this data"));
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].action, AckAction::Subscribe);
+        assert_eq!(done[0].report.failed().count(), 2);
+        assert_eq!(tracker.pending.len(), 2);
+    }
+
+    #[test]
+    fn test_unsubscribe_ack_does_not_resolve_subscribe() {
+        let mut tracker = AckTracker::default();
+        let _rx = register(
+            &mut tracker,
+            AckAction::Subscribe,
+            &[Subscription::trade("AAPL")],
+        );
+
+        assert!(tracker
+            .on_status(&status("success", "unsubscribed to: T.AAPL"))
+            .is_empty());
+        assert!(tracker.has_pending());
+    }
+
+    #[test]
+    fn test_expire_times_out_unanswered_topics() {
+        let mut tracker = AckTracker::default();
+        let _rx = register(
+            &mut tracker,
+            AckAction::Unsubscribe,
+            &[Subscription::trade("AAPL"), Subscription::trade("AAPL")],
+        );
+
+        assert!(tracker.expire(Instant::now()).is_empty());
+        let deadline = tracker.next_deadline().unwrap();
+        let done = tracker.expire(deadline);
+        assert_eq!(
+            done[0].report.results,
+            vec![(Subscription::trade("AAPL"), TopicStatus::TimedOut)]
+        );
+    }
+
+    #[test]
+    fn test_empty_request_completes_immediately() {
+        let mut tracker = AckTracker::default();
+        let (tx, _rx) = oneshot::channel();
+        let done = tracker.register(AckAction::Subscribe, Vec::new(), Instant::now(), Some(tx));
+        assert!(done.unwrap().report.is_success());
+        assert!(!tracker.has_pending());
+    }
+
+    #[test]
+    fn test_disconnect_fails_waiting_requests() {
+        let mut tracker = AckTracker::default();
+        let mut rx = register(
+            &mut tracker,
+            AckAction::Subscribe,
+            &[Subscription::trade("AAPL")],
+        );
+        let deadline = Instant::now() + Duration::from_secs(10);
+        tracker.register(
+            AckAction::Subscribe,
+            vec![Subscription::trade("MSFT")],
+            deadline,
+            None,
+        );
+
+        tracker.disconnect();
+        assert!(!tracker.has_pending());
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(Err(MassiveError::Ws(e))) if matches!(*e, WsError::Disconnected)
+        ));
+    }
+}
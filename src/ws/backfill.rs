@@ -0,0 +1,496 @@
+//! Gap recovery after reconnects.
+//!
+//! While [`WsConfig::backfill`](crate::config::WsConfig::backfill) is set,
+//! the IO task records the last trade and aggregate seen for each symbol in
+//! a [`GapTracker`]. After a reconnect, once the server has confirmed the
+//! restored subscriptions, it plans one [`Gap`] per subscribed symbol and
+//! event type, and [`run`] fetches the missed events over REST and
+//! dispatches them in batches marked as backfilled.
+
+use crate::config::{BackfillConfig, PaginationMode};
+use crate::error::MassiveError;
+use crate::models::{AggregateBar, Price};
+use crate::rest::endpoints::{GetAggsRequest, GetTradesRequest, Sort, Timespan, Trade};
+use crate::util::Symbol;
+use crate::ws::client::WsMessageBatch;
+use crate::ws::dispatch::Dispatcher;
+use crate::ws::lifecycle::{ConnectionEvent, LifecycleHub};
+use crate::ws::models::events::{WsAggregateEvent, WsEvent, WsTradeEvent};
+use crate::ws::protocol::Subscription;
+use dashmap::DashSet;
+use futures::{StreamExt, TryStreamExt};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+/// Gaps fetched concurrently after a reconnect.
+const BACKFILL_CONCURRENCY: usize = 4;
+
+/// Largest page the trades and aggregates endpoints return.
+const MAX_PAGE_SIZE: u64 = 50_000;
+
+const NANOS_PER_MILLI: i64 = 1_000_000;
+
+/// Event types that can be recovered over REST.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Channel {
+    Trade,
+    SecondAgg,
+    MinuteAgg,
+}
+
+impl Channel {
+    /// Wire event type, which is also the subscription prefix.
+    fn prefix(self) -> &'static str {
+        match self {
+            Channel::Trade => "T",
+            Channel::SecondAgg => "A",
+            Channel::MinuteAgg => "AM",
+        }
+    }
+}
+
+/// Last live event seen for a symbol and channel.
+#[derive(Debug, Clone, Copy)]
+struct Cursor {
+    /// Trade timestamp or aggregate window start (Unix milliseconds)
+    last_ms: i64,
+    /// Highest trade sequence number
+    last_seq: Option<u64>,
+}
+
+/// Window of missed events for one symbol and channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Gap {
+    channel: Channel,
+    symbol: Symbol,
+    /// Timestamp of the last delivered event (Unix milliseconds)
+    since_ms: i64,
+    /// End of the window, exclusive (Unix milliseconds)
+    until_ms: i64,
+    /// Sequence number of the last delivered trade
+    last_seq: Option<u64>,
+}
+
+impl Gap {
+    /// Topic this gap belongs to, e.g. `T.AAPL`.
+    fn topic(&self) -> Subscription {
+        Subscription::raw(format!("{}.{}", self.channel.prefix(), self.symbol))
+    }
+
+    /// Whether an event falls inside the gap.
+    ///
+    /// Trades are compared by sequence number when both sides have one, so
+    /// trades sharing the last delivered millisecond are not lost.
+    fn contains(&self, ms: i64, seq: Option<u64>) -> bool {
+        if ms >= self.until_ms {
+            return false;
+        }
+        match (self.last_seq, seq) {
+            (Some(last), Some(seq)) => seq > last,
+            _ => ms > self.since_ms,
+        }
+    }
+}
+
+/// Tracks the last trade and aggregate per symbol across reconnects.
+#[derive(Debug, Default)]
+pub(crate) struct GapTracker {
+    cursors: HashMap<(Channel, Symbol), Cursor>,
+}
+
+impl GapTracker {
+    /// Record the live trades and aggregates in a batch.
+    pub fn observe(&mut self, events: &[WsEvent]) {
+        for event in events {
+            let (channel, symbol, ms, seq) = match event {
+                WsEvent::Trade(e) => (Channel::Trade, &e.sym, e.t, Some(e.q)),
+                WsEvent::SecondAggregate(e) => (Channel::SecondAgg, &e.sym, e.s, None),
+                WsEvent::MinuteAggregate(e) => (Channel::MinuteAgg, &e.sym, e.s, None),
+                _ => continue,
+            };
+            let cursor = self
+                .cursors
+                .entry((channel, symbol.clone()))
+                .or_insert(Cursor {
+                    last_ms: ms,
+                    last_seq: None,
+                });
+            cursor.last_ms = cursor.last_ms.max(ms);
+            if let Some(seq) = seq {
+                cursor.last_seq = Some(cursor.last_seq.map_or(seq, |last| last.max(seq)));
+            }
+        }
+    }
+
+    /// Plan the gaps to recover for subscribed topics, up to `now_ms`.
+    ///
+    /// Gaps reach back at most `max_gap`. Cursors are moved to the end of
+    /// each gap, so a later reconnect does not fetch the same window again.
+    pub fn plan(
+        &mut self,
+        subscriptions: &DashSet<Subscription>,
+        now_ms: i64,
+        max_gap: Duration,
+    ) -> Vec<Gap> {
+        let floor = now_ms.saturating_sub(max_gap.as_millis() as i64);
+        let mut gaps = Vec::new();
+        let last_ms = now_ms - 1;
+        for ((channel, symbol), cursor) in &mut self.cursors {
+            if cursor.last_ms >= last_ms || !is_subscribed(subscriptions, *channel, symbol) {
+                continue;
+            }
+            gaps.push(Gap {
+                channel: *channel,
+                symbol: symbol.clone(),
+                since_ms: cursor.last_ms.max(floor),
+                until_ms: now_ms,
+                last_seq: cursor.last_seq,
+            });
+            cursor.last_ms = last_ms;
+        }
+        gaps.sort_by(|a, b| a.topic().as_str().cmp(b.topic().as_str()));
+        gaps
+    }
+}
+
+/// Check whether a symbol is still subscribed on a channel, directly or
+/// through a wildcard such as `T.*` or `T.O:*`.
+fn is_subscribed(subscriptions: &DashSet<Subscription>, channel: Channel, symbol: &str) -> bool {
+    let prefix = channel.prefix();
+    let covers = |topic: String| subscriptions.contains(&Subscription::raw(topic));
+    covers(format!("{prefix}.{symbol}"))
+        || covers(format!("{prefix}.*"))
+        || symbol
+            .split_once(':')
+            .is_some_and(|(class, _)| covers(format!("{prefix}.{class}:*")))
+}
+
+/// Fetch every gap and dispatch the recovered events.
+///
+/// Each gap is delivered as one batch, in timestamp order, as soon as it
+/// has been fetched. Publishes [`ConnectionEvent::Backfilled`] when done.
+pub(crate) async fn run(
+    config: BackfillConfig,
+    gaps: Vec<Gap>,
+    dispatcher: Arc<Dispatcher>,
+    lifecycle: Arc<LifecycleHub>,
+) {
+    let mut fetches = futures::stream::iter(gaps)
+        .map(|gap| fetch(&config, gap))
+        .buffer_unordered(BACKFILL_CONCURRENCY);
+
+    let mut events = 0;
+    let mut failed = Vec::new();
+    let mut truncated = Vec::new();
+    while let Some((gap, result)) = fetches.next().await {
+        match result {
+            Ok(recovered) => {
+                if recovered.truncated {
+                    warn!(topic = %gap.topic(), max_events = config.max_events, "Backfill truncated");
+                    truncated.push(gap.topic());
+                }
+                if recovered.events.is_empty() {
+                    continue;
+                }
+                events += recovered.events.len();
+                let batch = WsMessageBatch {
+                    events: recovered.events,
+                    received_at: Instant::now(),
+                    latency_hint_ns: None,
+                    backfilled: true,
+                };
+                if dispatcher.dispatch(batch).is_err() {
+                    debug!("Event stream closed, stopping backfill");
+                    return;
+                }
+            }
+            Err(e) => {
+                warn!(topic = %gap.topic(), error = %e, "Failed to backfill gap");
+                failed.push(gap.topic());
+            }
+        }
+    }
+
+    debug!(
+        events,
+        failed = failed.len(),
+        truncated = truncated.len(),
+        "Backfill complete"
+    );
+    lifecycle.publish(ConnectionEvent::Backfilled {
+        events,
+        failed,
+        truncated,
+    });
+}
+
+/// Events recovered for one gap.
+struct Recovered {
+    events: Vec<WsEvent>,
+    /// The gap held more than [`BackfillConfig::max_events`] events
+    truncated: bool,
+}
+
+async fn fetch(config: &BackfillConfig, gap: Gap) -> (Gap, Result<Recovered, MassiveError>) {
+    let result = match gap.channel {
+        Channel::Trade => fetch_trades(config, &gap).await,
+        Channel::SecondAgg | Channel::MinuteAgg => fetch_aggs(config, &gap).await,
+    };
+    (gap, result)
+}
+
+async fn fetch_trades(config: &BackfillConfig, gap: &Gap) -> Result<Recovered, MassiveError> {
+    let request = GetTradesRequest::new(gap.symbol.as_str())
+        .timestamp_gte((gap.since_ms * NANOS_PER_MILLI).to_string())
+        .timestamp_lt((gap.until_ms * NANOS_PER_MILLI).to_string())
+        .order("asc")
+        .sort("timestamp")
+        .limit(page_size(config));
+    let mut trades: Vec<Trade> = config
+        .rest
+        .stream_with_mode(
+            request,
+            PaginationMode::MaxItems(config.max_events.saturating_add(1)),
+        )
+        .try_collect()
+        .await?;
+    let truncated = cap(&mut trades, config.max_events);
+
+    let events = trades
+        .into_iter()
+        .filter_map(|trade| trade_event(&gap.symbol, trade))
+        .filter_map(|(event, seq)| gap.contains(event.t, seq).then_some(WsEvent::Trade(event)))
+        .collect();
+    Ok(Recovered { events, truncated })
+}
+
+async fn fetch_aggs(config: &BackfillConfig, gap: &Gap) -> Result<Recovered, MassiveError> {
+    let (timespan, span_ms) = match gap.channel {
+        Channel::SecondAgg => (Timespan::Second, 1_000),
+        _ => (Timespan::Minute, 60_000),
+    };
+    let request = GetAggsRequest::new(gap.symbol.as_str())
+        .timespan(timespan)
+        .from(gap.since_ms.to_string())
+        .to(gap.until_ms.to_string())
+        .sort(Sort::Asc)
+        .limit(page_size(config));
+    let mut bars: Vec<AggregateBar> = config
+        .rest
+        .stream_with_mode(
+            request,
+            PaginationMode::MaxItems(config.max_events.saturating_add(1)),
+        )
+        .try_collect()
+        .await?;
+    let truncated = cap(&mut bars, config.max_events);
+
+    let events = bars
+        .into_iter()
+        .filter(|bar| gap.contains(bar.timestamp, None))
+        .map(|bar| {
+            let event = agg_event(&gap.symbol, bar, span_ms);
+            match gap.channel {
+                Channel::SecondAgg => WsEvent::SecondAggregate(event),
+                _ => WsEvent::MinuteAggregate(event),
+            }
+        })
+        .collect();
+    Ok(Recovered { events, truncated })
+}
+
+/// Page size for fetching a gap, which reads one item past
+/// [`BackfillConfig::max_events`] to detect truncation.
+fn page_size(config: &BackfillConfig) -> u32 {
+    config.max_events.saturating_add(1).min(MAX_PAGE_SIZE) as u32
+}
+
+/// Keep the first `max` items, returning whether any were dropped.
+fn cap<T>(items: &mut Vec<T>, max: u64) -> bool {
+    let max = usize::try_from(max).unwrap_or(usize::MAX);
+    let truncated = items.len() > max;
+    items.truncate(max);
+    truncated
+}
+
+/// Convert a REST trade into the WebSocket event shape, along with its
+/// sequence number if the API returned one.
+///
+/// Returns `None` for trades without a SIP timestamp.
+fn trade_event(symbol: &Symbol, trade: Trade) -> Option<(WsTradeEvent, Option<u64>)> {
+    let event = WsTradeEvent {
+        sym: symbol.clone(),
+        x: trade.exchange.unwrap_or_default(),
+        i: trade.id.unwrap_or_default(),
+        z: trade.tape.unwrap_or_default(),
+        p: trade.price,
+        s: trade.size,
+        c: trade.conditions,
+        t: trade.sip_timestamp? / NANOS_PER_MILLI,
+        q: trade.sequence_number.unwrap_or_default(),
+        trfi: trade.reporting_facility,
+        trft: trade.trf_timestamp.map(|ns| ns / NANOS_PER_MILLI),
+    };
+    Some((event, trade.sequence_number))
+}
+
+/// Convert a REST bar into the WebSocket event shape.
+///
+/// Day-level fields the REST API does not return (`av`, `op`, and `a`)
+/// are zero.
+fn agg_event(symbol: &Symbol, bar: AggregateBar, span_ms: i64) -> WsAggregateEvent {
+    let volume = bar.volume.unwrap_or_default() as u64;
+    WsAggregateEvent {
+        sym: symbol.clone(),
+        v: volume,
+        av: 0,
+        op: Price::default(),
+        vw: bar.vwap.unwrap_or_default(),
+        o: bar.open,
+        c: bar.close,
+        h: bar.high,
+        l: bar.low,
+        a: Price::default(),
+        z: bar
+            .transactions
+            .filter(|&n| n > 0)
+            .map_or(0, |n| volume / n),
+        s: bar.timestamp,
+        e: bar.timestamp + span_ms,
+        otc: bar.otc,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::price::px;
+
+    fn event(json: &str) -> WsEvent {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn trade(sym: &str, t: i64, q: u64) -> WsEvent {
+        event(&format!(
+            r#"{{"ev":"T","sym":"{sym}","x":4,"i":"1","z":3,"p":150.0,"s":100,"t":{t},"q":{q}}}"#
+        ))
+    }
+
+    fn minute_agg(sym: &str, s: i64) -> WsEvent {
+        event(&format!(
+            r#"{{"ev":"AM","sym":"{sym}","v":100,"av":1000,"op":150.0,"vw":150.5,"o":150.0,"c":151.0,"h":151.5,"l":149.5,"a":150.2,"z":10,"s":{s},"e":{}}}"#,
+            s + 60_000
+        ))
+    }
+
+    fn subscriptions(topics: &[&str]) -> DashSet<Subscription> {
+        topics.iter().map(|t| Subscription::raw(*t)).collect()
+    }
+
+    #[test]
+    fn test_plan_covers_subscribed_topics_only() {
+        let mut tracker = GapTracker::default();
+        tracker.observe(&[
+            trade("AAPL", 1_000, 7),
+            trade("AAPL", 2_000, 9),
+            trade("MSFT", 1_500, 3),
+            minute_agg("AAPL", 60_000),
+        ]);
+
+        let subs = subscriptions(&["T.AAPL", "AM.AAPL"]);
+        let gaps = tracker.plan(&subs, 200_000, Duration::from_secs(300));
+        assert_eq!(
+            gaps,
+            vec![
+                Gap {
+                    channel: Channel::MinuteAgg,
+                    symbol: "AAPL".into(),
+                    since_ms: 60_000,
+                    until_ms: 200_000,
+                    last_seq: None,
+                },
+                Gap {
+                    channel: Channel::Trade,
+                    symbol: "AAPL".into(),
+                    since_ms: 2_000,
+                    until_ms: 200_000,
+                    last_seq: Some(9),
+                },
+            ]
+        );
+
+        // A second reconnect with no live events in between finds nothing new
+        assert!(tracker
+            .plan(&subs, 200_000, Duration::from_secs(300))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_plan_limits_gap_and_honors_wildcards() {
+        let mut tracker = GapTracker::default();
+        tracker.observe(&[trade("O:AAPL251219C00200000", 1_000, 1)]);
+
+        let gaps = tracker.plan(&subscriptions(&["T.O:*"]), 61_000, Duration::from_secs(10));
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].since_ms, 51_000);
+        assert_eq!(gaps[0].topic().as_str(), "T.O:AAPL251219C00200000");
+    }
+
+    #[test]
+    fn test_cap_reports_truncation() {
+        let mut items = vec![1, 2, 3];
+        assert!(!cap(&mut items, 3));
+        assert_eq!(items, vec![1, 2, 3]);
+        assert!(cap(&mut items, 2));
+        assert_eq!(items, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_gap_contains() {
+        let gap = Gap {
+            channel: Channel::Trade,
+            symbol: "AAPL".into(),
+            since_ms: 2_000,
+            until_ms: 5_000,
+            last_seq: Some(9),
+        };
+        // Same millisecond as the last trade, but a later sequence number
+        assert!(gap.contains(2_000, Some(10)));
+        assert!(!gap.contains(3_000, Some(9)));
+        assert!(!gap.contains(5_000, Some(11)));
+        // Without a sequence number, fall back to the timestamp
+        assert!(!gap.contains(2_000, None));
+        assert!(gap.contains(2_001, None));
+    }
+
+    #[test]
+    fn test_rest_conversions() {
+        let trade: Trade = serde_json::from_str(
+            r#"{"conditions":[12],"exchange":4,"id":"52983525","price":171.55,"sip_timestamp":1703001234567891000,"size":100,"sequence_number":1063,"tape":3}"#,
+        )
+        .unwrap();
+        let (event, seq) = trade_event(&"AAPL".into(), trade.clone()).unwrap();
+        assert_eq!(event.t, 1_703_001_234_567);
+        assert_eq!(event.q, 1063);
+        assert_eq!(seq, Some(1063));
+
+        let no_timestamp = Trade {
+            sip_timestamp: None,
+            ..trade
+        };
+        assert!(trade_event(&"AAPL".into(), no_timestamp).is_none());
+
+        let bar: AggregateBar = serde_json::from_str(
+            r#"{"o":150.0,"h":155.0,"l":148.0,"c":153.0,"v":1000,"vw":151.5,"t":1703001240000,"n":10}"#,
+        )
+        .unwrap();
+        let event = agg_event(&"AAPL".into(), bar, 60_000);
+        assert_eq!(event.e - event.s, 60_000);
+        assert_eq!(event.z, 100);
+        assert_eq!(event.c, px(153.0));
+        assert_eq!(event.a, Price::default());
+    }
+}
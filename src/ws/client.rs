@@ -4,10 +4,19 @@
 //! Massive real-time data streams with automatic reconnection,
 //! backpressure handling, and efficient message dispatch.
 
-use crate::config::{OverflowPolicy, WsConfig};
+use crate::config::{FanoutMode, WsConfig};
 use crate::error::{MassiveError, WsError};
-use crate::ws::models::events::{parse_ws_message, WsEvent};
-use crate::ws::protocol::{Subscription, WsAuthMessage, WsSubscribeMessage};
+use crate::metrics::{names, ClientStats, StatsSnapshot};
+use crate::ws::ack::{AckAction, AckReply, AckTracker, CompletedAck, SubscriptionReport};
+use crate::ws::backfill::{self, GapTracker};
+use crate::ws::diagnostics::{Diagnostic, DiagnosticHub, DiagnosticStream};
+use crate::ws::dispatch::{Dispatcher, WsSubscriberStream};
+use crate::ws::filter::EventFilter;
+#[cfg(feature = "record")]
+use crate::ws::journal::JournalWriter;
+use crate::ws::lifecycle::{ConnectionEvent, DisconnectReason, LifecycleHub, LifecycleStream};
+use crate::ws::models::events::{parse_ws_message_for, ParsedMessage, WsEvent};
+use crate::ws::protocol::{Subscription, WsAuthMessage, WsSubscribeMessage, MAX_TOPICS_PER_FRAME};
 use dashmap::DashSet;
 use futures::{SinkExt, StreamExt};
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
@@ -46,6 +55,9 @@ use tracing::{debug, error, info, instrument, warn};
 /// ```
 pub struct WsClient {
     config: WsConfig,
+    stats: Arc<ClientStats>,
+    #[cfg(feature = "record")]
+    recorder: Option<crate::config::RecorderConfig>,
 }
 
 /// Handle for managing an active WebSocket connection.
@@ -54,10 +66,15 @@ pub struct WsClient {
 /// It is `Clone` and `Send`, so it can be shared across tasks.
 #[derive(Clone)]
 pub struct WsHandle {
-    cmd_tx: mpsc::Sender<WsCommand>,
-    state: Arc<WsState>,
+    pub(crate) cmd_tx: mpsc::Sender<WsCommand>,
+    pub(crate) state: Arc<WsState>,
+    pub(crate) dispatcher: Arc<Dispatcher>,
     /// Watch channel for connection state changes
-    state_rx: watch::Receiver<ConnectionState>,
+    pub(crate) state_rx: watch::Receiver<ConnectionState>,
+    /// Lifecycle events published by the IO task
+    pub(crate) lifecycle: Arc<LifecycleHub>,
+    /// Protocol diagnostics published by the IO task
+    pub(crate) diagnostics: Arc<DiagnosticHub>,
 }
 
 /// Shared state for WebSocket connection.
@@ -73,10 +90,12 @@ pub struct WsState {
     pub last_message_time: AtomicU64,
     /// Number of messages received
     pub message_count: AtomicU64,
-    /// Number of reconnection attempts
+    /// Total reconnection attempts since connecting
     pub reconnect_count: AtomicU32,
     /// Shutdown flag
-    shutdown: AtomicBool,
+    pub(crate) shutdown: AtomicBool,
+    /// Client-wide counters shared with the `WsClient`
+    pub(crate) stats: Arc<ClientStats>,
 }
 
 /// Connection state for monitoring.
@@ -110,12 +129,15 @@ pub struct WsMessageBatch {
     pub received_at: Instant,
     /// Estimated server-to-client latency if available
     pub latency_hint_ns: Option<u64>,
+    /// Whether the events were recovered over REST after a reconnect
+    /// instead of received live (see [`BackfillConfig`](crate::config::BackfillConfig))
+    pub backfilled: bool,
 }
 
 /// Commands sent to the WebSocket IO task.
-enum WsCommand {
-    Subscribe(Vec<Subscription>, oneshot::Sender<Result<(), MassiveError>>),
-    Unsubscribe(Vec<Subscription>, oneshot::Sender<Result<(), MassiveError>>),
+pub(crate) enum WsCommand {
+    Subscribe(Vec<Subscription>, AckReply),
+    Unsubscribe(Vec<Subscription>, AckReply),
     Close(oneshot::Sender<()>),
 }
 
@@ -126,10 +148,17 @@ pub struct WsStats {
     pub message_count: u64,
     /// Time since last message
     pub last_message_age: Duration,
-    /// Number of reconnection attempts
+    /// Total reconnection attempts since connecting
     pub reconnect_count: u32,
     /// Current subscription count
     pub subscription_count: usize,
+    /// Number of live event streams
+    pub stream_count: usize,
+    /// Batches dropped across all streams due to backpressure
+    pub dropped_count: u64,
+    /// Quote and aggregate events replaced by newer updates across all
+    /// streams under [`OverflowPolicy::ConflateLatest`](crate::config::OverflowPolicy::ConflateLatest)
+    pub conflated_count: u64,
 }
 
 impl WsClient {
@@ -137,7 +166,8 @@ impl WsClient {
     ///
     /// # Errors
     ///
-    /// Returns an error if the API key is empty.
+    /// Returns an error if the API key is empty or the base URL does not
+    /// use the `ws` or `wss` scheme.
     pub fn new(config: WsConfig) -> Result<Self, MassiveError> {
         // Validate API key is not empty
         if config.api_key.is_empty() {
@@ -145,7 +175,31 @@ impl WsClient {
                 "API key is empty. Set MASSIVE_API_KEY environment variable or provide a key via WsConfig::new()".into()
             ));
         }
-        Ok(Self { config })
+        if let Some(url) = &config.base_url {
+            if !matches!(url.scheme(), "ws" | "wss") {
+                return Err(MassiveError::InvalidArgument(
+                    "WebSocket base URL must use ws:// or wss://",
+                ));
+            }
+        }
+        Ok(Self {
+            config,
+            stats: Arc::new(ClientStats::new()),
+            #[cfg(feature = "record")]
+            recorder: None,
+        })
+    }
+
+    /// Record every frame received by connections from this client.
+    ///
+    /// The journal can be replayed with
+    /// [`ReplayClient`](crate::ws::ReplayClient). Each call to
+    /// [`connect`](Self::connect) starts a new journal file.
+    #[cfg(feature = "record")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "record")))]
+    pub fn with_recorder(mut self, recorder: crate::config::RecorderConfig) -> Self {
+        self.recorder = Some(recorder);
+        self
     }
 
     /// Create a WebSocket client builder.
@@ -158,6 +212,12 @@ impl WsClient {
         &self.config
     }
 
+    /// Get a snapshot of message statistics across all connections made
+    /// by this client.
+    pub fn stats(&self) -> StatsSnapshot {
+        self.stats.snapshot()
+    }
+
     /// Connect to the WebSocket server.
     ///
     /// Returns a handle for managing the connection and a stream of events.
@@ -165,8 +225,10 @@ impl WsClient {
     ///
     /// # Errors
     ///
-    /// Returns an error if the initial connection cannot be established or
-    /// authentication fails.
+    /// Returns [`MassiveError::Timeout`] if the connection is not
+    /// established and authenticated within
+    /// [`WsConfig::connect_timeout`], or an error if the connection fails or
+    /// the server rejects the API key.
     #[instrument(skip(self))]
     pub async fn connect(&self) -> Result<(WsHandle, WsEventStream), MassiveError> {
         let url = self.config.build_url();
@@ -175,56 +237,125 @@ impl WsClient {
         // Create channels
         let (cmd_tx, cmd_rx) = mpsc::channel::<WsCommand>(32);
         let (state_tx, state_rx) = watch::channel(ConnectionState::Connecting);
-        let (event_tx, event_rx) = mpsc::channel(self.config.dispatch.capacity);
+        let lifecycle = Arc::new(LifecycleHub::new());
+        let mut events = lifecycle.subscribe();
+        let diagnostics = Arc::new(DiagnosticHub::new());
+        let dispatcher = Arc::new(Dispatcher::new(self.config.dispatch.clone()));
+        let stream = dispatcher.add_subscriber();
 
         // Create shared state
-        let state = Arc::new(WsState {
-            authenticated: AtomicBool::new(false),
-            subscriptions: DashSet::new(),
-            last_message_time: AtomicU64::new(0),
-            message_count: AtomicU64::new(0),
-            reconnect_count: AtomicU32::new(0),
-            shutdown: AtomicBool::new(false),
-        });
+        let state = Arc::new(WsState::new(self.stats.clone()));
+        let session = Session {
+            gaps: GapTracker::default(),
+            acks: AckTracker::default(),
+            diagnostics: diagnostics.clone(),
+            #[cfg(feature = "record")]
+            journal: self
+                .recorder
+                .clone()
+                .map(|recorder| JournalWriter::spawn(recorder, self.stats.clone()))
+                .transpose()?,
+        };
 
         // Establish initial connection
-        let (ws_stream, _response) = connect_async(&url)
-            .await
-            .map_err(|e| Box::new(WsError::Connection(e)))?;
+        let started = Instant::now();
+        let (ws_stream, _response) =
+            tokio::time::timeout(self.config.connect_timeout, connect_async(&url))
+                .await
+                .map_err(|_| MassiveError::Timeout)?
+                .map_err(|e| Box::new(WsError::Connection(e)))?;
 
         let _ = state_tx.send(ConnectionState::Authenticating);
 
         // Spawn IO task with reconnection logic
         let io_state = state.clone();
+        let io_dispatcher = dispatcher.clone();
+        let io_lifecycle = lifecycle.clone();
         let config = self.config.clone();
 
         tokio::spawn(async move {
-            run_io_loop(ws_stream, cmd_rx, event_tx, io_state, config, state_tx).await;
+            run_io_loop(
+                ws_stream,
+                cmd_rx,
+                io_dispatcher,
+                io_state,
+                config,
+                state_tx,
+                io_lifecycle,
+                session,
+            )
+            .await;
         });
 
         // Create handle
         let handle = WsHandle {
             cmd_tx,
             state: state.clone(),
+            dispatcher,
             state_rx,
+            lifecycle,
+            diagnostics,
         };
 
-        // Wait for authentication
-        handle.wait_for_auth().await?;
+        // Wait for authentication within what is left of connect_timeout
+        let remaining = self
+            .config
+            .connect_timeout
+            .saturating_sub(started.elapsed());
+        let auth = tokio::time::timeout(remaining, async {
+            while let Some(event) = events.next().await {
+                match event {
+                    ConnectionEvent::Authenticated => return Ok(()),
+                    ConnectionEvent::Disconnected {
+                        reason: DisconnectReason::AuthFailed,
+                    } => {
+                        return Err(MassiveError::Ws(Box::new(WsError::AuthFailed(
+                            "API key rejected by server".into(),
+                        ))))
+                    }
+                    _ => {}
+                }
+            }
+            Err(MassiveError::Closed)
+        })
+        .await
+        .unwrap_or(Err(MassiveError::Timeout));
 
-        // Create event stream
-        let stream = Box::pin(futures::stream::unfold(event_rx, |mut rx| async move {
-            rx.recv().await.map(|batch| (batch, rx))
-        }));
+        if let Err(e) = auth {
+            // Stop the IO task instead of reconnecting in the background
+            state.shutdown.store(true, Ordering::Release);
+            return Err(e);
+        }
+
+        Ok((handle, Box::pin(stream)))
+    }
+}
 
-        Ok((handle, stream))
+impl WsState {
+    pub(crate) fn new(stats: Arc<ClientStats>) -> Self {
+        Self {
+            authenticated: AtomicBool::new(false),
+            subscriptions: DashSet::new(),
+            last_message_time: AtomicU64::new(0),
+            message_count: AtomicU64::new(0),
+            reconnect_count: AtomicU32::new(0),
+            shutdown: AtomicBool::new(false),
+            stats,
+        }
     }
 }
 
 impl WsHandle {
     /// Subscribe to topics.
     ///
-    /// Subscriptions are persisted and will be restored on reconnection.
+    /// Waits until the server confirms or rejects each topic, or until
+    /// [`WsConfig::subscribe_timeout`] elapses, and returns the status of
+    /// every topic. Only accepted topics are recorded in
+    /// [`subscriptions`](Self::subscriptions) and restored on reconnection.
+    /// Large topic lists are sent across several frames.
+    ///
+    /// Use [`SubscriptionReport::into_result`] to treat any rejected or
+    /// unconfirmed topic as an error.
     ///
     /// # Arguments
     ///
@@ -232,8 +363,13 @@ impl WsHandle {
     ///
     /// # Errors
     ///
-    /// Returns an error if the WebSocket connection is closed.
-    pub async fn subscribe(&self, topics: &[Subscription]) -> Result<(), MassiveError> {
+    /// Returns [`WsError::Disconnected`] if the connection drops before the
+    /// server answers; the client reconnects, so the request can be retried.
+    /// Returns [`MassiveError::Closed`] if the client has shut down.
+    pub async fn subscribe(
+        &self,
+        topics: &[Subscription],
+    ) -> Result<SubscriptionReport, MassiveError> {
         let (tx, rx) = oneshot::channel();
         self.cmd_tx
             .send(WsCommand::Subscribe(topics.to_vec(), tx))
@@ -244,14 +380,21 @@ impl WsHandle {
 
     /// Unsubscribe from topics.
     ///
+    /// Like [`subscribe`](Self::subscribe), waits for the server to confirm
+    /// each topic. Only confirmed topics are removed from
+    /// [`subscriptions`](Self::subscriptions).
+    ///
     /// # Arguments
     ///
     /// * `topics` - Slice of subscriptions to remove
     ///
     /// # Errors
     ///
-    /// Returns an error if the WebSocket connection is closed.
-    pub async fn unsubscribe(&self, topics: &[Subscription]) -> Result<(), MassiveError> {
+    /// Same as [`subscribe`](Self::subscribe).
+    pub async fn unsubscribe(
+        &self,
+        topics: &[Subscription],
+    ) -> Result<SubscriptionReport, MassiveError> {
         let (tx, rx) = oneshot::channel();
         self.cmd_tx
             .send(WsCommand::Unsubscribe(topics.to_vec(), tx))
@@ -279,6 +422,131 @@ impl WsHandle {
         *self.state_rx.borrow()
     }
 
+    /// Open an additional event stream on this connection.
+    ///
+    /// Requires [`FanoutMode::Broadcast`]. Every stream receives all batches
+    /// from the time it is opened, and applies the configured
+    /// [`OverflowPolicy`](crate::config::OverflowPolicy) to its own queue,
+    /// so a lagging consumer only drops its own messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client is configured for
+    /// [`FanoutMode::SingleConsumer`].
+    pub fn subscribe_stream(&self) -> Result<WsSubscriberStream, MassiveError> {
+        if self.dispatcher.fanout() != FanoutMode::Broadcast {
+            return Err(MassiveError::InvalidArgument(
+                "subscribe_stream requires FanoutMode::Broadcast",
+            ));
+        }
+        Ok(self.dispatcher.add_subscriber())
+    }
+
+    /// Open an event stream that only receives events matching `filter`.
+    ///
+    /// Routing happens in the IO task, so per-symbol consumers do not have
+    /// to filter the full feed themselves. Batches with no matching events
+    /// are skipped. Patterns only select among events already received;
+    /// the underlying topics still need to be subscribed with
+    /// [`subscribe`](Self::subscribe).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use massive_rs::ws::{WsHandle, EventFilter, Subscription};
+    /// # fn example(handle: &WsHandle) -> Result<(), massive_rs::MassiveError> {
+    /// let aapl_trades = handle.subscribe_filtered("T.AAPL")?;
+    /// let quotes = handle.subscribe_filtered(EventFilter::new().topic("Q.*"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client is configured for
+    /// [`FanoutMode::SingleConsumer`].
+    pub fn subscribe_filtered(
+        &self,
+        filter: impl Into<EventFilter>,
+    ) -> Result<WsSubscriberStream, MassiveError> {
+        if self.dispatcher.fanout() != FanoutMode::Broadcast {
+            return Err(MassiveError::InvalidArgument(
+                "subscribe_filtered requires FanoutMode::Broadcast",
+            ));
+        }
+        Ok(self.dispatcher.add_filtered_subscriber(Some(filter.into())))
+    }
+
+    /// Stream connection lifecycle events.
+    ///
+    /// The stream receives events published after it is created, and ends
+    /// when the connection shuts down. Events for the initial connection
+    /// and authentication are published before [`WsClient::connect`]
+    /// returns, so they are not observed here.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use massive_rs::ws::{ConnectionEvent, WsHandle};
+    /// # async fn example(handle: &WsHandle) {
+    /// use futures::StreamExt;
+    ///
+    /// let mut events = handle.lifecycle();
+    /// while let Some(event) = events.next().await {
+    ///     match event {
+    ///         ConnectionEvent::Disconnected { reason } => println!("paused: {reason}"),
+    ///         ConnectionEvent::Resubscribed { .. } => println!("resumed"),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn lifecycle(&self) -> LifecycleStream {
+        self.lifecycle.subscribe()
+    }
+
+    /// Stream protocol diagnostics.
+    ///
+    /// Delivers a [`Diagnostic`] for every frame or event that could not be
+    /// interpreted, such as malformed JSON, an event with missing fields, a
+    /// binary frame, or an unknown `status` message. These are not fatal and
+    /// the connection stays open, but events in malformed data are lost.
+    /// Only diagnostics published after the call are delivered; the stream
+    /// ends when the connection is closed for good.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use massive_rs::ws::WsHandle;
+    /// # async fn example(handle: &WsHandle) {
+    /// use futures::StreamExt;
+    ///
+    /// let mut diagnostics = handle.diagnostics();
+    /// tokio::spawn(async move {
+    ///     while let Some(diagnostic) = diagnostics.next().await {
+    ///         eprintln!("WebSocket data lost: {diagnostic}");
+    ///     }
+    /// });
+    /// # }
+    /// ```
+    pub fn diagnostics(&self) -> DiagnosticStream {
+        self.diagnostics.subscribe()
+    }
+
+    /// Register a callback for connection lifecycle events.
+    ///
+    /// The callback runs on the IO task as each event is published, before
+    /// it reaches [`lifecycle`](Self::lifecycle) streams, so it must return
+    /// quickly and must not block. It runs without holding any handle lock,
+    /// so it may call back into the handle, for example to register another
+    /// callback. Callbacks are released when the connection shuts down.
+    pub fn on_lifecycle<F>(&self, callback: F)
+    where
+        F: Fn(&ConnectionEvent) + Send + Sync + 'static,
+    {
+        self.lifecycle.add_callback(Arc::new(callback));
+    }
+
     /// Get current subscriptions.
     pub fn subscriptions(&self) -> Vec<Subscription> {
         self.state.subscriptions.iter().map(|s| s.clone()).collect()
@@ -287,19 +555,26 @@ impl WsHandle {
     /// Get connection statistics.
     pub fn stats(&self) -> WsStats {
         let last_msg = self.state.last_message_time.load(Ordering::Acquire);
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or(Duration::ZERO)
-            .as_millis() as u64;
 
         WsStats {
             message_count: self.state.message_count.load(Ordering::Acquire),
-            last_message_age: Duration::from_millis(now.saturating_sub(last_msg)),
+            last_message_age: Duration::from_millis(unix_millis().saturating_sub(last_msg)),
             reconnect_count: self.state.reconnect_count.load(Ordering::Acquire),
             subscription_count: self.state.subscriptions.len(),
+            stream_count: self.dispatcher.subscriber_count(),
+            dropped_count: self.dispatcher.dropped_count(),
+            conflated_count: self.dispatcher.conflated_count(),
         }
     }
 
+    /// Get a snapshot of message statistics.
+    ///
+    /// Unlike [`stats`](Self::stats), the counters are shared with the
+    /// [`WsClient`] and cover every connection it made.
+    pub fn client_stats(&self) -> StatsSnapshot {
+        self.state.stats.snapshot()
+    }
+
     /// Wait for a state change.
     pub async fn wait_for_state(&mut self, target: ConnectionState) {
         while *self.state_rx.borrow() != target {
@@ -308,23 +583,6 @@ impl WsHandle {
             }
         }
     }
-
-    /// Wait for authentication to complete.
-    async fn wait_for_auth(&self) -> Result<(), MassiveError> {
-        let start = Instant::now();
-        let timeout = Duration::from_secs(10);
-
-        while start.elapsed() < timeout {
-            if self.is_authenticated() {
-                return Ok(());
-            }
-            tokio::time::sleep(Duration::from_millis(10)).await;
-        }
-
-        Err(MassiveError::Ws(Box::new(WsError::AuthFailed(
-            "Timeout waiting for auth".into(),
-        ))))
-    }
 }
 
 impl std::fmt::Debug for WsHandle {
@@ -356,14 +614,53 @@ impl WsClientBuilder {
     }
 }
 
+/// State kept across the reconnects of one connection.
+struct Session {
+    /// Last trade and aggregate per symbol, for backfill
+    gaps: GapTracker,
+    /// Subscribe and unsubscribe requests waiting for server confirmation
+    acks: AckTracker,
+    /// Journal receiving every text frame
+    #[cfg(feature = "record")]
+    journal: Option<JournalWriter>,
+    /// Diagnostics shared with every handle
+    diagnostics: Arc<DiagnosticHub>,
+}
+
+impl Session {
+    /// Count a diagnostic and publish it to diagnostics streams.
+    fn report(&self, diagnostic: Diagnostic, state: &WsState, config: &WsConfig) {
+        state.stats.inc_parse_errors();
+        config.metrics.counter(
+            names::WS_PARSE_ERRORS,
+            1,
+            &[("market", config.market.as_path())],
+        );
+        warn!(?diagnostic, "Received data that could not be interpreted");
+        self.diagnostics.publish(diagnostic);
+    }
+
+    /// Finish the journal and end diagnostics streams.
+    async fn close(self) {
+        #[cfg(feature = "record")]
+        if let Some(journal) = self.journal {
+            journal.finish().await;
+        }
+        self.diagnostics.close();
+    }
+}
+
 /// Main IO loop with reconnection support.
+#[allow(clippy::too_many_arguments)]
 async fn run_io_loop<S>(
     initial_stream: S,
     mut cmd_rx: mpsc::Receiver<WsCommand>,
-    event_tx: mpsc::Sender<Result<WsMessageBatch, MassiveError>>,
+    dispatcher: Arc<Dispatcher>,
     state: Arc<WsState>,
     config: WsConfig,
     state_tx: watch::Sender<ConnectionState>,
+    lifecycle: Arc<LifecycleHub>,
+    mut session: Session,
 ) where
     S: futures::Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>>
         + futures::Sink<Message, Error = tokio_tungstenite::tungstenite::Error>
@@ -371,22 +668,32 @@ async fn run_io_loop<S>(
         + Send,
 {
     let (write, read) = initial_stream.split();
+    lifecycle.publish(ConnectionEvent::Connected);
 
     // Run first connection
     let result = run_connection(
         write,
         read,
         &mut cmd_rx,
-        &event_tx,
+        &dispatcher,
         &state,
         &config,
         &state_tx,
+        &lifecycle,
+        &mut session,
     )
     .await;
+    publish_disconnect(&lifecycle, &result);
+    if result.is_err() {
+        session.acks.disconnect();
+    }
 
     if result.is_ok() || state.shutdown.load(Ordering::Acquire) {
         info!("Connection closed cleanly");
         let _ = state_tx.send(ConnectionState::Disconnected);
+        // Finish the journal before streams observe the shutdown
+        session.close().await;
+        lifecycle.close();
         return;
     }
 
@@ -401,67 +708,256 @@ async fn run_io_loop<S>(
         }
 
         attempt += 1;
-        state.reconnect_count.store(attempt, Ordering::Release);
+        state.reconnect_count.fetch_add(1, Ordering::AcqRel);
         let _ = state_tx.send(ConnectionState::Reconnecting(attempt));
 
         if !config.reconnect.should_retry(attempt) {
             error!(attempt, "Max reconnection attempts reached");
             let _ = state_tx.send(ConnectionState::Disconnected);
+            lifecycle.publish(ConnectionEvent::GaveUp {
+                attempts: attempt - 1,
+            });
             break;
         }
 
         let delay = config.reconnect.delay_for_attempt(attempt);
         info!(attempt, ?delay, "Reconnecting after delay");
+        lifecycle.publish(ConnectionEvent::Reconnecting { attempt, delay });
         tokio::time::sleep(delay).await;
 
         let url = config.build_url();
-        let (ws_stream, _) = match connect_async(&url).await {
-            Ok(s) => s,
-            Err(e) => {
-                warn!(error = %e, attempt, "Reconnection failed");
-                continue;
-            }
-        };
+        let (ws_stream, _) =
+            match tokio::time::timeout(config.connect_timeout, connect_async(&url)).await {
+                Ok(Ok(s)) => s,
+                Ok(Err(e)) => {
+                    warn!(error = %e, attempt, "Reconnection failed");
+                    continue;
+                }
+                Err(_) => {
+                    warn!(attempt, "Reconnection timed out");
+                    continue;
+                }
+            };
 
         info!(attempt, "Reconnected successfully");
         state.authenticated.store(false, Ordering::Release);
+        state.stats.inc_reconnections();
+        config.metrics.counter(
+            names::WS_RECONNECTS,
+            1,
+            &[("market", config.market.as_path())],
+        );
 
         let (write, read) = ws_stream.split();
+        lifecycle.publish(ConnectionEvent::Connected);
 
-        match run_connection(
+        let result = run_connection(
             write,
             read,
             &mut cmd_rx,
-            &event_tx,
+            &dispatcher,
             &state,
             &config,
             &state_tx,
+            &lifecycle,
+            &mut session,
         )
-        .await
-        {
+        .await;
+        publish_disconnect(&lifecycle, &result);
+        if result.is_err() {
+            session.acks.disconnect();
+        }
+        // Backoff and max_retries apply to consecutive failures only
+        if state.authenticated.load(Ordering::Acquire) {
+            attempt = 0;
+        }
+
+        match result {
             Ok(()) => {
                 info!("Connection closed cleanly after reconnect");
                 let _ = state_tx.send(ConnectionState::Disconnected);
                 break;
             }
+            // Retrying cannot fix rejected credentials
+            Err(MassiveError::Ws(e)) if matches!(*e, WsError::AuthFailed(_)) => {
+                error!(error = %e, attempt, "Authentication rejected after reconnect");
+                let _ = state_tx.send(ConnectionState::Disconnected);
+                lifecycle.publish(ConnectionEvent::GaveUp { attempts: attempt });
+                break;
+            }
             Err(e) => {
                 warn!(error = %e, "Connection error, will reconnect");
                 continue;
             }
         }
     }
+
+    session.close().await;
+    lifecycle.close();
+}
+
+/// Current wall-clock time in Unix milliseconds.
+fn unix_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_millis() as u64
+}
+
+/// Publish a [`ConnectionEvent::Disconnected`] for a finished connection.
+fn publish_disconnect(lifecycle: &LifecycleHub, result: &Result<(), MassiveError>) {
+    let reason = match result {
+        Ok(()) => DisconnectReason::ClientClose,
+        Err(MassiveError::Ws(e)) => match **e {
+            WsError::IdleTimeout => DisconnectReason::IdleTimeout,
+            WsError::PongTimeout => DisconnectReason::PongTimeout,
+            WsError::Disconnected => DisconnectReason::ServerClose,
+            WsError::AuthFailed(_) => DisconnectReason::AuthFailed,
+            _ => DisconnectReason::Error,
+        },
+        Err(_) => DisconnectReason::Error,
+    };
+    lifecycle.publish(ConnectionEvent::Disconnected { reason });
+}
+
+/// Send subscribe or unsubscribe frames for `topics`, at most
+/// [`MAX_TOPICS_PER_FRAME`] topics each.
+async fn send_topics<W>(
+    write: &mut W,
+    action: AckAction,
+    topics: &[Subscription],
+) -> Result<(), MassiveError>
+where
+    W: futures::Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
+{
+    for chunk in topics.chunks(MAX_TOPICS_PER_FRAME) {
+        let msg = match action {
+            AckAction::Subscribe => WsSubscribeMessage::subscribe(chunk),
+            AckAction::Unsubscribe => WsSubscribeMessage::unsubscribe(chunk),
+        };
+        let json = serde_json::to_string(&msg)
+            .map_err(|_| MassiveError::InvalidArgument("Failed to serialize subscribe message"))?;
+        write
+            .send(Message::Text(json))
+            .await
+            .map_err(|e| MassiveError::Ws(Box::new(WsError::Connection(e))))?;
+    }
+    Ok(())
+}
+
+/// Send a subscribe or unsubscribe request and start waiting for its
+/// confirmations.
+#[allow(clippy::too_many_arguments)]
+async fn track_request<W>(
+    write: &mut W,
+    acks: &mut AckTracker,
+    action: AckAction,
+    topics: Vec<Subscription>,
+    reply: AckReply,
+    config: &WsConfig,
+    state: &WsState,
+    lifecycle: &LifecycleHub,
+) where
+    W: futures::Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
+{
+    if let Err(e) = send_topics(write, action, &topics).await {
+        let _ = reply.send(Err(e));
+        return;
+    }
+    let deadline = Instant::now() + config.subscribe_timeout;
+    if let Some(done) = acks.register(action, topics, deadline, Some(reply)) {
+        complete_ack(done, state, lifecycle);
+    }
+}
+
+/// Record the accepted topics of a finished request and reply to the caller.
+///
+/// For subscriptions restored after a reconnect, drops the topics the server
+/// no longer accepts, publishes [`ConnectionEvent::Resubscribed`], and
+/// returns `true`.
+fn complete_ack(done: CompletedAck, state: &WsState, lifecycle: &LifecycleHub) -> bool {
+    let accepted: Vec<Subscription> = done.report.accepted().cloned().collect();
+    let Some(reply) = done.reply else {
+        for (topic, status) in done.report.failed() {
+            warn!(%topic, ?status, "Dropping subscription not restored after reconnect");
+            state.subscriptions.remove(topic);
+        }
+        lifecycle.publish(ConnectionEvent::Resubscribed { topics: accepted });
+        return true;
+    };
+    match done.action {
+        AckAction::Subscribe => {
+            for topic in &accepted {
+                state.subscriptions.insert(topic.clone());
+            }
+            if !accepted.is_empty() {
+                lifecycle.publish(ConnectionEvent::Subscribed { topics: accepted });
+            }
+        }
+        AckAction::Unsubscribe => {
+            for topic in &accepted {
+                state.subscriptions.remove(topic);
+            }
+        }
+    }
+    let _ = reply.send(Ok(done.report));
+    false
+}
+
+/// Fetch the events missed before a reconnect in the background.
+///
+/// Called once the restored subscriptions are confirmed, so each gap ends
+/// where live delivery resumed.
+fn start_backfill(
+    config: &WsConfig,
+    session: &mut Session,
+    state: &WsState,
+    dispatcher: &Arc<Dispatcher>,
+    lifecycle: &Arc<LifecycleHub>,
+) {
+    let Some(backfill) = &config.backfill else {
+        return;
+    };
+    let missed = session
+        .gaps
+        .plan(&state.subscriptions, unix_millis() as i64, backfill.max_gap);
+    if missed.is_empty() {
+        return;
+    }
+    debug!(
+        gaps = missed.len(),
+        "Backfilling events missed during reconnect"
+    );
+    tokio::spawn(backfill::run(
+        backfill.clone(),
+        missed,
+        dispatcher.clone(),
+        lifecycle.clone(),
+    ));
 }
 
 /// Handle a single WebSocket connection.
+///
+/// Returns an error when the connection should be re-established: on
+/// transport errors, a server close, or when liveness checks fail. The
+/// connection is considered dead if nothing is received for
+/// `idle_timeout`, or if a keepalive ping is still unanswered when the next
+/// one is due.
+///
+/// With [`WsConfig::backfill`] set, live trades and aggregates are recorded
+/// in the session, and events missed before a reconnect are fetched in the
+/// background once the existing subscriptions have been restored.
 #[allow(clippy::too_many_arguments)]
 async fn run_connection<W, R>(
     mut write: W,
     mut read: R,
     cmd_rx: &mut mpsc::Receiver<WsCommand>,
-    event_tx: &mpsc::Sender<Result<WsMessageBatch, MassiveError>>,
+    dispatcher: &Arc<Dispatcher>,
     state: &Arc<WsState>,
     config: &WsConfig,
     state_tx: &watch::Sender<ConnectionState>,
+    lifecycle: &Arc<LifecycleHub>,
+    session: &mut Session,
 ) -> Result<(), MassiveError>
 where
     W: futures::Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
@@ -478,25 +974,35 @@ where
 
     debug!("Sent authentication message");
 
-    // Resubscribe to existing subscriptions
+    // Restore existing subscriptions; they are confirmed like any other
+    // subscribe request, and rejected topics are dropped
     let subs: Vec<_> = state.subscriptions.iter().map(|s| s.clone()).collect();
     if !subs.is_empty() {
-        let msg = WsSubscribeMessage::subscribe(&subs);
-        let sub_json = serde_json::to_string(&msg)
-            .map_err(|_| MassiveError::InvalidArgument("Failed to serialize subscribe message"))?;
-        write
-            .send(Message::Text(sub_json))
-            .await
-            .map_err(|e| Box::new(WsError::Connection(e)))?;
-        debug!(count = subs.len(), "Resubscribed to existing topics");
+        send_topics(&mut write, AckAction::Subscribe, &subs).await?;
+        debug!(count = subs.len(), "Restoring existing subscriptions");
+        let deadline = Instant::now() + config.subscribe_timeout;
+        session
+            .acks
+            .register(AckAction::Subscribe, subs, deadline, None);
     }
 
+    let ack_timer = tokio::time::sleep(config.subscribe_timeout);
+    tokio::pin!(ack_timer);
+
     // Set up ping interval
     let mut ping_interval = tokio::time::interval(config.ping_interval);
     ping_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
     // Track last activity for idle timeout
     let mut last_activity = Instant::now();
+    let idle_deadline = tokio::time::sleep(config.idle_timeout);
+    tokio::pin!(idle_deadline);
+
+    // Whether the last keepalive ping is still waiting for its pong
+    let mut awaiting_pong = false;
+
+    let metrics = &*config.metrics;
+    let tags = [("market", config.market.as_path())];
 
     loop {
         tokio::select! {
@@ -504,45 +1010,80 @@ where
             msg = read.next() => {
                 match msg {
                     Some(Ok(Message::Text(text))) => {
+                        #[cfg(feature = "record")]
+                        if let Some(journal) = &session.journal {
+                            journal.record(&text);
+                        }
                         last_activity = Instant::now();
                         let received_at = Instant::now();
-                        let now_ms = std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap_or(Duration::ZERO)
-                            .as_millis() as u64;
-                        state.last_message_time.store(now_ms, Ordering::Release);
+                        state.last_message_time.store(unix_millis(), Ordering::Release);
                         state.message_count.fetch_add(1, Ordering::AcqRel);
+                        state.stats.inc_bytes_received(text.len() as u64);
+                        metrics.counter(names::WS_BYTES_RECEIVED, text.len() as u64, &tags);
+
+                        match parse_ws_message_for(&text, config.market) {
+                            Ok(ParsedMessage { events, errors }) => {
+                                for error in &errors {
+                                    session.report(Diagnostic::invalid_event(error), state, config);
+                                }
+                                if events.is_empty() {
+                                    continue;
+                                }
+                                state.stats.inc_messages_received(events.len() as u64);
+                                metrics.counter(names::WS_MESSAGES_RECEIVED, events.len() as u64, &tags);
 
-                        match parse_ws_message(&text) {
-                            Ok(events) => {
-                                // Check for auth success/failure
+                                // Check for auth results and subscription acks
                                 for event in &events {
                                     if let WsEvent::Status(status) = event {
                                         if status.is_auth_success() {
                                             state.authenticated.store(true, Ordering::Release);
                                             let _ = state_tx.send(ConnectionState::Connected);
                                             info!("WebSocket authenticated");
+                                            lifecycle.publish(ConnectionEvent::Authenticated);
                                         } else if status.is_auth_failed() {
                                             error!("WebSocket authentication failed: {:?}", status.message);
                                             return Err(MassiveError::Ws(Box::new(
                                                 WsError::AuthFailed(status.message.clone().unwrap_or_default())
                                             )));
+                                        } else {
+                                            if let Some(diagnostic) = Diagnostic::unknown_status(status, &text) {
+                                                session.report(diagnostic, state, config);
+                                            }
+                                            for done in session.acks.on_status(status) {
+                                                if complete_ack(done, state, lifecycle) {
+                                                    start_backfill(config, session, state, dispatcher, lifecycle);
+                                                }
+                                            }
                                         }
                                     }
                                 }
 
+                                if config.backfill.is_some() {
+                                    session.gaps.observe(&events);
+                                }
+
                                 let batch = WsMessageBatch {
                                     events,
                                     received_at,
                                     latency_hint_ns: None,
+                                    backfilled: false,
                                 };
 
-                                if try_send_event(event_tx, Ok(batch), config.dispatch.overflow).await.is_err() {
+                                let Ok(dropped) = dispatcher.dispatch(batch) else {
                                     return Err(MassiveError::Ws(Box::new(WsError::BackpressureOverflow)));
+                                };
+                                if dropped > 0 {
+                                    state.stats.inc_messages_dropped(dropped as u64);
+                                    metrics.counter(names::WS_MESSAGES_DROPPED, dropped as u64, &tags);
                                 }
+                                metrics.gauge(names::WS_QUEUE_DEPTH, dispatcher.queue_depth() as i64, &tags);
                             }
                             Err(e) => {
-                                warn!(error = %e, text = %text, "Failed to parse WebSocket message");
+                                let diagnostic = Diagnostic::MalformedFrame {
+                                    raw: text.to_string(),
+                                    error: e.to_string(),
+                                };
+                                session.report(diagnostic, state, config);
                             }
                         }
                     }
@@ -554,14 +1095,16 @@ where
                     }
                     Some(Ok(Message::Pong(_))) => {
                         last_activity = Instant::now();
+                        awaiting_pong = false;
                         debug!("Received pong");
                     }
                     Some(Ok(Message::Close(frame))) => {
                         info!(?frame, "WebSocket closed by server");
                         return Err(MassiveError::Ws(Box::new(WsError::Disconnected)));
                     }
-                    Some(Ok(Message::Binary(_))) => {
-                        debug!("Received unexpected binary message");
+                    Some(Ok(Message::Binary(data))) => {
+                        last_activity = Instant::now();
+                        session.report(Diagnostic::BinaryFrame { data }, state, config);
                     }
                     Some(Ok(Message::Frame(_))) => {
                         // Raw frame, usually not received
@@ -582,39 +1125,17 @@ where
                 match cmd {
                     Some(WsCommand::Subscribe(topics, reply)) => {
                         debug!(?topics, "Processing subscribe command");
-                        let msg = WsSubscribeMessage::subscribe(&topics);
-
-                        let result = match serde_json::to_string(&msg) {
-                            Ok(json) => write.send(Message::Text(json)).await
-                                .map_err(|e| MassiveError::Ws(Box::new(WsError::Connection(e)))),
-                            Err(_) => Err(MassiveError::InvalidArgument("Failed to serialize subscribe message")),
-                        };
-
-                        if result.is_ok() {
-                            for topic in topics {
-                                state.subscriptions.insert(topic);
-                            }
+                        track_request(&mut write, &mut session.acks, AckAction::Subscribe, topics, reply, config, state, lifecycle).await;
+                        if let Some(deadline) = session.acks.next_deadline() {
+                            ack_timer.as_mut().reset(deadline);
                         }
-
-                        let _ = reply.send(result);
                     }
                     Some(WsCommand::Unsubscribe(topics, reply)) => {
                         debug!(?topics, "Processing unsubscribe command");
-                        let msg = WsSubscribeMessage::unsubscribe(&topics);
-
-                        let result = match serde_json::to_string(&msg) {
-                            Ok(json) => write.send(Message::Text(json)).await
-                                .map_err(|e| MassiveError::Ws(Box::new(WsError::Connection(e)))),
-                            Err(_) => Err(MassiveError::InvalidArgument("Failed to serialize unsubscribe message")),
-                        };
-
-                        if result.is_ok() {
-                            for topic in &topics {
-                                state.subscriptions.remove(topic);
-                            }
+                        track_request(&mut write, &mut session.acks, AckAction::Unsubscribe, topics, reply, config, state, lifecycle).await;
+                        if let Some(deadline) = session.acks.next_deadline() {
+                            ack_timer.as_mut().reset(deadline);
                         }
-
-                        let _ = reply.send(result);
                     }
                     Some(WsCommand::Close(reply)) => {
                         debug!("Processing close command");
@@ -629,49 +1150,57 @@ where
                 }
             }
 
+            // Time out unconfirmed subscribe and unsubscribe requests
+            () = &mut ack_timer, if session.acks.has_pending() => {
+                for done in session.acks.expire(Instant::now()) {
+                    warn!(action = ?done.action, "Timed out waiting for subscription confirmation");
+                    if complete_ack(done, state, lifecycle) {
+                        start_backfill(config, session, state, dispatcher, lifecycle);
+                    }
+                }
+                if let Some(deadline) = session.acks.next_deadline() {
+                    ack_timer.as_mut().reset(deadline);
+                }
+            }
+
+            // Close the connection if nothing arrived within idle_timeout
+            () = &mut idle_deadline => {
+                let deadline = last_activity + config.idle_timeout;
+                if Instant::now() >= deadline {
+                    warn!(idle_timeout = ?config.idle_timeout, "Connection idle timeout, closing");
+                    return Err(MassiveError::Ws(Box::new(WsError::IdleTimeout)));
+                }
+                idle_deadline.as_mut().reset(deadline);
+            }
+
             // Send ping for keepalive
             _ = ping_interval.tick() => {
-                if last_activity.elapsed() > config.idle_timeout {
-                    warn!("Connection idle timeout, sending ping");
+                if awaiting_pong {
+                    warn!("Pong not received before next ping, closing");
+                    return Err(MassiveError::Ws(Box::new(WsError::PongTimeout)));
                 }
                 if let Err(e) = write.send(Message::Ping(vec![])).await {
                     warn!(error = %e, "Failed to send ping");
                     return Err(MassiveError::Ws(Box::new(WsError::Connection(e))));
                 }
+                awaiting_pong = true;
             }
         }
     }
 }
 
-/// Try to send an event with backpressure handling.
-async fn try_send_event(
-    tx: &mpsc::Sender<Result<WsMessageBatch, MassiveError>>,
-    batch: Result<WsMessageBatch, MassiveError>,
-    policy: OverflowPolicy,
-) -> Result<(), ()> {
-    match tx.try_send(batch) {
-        Ok(()) => Ok(()),
-        Err(mpsc::error::TrySendError::Full(_)) => match policy {
-            OverflowPolicy::DropNewest | OverflowPolicy::DropOldest => {
-                warn!("Buffer full, dropping message");
-                Ok(())
-            }
-            OverflowPolicy::ErrorAndClose => Err(()),
-        },
-        Err(mpsc::error::TrySendError::Closed(_)) => Err(()),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ws::models::events::parse_ws_message;
 
     #[test]
     fn test_ws_message_batch() {
         let batch = WsMessageBatch {
-            events: vec![WsEvent::Unknown],
+            events: parse_ws_message(r#"{"ev":"NEW"}"#).unwrap().events,
             received_at: Instant::now(),
             latency_hint_ns: Some(1000),
+            backfilled: false,
         };
 
         assert_eq!(batch.events.len(), 1);
@@ -687,6 +1216,7 @@ mod tests {
             message_count: AtomicU64::new(0),
             reconnect_count: AtomicU32::new(0),
             shutdown: AtomicBool::new(false),
+            stats: Arc::new(ClientStats::new()),
         };
 
         assert!(!state.authenticated.load(Ordering::Relaxed));
@@ -714,6 +1244,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ws_client_rejects_non_ws_base_url() {
+        let config = WsConfig::new("test-api-key")
+            .with_base_url(url::Url::parse("https://socket.massive.com").unwrap());
+        assert!(matches!(
+            WsClient::new(config),
+            Err(MassiveError::InvalidArgument(_))
+        ));
+    }
+
     #[test]
     fn test_connection_state_debug() {
         assert_eq!(format!("{:?}", ConnectionState::Connecting), "Connecting");
@@ -732,6 +1272,7 @@ mod tests {
             message_count: AtomicU64::new(42),
             reconnect_count: AtomicU32::new(2),
             shutdown: AtomicBool::new(false),
+            stats: Arc::new(ClientStats::new()),
         });
 
         state.subscriptions.insert(Subscription::trade("AAPL"));
@@ -739,16 +1280,60 @@ mod tests {
 
         let (_, state_rx) = watch::channel(ConnectionState::Connected);
         let (cmd_tx, _) = mpsc::channel(1);
+        let dispatcher = Arc::new(Dispatcher::new(Default::default()));
+        let _stream = dispatcher.add_subscriber();
 
         let handle = WsHandle {
             cmd_tx,
             state,
+            dispatcher,
             state_rx,
+            lifecycle: Arc::new(LifecycleHub::new()),
+            diagnostics: Arc::new(DiagnosticHub::new()),
         };
 
         let stats = handle.stats();
         assert_eq!(stats.message_count, 42);
         assert_eq!(stats.reconnect_count, 2);
         assert_eq!(stats.subscription_count, 2);
+        assert_eq!(stats.stream_count, 1);
+    }
+
+    #[test]
+    fn test_subscribe_stream_requires_broadcast() {
+        let (_, state_rx) = watch::channel(ConnectionState::Connected);
+        let (cmd_tx, _) = mpsc::channel(1);
+        let state = Arc::new(WsState {
+            authenticated: AtomicBool::new(true),
+            subscriptions: DashSet::new(),
+            last_message_time: AtomicU64::new(0),
+            message_count: AtomicU64::new(0),
+            reconnect_count: AtomicU32::new(0),
+            shutdown: AtomicBool::new(false),
+            stats: Arc::new(ClientStats::new()),
+        });
+        let dispatch = crate::config::DispatchConfig {
+            fanout: FanoutMode::Broadcast,
+            ..Default::default()
+        };
+
+        let mut handle = WsHandle {
+            cmd_tx,
+            state,
+            dispatcher: Arc::new(Dispatcher::new(Default::default())),
+            state_rx,
+            lifecycle: Arc::new(LifecycleHub::new()),
+            diagnostics: Arc::new(DiagnosticHub::new()),
+        };
+        assert!(matches!(
+            handle.subscribe_stream(),
+            Err(MassiveError::InvalidArgument(_))
+        ));
+
+        handle.dispatcher = Arc::new(Dispatcher::new(dispatch));
+        let a = handle.subscribe_stream().unwrap();
+        let b = handle.clone().subscribe_stream().unwrap();
+        assert_ne!(a.id(), b.id());
+        assert_eq!(handle.stats().stream_count, 2);
     }
 }
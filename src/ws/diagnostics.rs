@@ -0,0 +1,189 @@
+//! Non-fatal protocol diagnostics.
+//!
+//! Frames and events the client cannot interpret are not delivered on the
+//! event stream. Instead of only logging them, the IO task publishes a
+//! [`Diagnostic`] with the raw data, so consumers can detect data loss.
+//! Consume them with
+//! [`WsHandle::diagnostics`](crate::ws::WsHandle::diagnostics). Every
+//! diagnostic is also counted in
+//! [`ClientStats::parse_errors`](crate::metrics::ClientStats::parse_errors).
+
+use crate::ws::lifecycle::{EventHub, HubEvent};
+use crate::ws::models::events::{EventParseError, WsStatusEvent};
+use futures::Stream;
+use smol_str::SmolStr;
+use std::pin::Pin;
+
+/// Something received that could not be interpreted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Diagnostic {
+    /// A text frame that is not valid JSON. Nothing in it was delivered.
+    MalformedFrame {
+        /// The frame as received
+        raw: String,
+        /// Parser error
+        error: String,
+    },
+    /// An event that could not be parsed, such as a known event type with a
+    /// missing field. The other events of its frame were delivered.
+    InvalidEvent {
+        /// Position of the event in its frame
+        index: usize,
+        /// Event type, if the `ev` field could be read
+        ev: Option<SmolStr>,
+        /// The event JSON as received
+        raw: String,
+        /// Parser error
+        error: String,
+    },
+    /// A binary frame. The API only sends text frames.
+    BinaryFrame {
+        /// The frame payload
+        data: Vec<u8>,
+    },
+    /// A `status` event with a status the client does not handle, such as
+    /// a server notice. The event itself is still delivered on the stream.
+    UnknownStatus {
+        /// The `status` field
+        status: String,
+        /// The `message` field
+        message: Option<String>,
+        /// The frame containing the event
+        raw: String,
+    },
+    /// The diagnostics stream fell behind and missed `count` diagnostics.
+    /// They were still counted in
+    /// [`ClientStats::parse_errors`](crate::metrics::ClientStats::parse_errors).
+    Lagged {
+        /// Number of diagnostics missed
+        count: u64,
+    },
+}
+
+impl Diagnostic {
+    pub(crate) fn invalid_event(error: &EventParseError) -> Self {
+        Diagnostic::InvalidEvent {
+            index: error.index,
+            ev: error.ev.clone(),
+            raw: error.raw.get().to_string(),
+            error: error.error.to_string(),
+        }
+    }
+
+    /// Unknown-status diagnostic for `status`, or `None` if the client
+    /// handles it.
+    pub(crate) fn unknown_status(status: &WsStatusEvent, frame: &str) -> Option<Self> {
+        let known = status.is_connected()
+            || status.is_auth_success()
+            || status.is_auth_failed()
+            || matches!(status.status.as_str(), "success" | "error");
+        (!known).then(|| Diagnostic::UnknownStatus {
+            status: status.status.clone(),
+            message: status.message.clone(),
+            raw: frame.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Diagnostic::MalformedFrame { error, .. } => write!(f, "malformed frame: {error}"),
+            Diagnostic::InvalidEvent {
+                index,
+                ev: Some(ev),
+                error,
+                ..
+            } => write!(f, "invalid {ev} event at {index}: {error}"),
+            Diagnostic::InvalidEvent { index, error, .. } => {
+                write!(f, "invalid event at {index}: {error}")
+            }
+            Diagnostic::BinaryFrame { data } => write!(f, "binary frame of {} bytes", data.len()),
+            Diagnostic::UnknownStatus { status, .. } => write!(f, "unknown status {status:?}"),
+            Diagnostic::Lagged { count } => write!(f, "missed {count} diagnostics"),
+        }
+    }
+}
+
+/// Stream of protocol diagnostics.
+pub type DiagnosticStream = Pin<Box<dyn Stream<Item = Diagnostic> + Send>>;
+
+/// Capacity of the diagnostics broadcast channel.
+///
+/// Malformed data can arrive in bursts, so this is larger than the
+/// lifecycle channel.
+const DIAGNOSTICS_CAPACITY: usize = 1024;
+
+/// Hub for protocol diagnostics.
+pub(crate) type DiagnosticHub = EventHub<Diagnostic>;
+
+impl HubEvent for Diagnostic {
+    const CAPACITY: usize = DIAGNOSTICS_CAPACITY;
+
+    fn lagged(count: u64) -> Option<Self> {
+        Some(Diagnostic::Lagged { count })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ws::models::events::{parse_ws_message, WsEvent};
+
+    fn status(json: &str) -> WsStatusEvent {
+        match parse_ws_message(json).unwrap().events.remove(0) {
+            WsEvent::Status(status) => status,
+            _ => panic!("Expected status event"),
+        }
+    }
+
+    #[test]
+    fn test_unknown_status() {
+        let frame = r#"{"ev":"status","status":"max_connections","message":"Maximum number of connections exceeded."}"#;
+        let diagnostic = Diagnostic::unknown_status(&status(frame), frame).unwrap();
+        assert!(matches!(
+            &diagnostic,
+            Diagnostic::UnknownStatus { status, raw, .. } if status == "max_connections" && raw == frame
+        ));
+        assert_eq!(
+            diagnostic.to_string(),
+            r#"unknown status "max_connections""#
+        );
+
+        for known in [
+            "connected",
+            "auth_success",
+            "auth_failed",
+            "success",
+            "error",
+        ] {
+            let frame = format!(r#"{{"ev":"status","status":"{known}"}}"#);
+            assert_eq!(Diagnostic::unknown_status(&status(&frame), &frame), None);
+        }
+    }
+
+    #[test]
+    fn test_invalid_event() {
+        let parsed = parse_ws_message(r#"[{"ev":"Q","sym":"AAPL"}]"#).unwrap();
+        let diagnostic = Diagnostic::invalid_event(&parsed.errors[0]);
+        assert!(diagnostic
+            .to_string()
+            .starts_with("invalid Q event at 0: missing field"));
+    }
+
+    #[tokio::test]
+    async fn test_lagged_stream_reports_missed() {
+        use futures::StreamExt;
+
+        let hub = DiagnosticHub::new();
+        let mut stream = hub.subscribe();
+        for _ in 0..DIAGNOSTICS_CAPACITY + 3 {
+            hub.publish(Diagnostic::BinaryFrame { data: Vec::new() });
+        }
+        hub.close();
+
+        assert_eq!(stream.next().await, Some(Diagnostic::Lagged { count: 3 }));
+        assert_eq!(stream.count().await, DIAGNOSTICS_CAPACITY);
+    }
+}
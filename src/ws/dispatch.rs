@@ -0,0 +1,717 @@
+//! Event dispatch to stream consumers.
+//!
+//! The IO task hands every parsed batch to a [`Dispatcher`], which delivers
+//! it to a single consumer ([`FanoutMode::SingleConsumer`]) or to every
+//! registered subscriber ([`FanoutMode::Broadcast`]). Each subscriber owns
+//! its queue, so the [`OverflowPolicy`] and lag accounting apply to each
+//! consumer independently.
+//!
+//! Queues are bounded by [`DispatchConfig::capacity`] batches. When a queue
+//! is full, [`OverflowPolicy::DropOldest`] evicts the oldest queued batch,
+//! [`OverflowPolicy::DropNewest`] discards the incoming one, and
+//! [`OverflowPolicy::ConflateLatest`] replaces queued quotes and aggregates
+//! with newer updates for the same symbol.
+
+use crate::config::{DispatchConfig, FanoutMode, OverflowPolicy};
+use crate::error::{MassiveError, WsError};
+use crate::util::Symbol;
+use crate::ws::client::WsMessageBatch;
+use crate::ws::filter::EventFilter;
+use crate::ws::models::events::WsEvent;
+use futures::Stream;
+use std::collections::{HashSet, VecDeque};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use tracing::warn;
+
+/// Item type delivered to event streams.
+pub(crate) type EventResult = Result<WsMessageBatch, MassiveError>;
+
+/// Fans parsed batches out to one or more subscriber queues.
+pub(crate) struct Dispatcher {
+    config: DispatchConfig,
+    subscribers: Mutex<Vec<SubscriberSlot>>,
+    next_id: AtomicU64,
+}
+
+/// Dispatcher side of a subscriber queue.
+///
+/// Dropping the slot closes the queue, ending the stream once it is drained.
+struct SubscriberSlot {
+    id: u64,
+    queue: Arc<SubscriberQueue>,
+    /// Only events matching this filter are delivered (all if `None`)
+    filter: Option<EventFilter>,
+}
+
+impl Drop for SubscriberSlot {
+    fn drop(&mut self) {
+        let mut inner = self.queue.lock();
+        inner.closed = true;
+        let waker = inner.waker.take();
+        drop(inner);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Bounded batch queue shared by the dispatcher and one stream.
+struct SubscriberQueue {
+    inner: Mutex<QueueInner>,
+    capacity: usize,
+    /// Batches discarded because the queue was full
+    dropped: AtomicU64,
+    /// Events replaced by newer updates under `ConflateLatest`
+    conflated: AtomicU64,
+}
+
+#[derive(Default)]
+struct QueueInner {
+    batches: VecDeque<WsMessageBatch>,
+    /// Waker of a stream waiting for the next batch
+    waker: Option<Waker>,
+    /// Set when the dispatcher stopped delivering to this queue
+    closed: bool,
+    /// Set when the subscriber was closed by `ErrorAndClose`
+    overflowed: bool,
+    /// Set when the stream was dropped
+    detached: bool,
+}
+
+/// Why a batch could not be queued.
+enum PushError {
+    /// The queue is full and the policy is `ErrorAndClose`
+    Full,
+    /// The stream was dropped
+    Detached,
+}
+
+impl SubscriberQueue {
+    fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(QueueInner::default()),
+            capacity: capacity.max(1),
+            dropped: AtomicU64::new(0),
+            conflated: AtomicU64::new(0),
+        }
+    }
+
+    /// Queue a batch, applying `policy` if the queue is full.
+    ///
+    /// Returns the number of events discarded to make room.
+    fn push(&self, mut batch: WsMessageBatch, policy: OverflowPolicy) -> Result<usize, PushError> {
+        let mut inner = self.lock();
+        if inner.detached {
+            return Err(PushError::Detached);
+        }
+
+        let discarded = if inner.batches.len() < self.capacity {
+            inner.batches.push_back(batch);
+            0
+        } else {
+            match policy {
+                OverflowPolicy::DropNewest => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return Ok(batch.events.len());
+                }
+                OverflowPolicy::DropOldest => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    let oldest = inner.batches.pop_front();
+                    inner.batches.push_back(batch);
+                    oldest.map_or(0, |b| b.events.len())
+                }
+                OverflowPolicy::ConflateLatest => {
+                    let conflated = conflate(&mut inner.batches, &mut batch);
+                    self.conflated
+                        .fetch_add(conflated as u64, Ordering::Relaxed);
+                    let full = inner.batches.len() >= self.capacity;
+                    match inner.batches.back_mut() {
+                        Some(last) if full => last.events.append(&mut batch.events),
+                        _ => inner.batches.push_back(batch),
+                    }
+                    conflated
+                }
+                OverflowPolicy::ErrorAndClose => return Err(PushError::Full),
+            }
+        };
+
+        let waker = inner.waker.take();
+        drop(inner);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        Ok(discarded)
+    }
+
+    fn len(&self) -> usize {
+        self.lock().batches.len()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Key identifying events that `ConflateLatest` may replace: quotes and
+/// aggregates, per event type and symbol.
+fn conflation_key(event: &WsEvent) -> Option<(&'static str, &str)> {
+    match event {
+        WsEvent::Quote(e) => Some(("Q", e.sym.as_str())),
+        WsEvent::SecondAggregate(e) => Some(("A", e.sym.as_str())),
+        WsEvent::MinuteAggregate(e) => Some(("AM", e.sym.as_str())),
+        WsEvent::CryptoQuote(e) => Some(("XQ", e.pair.as_str())),
+        WsEvent::CryptoAggregate(e) => Some(("XA", e.pair.as_str())),
+        WsEvent::ForexQuote(e) => Some(("C", e.p.as_str())),
+        WsEvent::ForexAggregate(e) => Some(("CA", e.pair.as_str())),
+        WsEvent::FuturesQuote(e) => Some(("Q", e.sym.as_str())),
+        WsEvent::FuturesSecondAggregate(e) => Some(("A", e.sym.as_str())),
+        WsEvent::FuturesMinuteAggregate(e) => Some(("AM", e.sym.as_str())),
+        _ => None,
+    }
+}
+
+/// Remove queued quotes and aggregates superseded by `batch`, keeping only
+/// the latest update per key within `batch` itself.
+///
+/// Other events, trades included, are never removed. Batches emptied by
+/// conflation are dropped from the queue. Returns the number of events
+/// removed.
+fn conflate(queue: &mut VecDeque<WsMessageBatch>, batch: &mut WsMessageBatch) -> usize {
+    // Walk newest first so the last update per key survives
+    let mut latest = HashSet::new();
+    let mut keep: Vec<bool> = batch
+        .events
+        .iter()
+        .rev()
+        .map(|e| {
+            conflation_key(e).map_or(true, |(kind, sym)| latest.insert((kind, Symbol::new(sym))))
+        })
+        .collect();
+    keep.reverse();
+
+    let mut removed = keep.iter().filter(|k| !**k).count();
+    let mut keep = keep.into_iter();
+    batch.events.retain(|_| keep.next().unwrap_or(true));
+
+    if latest.is_empty() {
+        return removed;
+    }
+    queue.retain_mut(|queued| {
+        let before = queued.events.len();
+        queued.events.retain(|e| {
+            conflation_key(e).map_or(true, |(kind, sym)| {
+                !latest.contains(&(kind, Symbol::new(sym)))
+            })
+        });
+        removed += before - queued.events.len();
+        before == 0 || !queued.events.is_empty()
+    });
+    removed
+}
+
+impl Dispatcher {
+    /// Create a dispatcher with no subscribers.
+    pub fn new(config: DispatchConfig) -> Self {
+        Self {
+            config,
+            subscribers: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Get the configured fanout mode.
+    pub fn fanout(&self) -> FanoutMode {
+        self.config.fanout
+    }
+
+    /// Register a new subscriber and return its stream.
+    pub fn add_subscriber(&self) -> WsSubscriberStream {
+        self.add_filtered_subscriber(None)
+    }
+
+    /// Register a subscriber that only receives events matching `filter`.
+    pub fn add_filtered_subscriber(&self, filter: Option<EventFilter>) -> WsSubscriberStream {
+        let queue = Arc::new(SubscriberQueue::new(self.config.capacity));
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        self.lock().push(SubscriberSlot {
+            id,
+            queue: queue.clone(),
+            filter,
+        });
+
+        WsSubscriberStream { id, queue }
+    }
+
+    /// Remove every subscriber, ending their streams once drained.
+    #[cfg(feature = "record")]
+    pub fn close(&self) {
+        self.lock().clear();
+    }
+
+    /// Number of live subscribers.
+    pub fn subscriber_count(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Total batches dropped across all live subscribers.
+    pub fn dropped_count(&self) -> u64 {
+        self.lock()
+            .iter()
+            .map(|s| s.queue.dropped.load(Ordering::Relaxed))
+            .sum()
+    }
+
+    /// Total events conflated across all live subscribers.
+    pub fn conflated_count(&self) -> u64 {
+        self.lock()
+            .iter()
+            .map(|s| s.queue.conflated.load(Ordering::Relaxed))
+            .sum()
+    }
+
+    /// Number of batches waiting in the deepest subscriber queue.
+    pub fn queue_depth(&self) -> usize {
+        self.lock().iter().map(|s| s.queue.len()).max().unwrap_or(0)
+    }
+
+    /// Deliver a batch to every subscriber.
+    ///
+    /// Filtered subscribers receive only their matching events, and nothing
+    /// at all if none match.
+    ///
+    /// On success, returns the number of events dropped or conflated because
+    /// a subscriber queue was full. Returns `Err(())` when the connection
+    /// should be torn down: in single-consumer mode, when the consumer is
+    /// gone or overflowed under [`OverflowPolicy::ErrorAndClose`]. In
+    /// broadcast mode a failing subscriber is removed and the others keep
+    /// receiving.
+    pub fn dispatch(&self, batch: WsMessageBatch) -> Result<usize, ()> {
+        let single = self.config.fanout == FanoutMode::SingleConsumer;
+        let mut subs = self.lock();
+        let count = subs.len();
+        if single && count == 0 {
+            return Err(());
+        }
+        let mut batch = Some(batch);
+        let mut fatal = false;
+        let mut dropped = 0;
+        let mut closed = Vec::new();
+
+        for (idx, sub) in subs.iter().enumerate() {
+            // An unfiltered last subscriber takes ownership; the rest get copies
+            let item = match (&sub.filter, &batch) {
+                (Some(filter), Some(b)) => filter.apply(b),
+                (None, Some(_)) if idx + 1 == count => batch.take(),
+                (None, Some(b)) => Some(b.clone()),
+                (_, None) => None,
+            };
+            let Some(item) = item else { continue };
+
+            match sub.queue.push(item, self.config.overflow) {
+                Ok(0) => {}
+                Ok(discarded) => {
+                    dropped += discarded;
+                    warn!(subscriber = sub.id, policy = ?self.config.overflow, "Buffer full, discarding messages");
+                }
+                Err(PushError::Full) if single => fatal = true,
+                Err(PushError::Full) => {
+                    warn!(subscriber = sub.id, "Buffer full, closing subscriber");
+                    sub.queue.lock().overflowed = true;
+                    closed.push(idx);
+                }
+                Err(PushError::Detached) => {
+                    fatal |= single;
+                    closed.push(idx);
+                }
+            }
+        }
+
+        for idx in closed.into_iter().rev() {
+            subs.remove(idx);
+        }
+
+        if fatal {
+            Err(())
+        } else {
+            Ok(dropped)
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<SubscriberSlot>> {
+        self.subscribers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Stream of WebSocket batches for one subscriber.
+///
+/// Returned by [`WsHandle::subscribe_stream`](crate::ws::WsHandle::subscribe_stream).
+/// Each subscriber has its own bounded queue and lag counters, so a slow
+/// consumer does not hold back the others.
+pub struct WsSubscriberStream {
+    id: u64,
+    queue: Arc<SubscriberQueue>,
+}
+
+impl WsSubscriberStream {
+    /// Get the subscriber ID (unique per connection).
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Number of batches dropped because this subscriber lagged.
+    pub fn dropped_count(&self) -> u64 {
+        self.queue.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Number of quote and aggregate events replaced by newer updates under
+    /// [`OverflowPolicy::ConflateLatest`].
+    pub fn conflated_count(&self) -> u64 {
+        self.queue.conflated.load(Ordering::Relaxed)
+    }
+
+    /// Number of batches waiting in this subscriber's queue.
+    pub fn queued(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+impl std::fmt::Debug for WsSubscriberStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WsSubscriberStream")
+            .field("id", &self.id)
+            .field("queued", &self.queued())
+            .field("dropped_count", &self.dropped_count())
+            .field("conflated_count", &self.conflated_count())
+            .finish()
+    }
+}
+
+impl Stream for WsSubscriberStream {
+    type Item = EventResult;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut inner = self.queue.lock();
+        if let Some(batch) = inner.batches.pop_front() {
+            return Poll::Ready(Some(Ok(batch)));
+        }
+        if inner.closed {
+            if std::mem::take(&mut inner.overflowed) {
+                return Poll::Ready(Some(Err(MassiveError::Ws(Box::new(
+                    WsError::BackpressureOverflow,
+                )))));
+            }
+            return Poll::Ready(None);
+        }
+        match &inner.waker {
+            Some(waker) if waker.will_wake(cx.waker()) => {}
+            _ => inner.waker = Some(cx.waker().clone()),
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for WsSubscriberStream {
+    fn drop(&mut self) {
+        let mut inner = self.queue.lock();
+        inner.detached = true;
+        inner.batches.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use tokio::time::Instant;
+
+    fn batch() -> WsMessageBatch {
+        WsMessageBatch {
+            events: Vec::new(),
+            received_at: Instant::now(),
+            latency_hint_ns: None,
+            backfilled: false,
+        }
+    }
+
+    fn config(fanout: FanoutMode, overflow: OverflowPolicy) -> DispatchConfig {
+        DispatchConfig {
+            capacity: 1,
+            overflow,
+            fanout,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_delivers_to_all() {
+        let dispatcher = Dispatcher::new(config(FanoutMode::Broadcast, OverflowPolicy::DropNewest));
+        let mut a = dispatcher.add_subscriber();
+        let mut b = dispatcher.add_subscriber();
+        assert_ne!(a.id(), b.id());
+
+        dispatcher.dispatch(batch()).unwrap();
+        assert!(a.next().await.unwrap().is_ok());
+        assert!(b.next().await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_lag_is_per_subscriber() {
+        let dispatcher = Dispatcher::new(config(FanoutMode::Broadcast, OverflowPolicy::DropNewest));
+        let slow = dispatcher.add_subscriber();
+        let mut fast = dispatcher.add_subscriber();
+
+        dispatcher.dispatch(batch()).unwrap();
+        fast.next().await.unwrap().unwrap();
+        assert_eq!(dispatcher.queue_depth(), 1);
+        dispatcher.dispatch(batch()).unwrap();
+
+        assert_eq!(slow.dropped_count(), 1);
+        assert_eq!(slow.queued(), 1);
+        assert_eq!(fast.dropped_count(), 0);
+        assert_eq!(dispatcher.dropped_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_error_and_close_only_closes_laggard() {
+        let dispatcher =
+            Dispatcher::new(config(FanoutMode::Broadcast, OverflowPolicy::ErrorAndClose));
+        let mut slow = dispatcher.add_subscriber();
+        let mut fast = dispatcher.add_subscriber();
+
+        dispatcher.dispatch(batch()).unwrap();
+        fast.next().await.unwrap().unwrap();
+        assert!(dispatcher.dispatch(batch()).is_ok());
+        assert_eq!(dispatcher.subscriber_count(), 1);
+
+        // The laggard drains its queue, then sees the overflow error
+        assert!(slow.next().await.unwrap().is_ok());
+        assert!(matches!(slow.next().await, Some(Err(MassiveError::Ws(_)))));
+        assert!(slow.next().await.is_none());
+
+        assert!(fast.next().await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_filtered_subscriber() {
+        use crate::ws::models::events::parse_ws_message;
+
+        let dispatcher = Dispatcher::new(config(FanoutMode::Broadcast, OverflowPolicy::DropNewest));
+        let mut aapl = dispatcher.add_filtered_subscriber(Some(EventFilter::from("T.AAPL")));
+        let mut all = dispatcher.add_subscriber();
+
+        let json = r#"[{"ev":"T","sym":"AAPL","x":4,"i":"1","z":3,"p":150.0,"s":100,"t":1,"q":1},{"ev":"T","sym":"MSFT","x":4,"i":"2","z":3,"p":350.0,"s":100,"t":1,"q":2}]"#;
+        let mut b = batch();
+        b.events = parse_ws_message(json).unwrap().events;
+        dispatcher.dispatch(b).unwrap();
+
+        let filtered = aapl.next().await.unwrap().unwrap();
+        assert_eq!(filtered.events.len(), 1);
+        assert_eq!(filtered.events[0].symbol(), Some("AAPL"));
+        assert_eq!(all.next().await.unwrap().unwrap().events.len(), 2);
+
+        // Batches without matches are not delivered to the filtered stream
+        let mut b = batch();
+        b.events = parse_ws_message(json).unwrap().events.split_off(1);
+        dispatcher.dispatch(b).unwrap();
+        assert_eq!(aapl.queued(), 0);
+        assert_eq!(all.queued(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest_evicts_oldest_batch() {
+        use crate::ws::models::events::parse_ws_message;
+
+        let dispatcher = Dispatcher::new(config(
+            FanoutMode::SingleConsumer,
+            OverflowPolicy::DropOldest,
+        ));
+        let mut stream = dispatcher.add_subscriber();
+
+        let mut old = batch();
+        old.events = parse_ws_message(
+            r#"[{"ev":"T","sym":"OLD","x":4,"i":"1","z":3,"p":1.0,"s":1,"t":1,"q":1}]"#,
+        )
+        .unwrap()
+        .events;
+        let mut new = batch();
+        new.events = parse_ws_message(
+            r#"[{"ev":"T","sym":"NEW","x":4,"i":"2","z":3,"p":1.0,"s":1,"t":2,"q":2}]"#,
+        )
+        .unwrap()
+        .events;
+
+        assert_eq!(dispatcher.dispatch(old), Ok(0));
+        assert_eq!(dispatcher.dispatch(new), Ok(1));
+        assert_eq!(stream.dropped_count(), 1);
+        assert_eq!(stream.queued(), 1);
+
+        let received = stream.next().await.unwrap().unwrap();
+        assert_eq!(received.events[0].symbol(), Some("NEW"));
+    }
+
+    #[tokio::test]
+    async fn test_conflate_latest_keeps_trades() {
+        use crate::ws::models::events::parse_ws_message;
+
+        let dispatcher = Dispatcher::new(config(
+            FanoutMode::SingleConsumer,
+            OverflowPolicy::ConflateLatest,
+        ));
+        let mut stream = dispatcher.add_subscriber();
+
+        let first = r#"[{"ev":"Q","sym":"AAPL","bx":4,"bp":150.0,"bs":1,"ax":4,"ap":150.1,"as":1,"t":1,"q":1},{"ev":"T","sym":"AAPL","x":4,"i":"1","z":3,"p":150.0,"s":1,"t":1,"q":2},{"ev":"Q","sym":"MSFT","bx":4,"bp":350.0,"bs":1,"ax":4,"ap":350.1,"as":1,"t":1,"q":3}]"#;
+        let second = r#"[{"ev":"Q","sym":"AAPL","bx":4,"bp":150.2,"bs":1,"ax":4,"ap":150.3,"as":1,"t":2,"q":4},{"ev":"T","sym":"AAPL","x":4,"i":"2","z":3,"p":150.2,"s":1,"t":2,"q":5},{"ev":"Q","sym":"AAPL","bx":4,"bp":150.4,"bs":1,"ax":4,"ap":150.5,"as":1,"t":3,"q":6}]"#;
+        let mut b = batch();
+        b.events = parse_ws_message(first).unwrap().events;
+        dispatcher.dispatch(b).unwrap();
+        let mut b = batch();
+        b.events = parse_ws_message(second).unwrap().events;
+
+        // Both older AAPL quotes are superseded by the last one
+        assert_eq!(dispatcher.dispatch(b), Ok(2));
+        assert_eq!(stream.conflated_count(), 2);
+        assert_eq!(dispatcher.conflated_count(), 2);
+        assert_eq!(stream.dropped_count(), 0);
+        assert_eq!(stream.queued(), 1);
+
+        let received = stream.next().await.unwrap().unwrap();
+        let types: Vec<_> = received
+            .events
+            .iter()
+            .map(|e| (e.event_type(), e.symbol().unwrap()))
+            .collect();
+        assert_eq!(
+            types,
+            [("T", "AAPL"), ("Q", "MSFT"), ("T", "AAPL"), ("Q", "AAPL")]
+        );
+        match &received.events[3] {
+            WsEvent::Quote(q) => assert_eq!(q.bp, crate::models::price::px(150.4)),
+            other => panic!("Expected quote, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conflate_latest_merges_updates() {
+        use crate::ws::models::events::parse_ws_message;
+
+        let dispatcher = Dispatcher::new(config(
+            FanoutMode::SingleConsumer,
+            OverflowPolicy::ConflateLatest,
+        ));
+        let mut stream = dispatcher.add_subscriber();
+
+        let first = r#"[{"ev":"Q","sym":"AAPL","bx":4,"bp":150.0,"bs":1,"ax":4,"ap":150.1,"as":1,"t":1,"q":1},{"ev":"T","sym":"AAPL","x":4,"i":"1","z":3,"p":150.0,"s":1,"t":1,"q":2},{"ev":"Q","sym":"MSFT","bx":4,"bp":350.0,"bs":1,"ax":4,"ap":350.1,"as":1,"t":1,"q":3}]"#;
+        let second = r#"[{"ev":"Q","sym":"AAPL","bx":4,"bp":150.2,"bs":1,"ax":4,"ap":150.3,"as":1,"t":2,"q":4},{"ev":"Q","sym":"AAPL","bx":4,"bp":150.4,"bs":1,"ax":4,"ap":150.5,"as":1,"t":3,"q":6}]"#;
+        let mut b = batch();
+        b.events = parse_ws_message(first).unwrap().events;
+        dispatcher.dispatch(b).unwrap();
+        for _ in 0..3 {
+            let mut b = batch();
+            b.events = parse_ws_message(second).unwrap().events;
+            dispatcher.dispatch(b).unwrap();
+        }
+
+        // Each dispatch supersedes the previous AAPL quotes, so the merged
+        // batch does not grow
+        assert_eq!(stream.conflated_count(), 6);
+        assert_eq!(stream.dropped_count(), 0);
+        assert_eq!(stream.queued(), 1);
+
+        let received = stream.next().await.unwrap().unwrap();
+        let types: Vec<_> = received
+            .events
+            .iter()
+            .map(|e| (e.event_type(), e.symbol().unwrap()))
+            .collect();
+        assert_eq!(types, [("T", "AAPL"), ("Q", "MSFT"), ("Q", "AAPL")]);
+    }
+
+    #[tokio::test]
+    async fn test_conflate_latest_never_drops_trades() {
+        use crate::ws::models::events::parse_ws_message;
+
+        let dispatcher = Dispatcher::new(config(
+            FanoutMode::SingleConsumer,
+            OverflowPolicy::ConflateLatest,
+        ));
+        let mut stream = dispatcher.add_subscriber();
+
+        // The consumer lags behind 50 batches, each with a quote and a trade
+        for q in 0..50u64 {
+            let json = format!(
+                r#"[{{"ev":"Q","sym":"AAPL","bx":4,"bp":150.0,"bs":1,"ax":4,"ap":150.1,"as":1,"t":{q},"q":{q}}},{{"ev":"T","sym":"AAPL","x":4,"i":"{q}","z":3,"p":150.0,"s":1,"t":{q},"q":{q}}}]"#
+            );
+            let mut b = batch();
+            b.events = parse_ws_message(&json).unwrap().events;
+            dispatcher.dispatch(b).unwrap();
+        }
+        assert_eq!(stream.dropped_count(), 0);
+        assert_eq!(stream.conflated_count(), 49);
+
+        let received = stream.next().await.unwrap().unwrap();
+        let trades: Vec<u64> = received
+            .events
+            .iter()
+            .filter_map(|e| match e {
+                WsEvent::Trade(t) => Some(t.q),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(trades, (0..50).collect::<Vec<_>>());
+        // Only the latest quote is left
+        let quotes: Vec<i64> = received
+            .events
+            .iter()
+            .filter_map(|e| match e {
+                WsEvent::Quote(q) => Some(q.t),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(quotes, vec![49]);
+    }
+
+    #[test]
+    fn test_conflate_removes_emptied_batches() {
+        use crate::ws::models::events::parse_ws_message;
+
+        let quote = r#"[{"ev":"Q","sym":"AAPL","bx":4,"bp":150.0,"bs":1,"ax":4,"ap":150.1,"as":1,"t":1,"q":1}]"#;
+        let mut queue = VecDeque::new();
+        let mut b = batch();
+        b.events = parse_ws_message(quote).unwrap().events;
+        queue.push_back(b);
+        queue.push_back(batch());
+
+        let mut incoming = batch();
+        incoming.events = parse_ws_message(quote).unwrap().events;
+        assert_eq!(conflate(&mut queue, &mut incoming), 1);
+        // The emptied batch is gone; batches that arrived empty are kept
+        assert_eq!(queue.len(), 1);
+        assert_eq!(incoming.events.len(), 1);
+    }
+
+    #[test]
+    fn test_broadcast_removes_dropped_subscribers() {
+        let dispatcher = Dispatcher::new(config(FanoutMode::Broadcast, OverflowPolicy::DropNewest));
+        let keep = dispatcher.add_subscriber();
+        drop(dispatcher.add_subscriber());
+
+        dispatcher.dispatch(batch()).unwrap();
+        assert_eq!(dispatcher.subscriber_count(), 1);
+        assert_eq!(keep.queued(), 1);
+    }
+
+    #[test]
+    fn test_single_consumer_overflow_is_fatal() {
+        let dispatcher = Dispatcher::new(config(
+            FanoutMode::SingleConsumer,
+            OverflowPolicy::ErrorAndClose,
+        ));
+        let _stream = dispatcher.add_subscriber();
+
+        assert!(dispatcher.dispatch(batch()).is_ok());
+        assert!(dispatcher.dispatch(batch()).is_err());
+    }
+}
@@ -0,0 +1,249 @@
+//! Topic filters for routing events to subscribers.
+//!
+//! An [`EventFilter`] selects events by the same `{type}.{symbol}` patterns
+//! used for [`Subscription`]s, so a consumer can receive only the events it
+//! cares about instead of filtering the whole feed itself.
+
+use crate::ws::client::WsMessageBatch;
+use crate::ws::models::events::WsEvent;
+use crate::ws::protocol::Subscription;
+use smol_str::SmolStr;
+
+/// Filter selecting events by topic pattern.
+///
+/// Patterns follow the subscription format `{type}.{symbol}`:
+///
+/// - `T.AAPL` - trades for AAPL
+/// - `Q.*` - all quotes
+/// - `T.O:*` - all options trades (symbol prefix match)
+/// - `*.AAPL` - every event type for AAPL
+/// - `XL2.BTC-USD` - crypto L2 book for BTC-USD
+///
+/// An event matches the filter if it matches any of its patterns.
+///
+/// # Example
+///
+/// ```
+/// use massive_rs::ws::{EventFilter, Subscription};
+///
+/// let filter = EventFilter::new()
+///     .topic("T.AAPL")
+///     .topic(Subscription::all_quotes());
+/// assert_eq!(filter.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    patterns: Vec<TopicPattern>,
+}
+
+/// A single parsed `{type}.{symbol}` pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TopicPattern {
+    /// Event type, or `None` for any type
+    ev: Option<SmolStr>,
+    /// Symbol pattern
+    sym: SymbolPattern,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SymbolPattern {
+    Any,
+    Exact(SmolStr),
+    Prefix(SmolStr),
+}
+
+impl EventFilter {
+    /// Create an empty filter (matches nothing).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a topic pattern.
+    pub fn topic(mut self, pattern: impl AsRef<str>) -> Self {
+        self.patterns.push(TopicPattern::parse(pattern.as_ref()));
+        self
+    }
+
+    /// Number of patterns in this filter.
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Check if the filter has no patterns.
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Check if an event matches any pattern.
+    pub fn matches(&self, event: &WsEvent) -> bool {
+        self.patterns.iter().any(|p| p.matches(event))
+    }
+
+    /// Build a batch holding only the matching events.
+    ///
+    /// Returns `None` if no event in the batch matches.
+    pub(crate) fn apply(&self, batch: &WsMessageBatch) -> Option<WsMessageBatch> {
+        let events: Vec<WsEvent> = batch
+            .events
+            .iter()
+            .filter(|e| self.matches(e))
+            .cloned()
+            .collect();
+
+        if events.is_empty() {
+            return None;
+        }
+
+        Some(WsMessageBatch {
+            events,
+            received_at: batch.received_at,
+            latency_hint_ns: batch.latency_hint_ns,
+            backfilled: batch.backfilled,
+        })
+    }
+}
+
+impl TopicPattern {
+    fn parse(pattern: &str) -> Self {
+        let (ev, sym) = pattern.split_once('.').unwrap_or((pattern, "*"));
+
+        let ev = match ev {
+            "*" | "" => None,
+            ev => Some(SmolStr::new(ev)),
+        };
+
+        let sym = match sym {
+            "*" | "" => SymbolPattern::Any,
+            s => match s.strip_suffix('*') {
+                Some(prefix) => SymbolPattern::Prefix(SmolStr::new(prefix)),
+                None => SymbolPattern::Exact(SmolStr::new(s)),
+            },
+        };
+
+        Self { ev, sym }
+    }
+
+    fn matches(&self, event: &WsEvent) -> bool {
+        if let Some(ev) = &self.ev {
+            if ev != event.event_type() {
+                return false;
+            }
+        }
+
+        match &self.sym {
+            SymbolPattern::Any => true,
+            SymbolPattern::Exact(s) => event.symbol().is_some_and(|sym| symbol_eq(sym, s)),
+            SymbolPattern::Prefix(p) => event.symbol().is_some_and(|sym| sym.starts_with(&**p)),
+        }
+    }
+}
+
+/// Compare an event symbol to a pattern symbol.
+///
+/// Forex events carry pairs as `EUR/USD` while topics use `EURUSD`.
+fn symbol_eq(sym: &str, pattern: &str) -> bool {
+    sym == pattern
+        || (sym.len() == pattern.len() + 1
+            && sym.contains('/')
+            && sym.chars().filter(|&c| c != '/').eq(pattern.chars()))
+}
+
+impl From<&str> for EventFilter {
+    fn from(pattern: &str) -> Self {
+        Self::new().topic(pattern)
+    }
+}
+
+impl From<Subscription> for EventFilter {
+    fn from(sub: Subscription) -> Self {
+        Self::new().topic(sub)
+    }
+}
+
+impl From<&[Subscription]> for EventFilter {
+    fn from(subs: &[Subscription]) -> Self {
+        subs.iter().fold(Self::new(), |f, s| f.topic(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ws::models::events::parse_ws_message;
+
+    fn event(json: &str) -> WsEvent {
+        parse_ws_message(json).unwrap().events.remove(0)
+    }
+
+    fn trade(sym: &str) -> WsEvent {
+        event(&format!(
+            r#"{{"ev":"T","sym":"{}","x":4,"i":"1","z":3,"p":150.0,"s":100,"t":1703001234567,"q":1}}"#,
+            sym
+        ))
+    }
+
+    fn quote(sym: &str) -> WsEvent {
+        event(&format!(
+            r#"{{"ev":"Q","sym":"{}","bx":4,"bp":150.0,"bs":100,"ax":4,"ap":150.1,"as":200,"t":1703001234567}}"#,
+            sym
+        ))
+    }
+
+    #[test]
+    fn test_exact_topic() {
+        let filter = EventFilter::from("T.AAPL");
+        assert!(filter.matches(&trade("AAPL")));
+        assert!(!filter.matches(&trade("MSFT")));
+        assert!(!filter.matches(&quote("AAPL")));
+    }
+
+    #[test]
+    fn test_wildcards() {
+        let all_quotes = EventFilter::from(Subscription::all_quotes());
+        assert!(all_quotes.matches(&quote("AAPL")));
+        assert!(!all_quotes.matches(&trade("AAPL")));
+
+        let all_aapl = EventFilter::from("*.AAPL");
+        assert!(all_aapl.matches(&quote("AAPL")));
+        assert!(all_aapl.matches(&trade("AAPL")));
+        assert!(!all_aapl.matches(&trade("MSFT")));
+
+        let options = EventFilter::from("T.O:*");
+        assert!(options.matches(&trade("O:AAPL251219C00200000")));
+        assert!(!options.matches(&trade("AAPL")));
+    }
+
+    #[test]
+    fn test_crypto_and_forex_pairs() {
+        let l2 = event(r#"{"ev":"XL2","pair":"BTC-USD","b":[],"a":[],"t":1703001234567}"#);
+        assert!(EventFilter::from(Subscription::crypto_l2("BTC", "USD")).matches(&l2));
+
+        let fx = event(r#"{"ev":"C","p":"EUR/USD","a":1.1,"b":1.09,"t":1703001234567}"#);
+        assert!(EventFilter::from(Subscription::forex_quote("EUR", "USD")).matches(&fx));
+        assert!(!EventFilter::from("C.EURGBP").matches(&fx));
+    }
+
+    #[test]
+    fn test_status_excluded_unless_requested() {
+        let status = event(r#"{"ev":"status","status":"connected"}"#);
+        assert!(!EventFilter::from("*.AAPL").matches(&status));
+        assert!(EventFilter::from("status").matches(&status));
+    }
+
+    #[test]
+    fn test_apply_batch() {
+        let batch = WsMessageBatch {
+            events: vec![trade("AAPL"), trade("MSFT"), quote("AAPL")],
+            received_at: tokio::time::Instant::now(),
+            latency_hint_ns: None,
+            backfilled: false,
+        };
+
+        let subs = [Subscription::trade("AAPL"), Subscription::quote("AAPL")];
+        let filtered = EventFilter::from(&subs[..]).apply(&batch).unwrap();
+        assert_eq!(filtered.events.len(), 2);
+
+        assert!(EventFilter::from("T.GOOG").apply(&batch).is_none());
+        assert!(EventFilter::new().apply(&batch).is_none());
+    }
+}
@@ -0,0 +1,408 @@
+//! Session journals.
+//!
+//! A journal is a sequence of gzip-compressed JSON-lines files. Each line
+//! holds one raw text frame and the wall-clock time it was received:
+//!
+//! ```text
+//! {"ts":1703001234567891234,"frame":"[{\"ev\":\"T\",\"sym\":\"AAPL\",...}]"}
+//! ```
+//!
+//! [`JournalWriter`] appends frames from the IO task on a background thread
+//! and rotates files by size. [`read_journal`] streams frames back for
+//! [`ReplayClient`](crate::ws::ReplayClient).
+
+use crate::config::RecorderConfig;
+use crate::error::MassiveError;
+use crate::metrics::ClientStats;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc as std_mpsc;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// File name extension of journal files.
+const JOURNAL_EXTENSION: &str = ".jsonl.gz";
+
+/// One recorded frame.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct JournalEntry {
+    /// Receive time (Unix nanoseconds)
+    pub ts: i64,
+    /// Raw text frame
+    pub frame: String,
+}
+
+/// Appends frames to a rotating journal on a background thread.
+///
+/// Frames wait in a queue of
+/// [`queue_capacity`](RecorderConfig::queue_capacity) entries; frames that
+/// find it full are dropped and counted, so a slow disk never stalls the IO
+/// task. [`finish`](Self::finish) flushes and closes the current file.
+/// Dropping the writer instead leaves the thread to finish on its own.
+pub(crate) struct JournalWriter {
+    tx: Option<std_mpsc::SyncSender<JournalEntry>>,
+    thread: Option<JoinHandle<()>>,
+    stats: Arc<ClientStats>,
+    /// Frames written and flushed by the thread, read by tests
+    #[cfg_attr(not(test), allow(dead_code))]
+    written: Arc<AtomicU64>,
+}
+
+impl JournalWriter {
+    /// Create the journal directory and first file, and start the writer
+    /// thread.
+    pub fn spawn(config: RecorderConfig, stats: Arc<ClientStats>) -> Result<Self, MassiveError> {
+        std::fs::create_dir_all(&config.dir).map_err(MassiveError::Journal)?;
+        let file = JournalFile::create(&config, 0).map_err(MassiveError::Journal)?;
+        let (tx, rx) = std_mpsc::sync_channel(config.queue_capacity.max(1));
+        let written = Arc::new(AtomicU64::new(0));
+        let thread = std::thread::Builder::new()
+            .name("massive-ws-journal".into())
+            .spawn({
+                let written = written.clone();
+                move || write_loop(config, file, rx, &written)
+            })
+            .map_err(MassiveError::Journal)?;
+        Ok(Self {
+            tx: Some(tx),
+            thread: Some(thread),
+            stats,
+            written,
+        })
+    }
+
+    /// Record a frame received now, or count it as dropped if the queue is
+    /// full.
+    pub fn record(&self, frame: &str) {
+        let ts = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+        if let Some(tx) = &self.tx {
+            let entry = JournalEntry {
+                ts,
+                frame: frame.to_string(),
+            };
+            match tx.try_send(entry) {
+                Ok(()) => {}
+                Err(std_mpsc::TrySendError::Full(_)) => self.stats.inc_journal_dropped(),
+                // The thread only exits early after a write error it has logged
+                Err(std_mpsc::TrySendError::Disconnected(_)) => {}
+            }
+        }
+    }
+
+    /// Number of frames written and flushed so far.
+    #[cfg(test)]
+    fn written(&self) -> u64 {
+        self.written.load(Ordering::Acquire)
+    }
+
+    /// Write the queued frames, close the current file, and wait for the
+    /// thread without blocking the runtime.
+    pub async fn finish(mut self) {
+        self.tx.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = tokio::task::spawn_blocking(move || thread.join()).await;
+        }
+    }
+}
+
+impl Drop for JournalWriter {
+    fn drop(&mut self) {
+        // Closing the queue ends the thread once it has written the rest.
+        // It is detached rather than joined, as this may run on the runtime.
+        self.tx.take();
+        self.thread.take();
+    }
+}
+
+/// Journal file being written.
+struct JournalFile {
+    path: PathBuf,
+    encoder: GzEncoder<BufWriter<File>>,
+    bytes: u64,
+}
+
+impl JournalFile {
+    /// Create file number `index`, named so that files sort chronologically.
+    fn create(config: &RecorderConfig, index: u32) -> std::io::Result<Self> {
+        let name = format!(
+            "{}-{}-{:04}{}",
+            config.prefix,
+            chrono::Utc::now().format("%Y%m%dT%H%M%S%3f"),
+            index,
+            JOURNAL_EXTENSION
+        );
+        let path = config.dir.join(name);
+        let file = File::create(&path)?;
+        Ok(Self {
+            path,
+            encoder: GzEncoder::new(BufWriter::new(file), Compression::default()),
+            bytes: 0,
+        })
+    }
+
+    fn write(&mut self, entry: &JournalEntry) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        self.encoder.write_all(&line)?;
+        self.bytes += line.len() as u64;
+        Ok(())
+    }
+
+    fn finish(self) -> std::io::Result<()> {
+        self.encoder.finish()?.flush()
+    }
+}
+
+fn write_loop(
+    config: RecorderConfig,
+    file: JournalFile,
+    rx: std_mpsc::Receiver<JournalEntry>,
+    written: &AtomicU64,
+) {
+    let mut file = Some(file);
+    let mut index = 0;
+    let result = (|| -> std::io::Result<()> {
+        while let Ok(entry) = rx.recv() {
+            // Start the next file only once there is something to put in
+            // it, so pruning never keeps an empty file
+            if file.is_none() {
+                index += 1;
+                file = Some(JournalFile::create(&config, index)?);
+                prune(&config)?;
+            }
+            let current = file.as_mut().expect("journal file open");
+            current.write(&entry)?;
+            let mut count = 1;
+            // Write whatever else is queued before flushing
+            while let Ok(entry) = rx.try_recv() {
+                current.write(&entry)?;
+                count += 1;
+            }
+            current.encoder.flush()?;
+
+            if current.bytes >= config.max_file_bytes {
+                file.take().expect("journal file open").finish()?;
+            }
+            written.fetch_add(count, Ordering::Release);
+        }
+        Ok(())
+    })();
+
+    let result = result.and_then(|()| file.take().map_or(Ok(()), JournalFile::finish));
+    if let Err(e) = result {
+        let path = file.as_ref().map(|f| f.path.display().to_string());
+        warn!(error = %e, ?path, "Journal write failed, recording stopped");
+    }
+}
+
+/// Delete the oldest journal files beyond `max_files`.
+fn prune(config: &RecorderConfig) -> std::io::Result<()> {
+    let Some(max_files) = config.max_files else {
+        return Ok(());
+    };
+    let prefix = format!("{}-", config.prefix);
+    let mut files: Vec<PathBuf> = journal_files(&config.dir)?
+        .into_iter()
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(&prefix))
+        })
+        .collect();
+    let excess = files.len().saturating_sub(max_files.max(1));
+    for path in files.drain(..excess) {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Journal files in `dir`, in chronological order.
+fn journal_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.ends_with(JOURNAL_EXTENSION))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Resolve a journal path: a single file, or a directory of journal files.
+pub(crate) fn resolve(path: &Path) -> Result<Vec<PathBuf>, MassiveError> {
+    if path.is_dir() {
+        let files = journal_files(path).map_err(MassiveError::Journal)?;
+        if files.is_empty() {
+            return Err(MassiveError::InvalidArgument(
+                "journal directory contains no journal files",
+            ));
+        }
+        Ok(files)
+    } else if path.is_file() {
+        Ok(vec![path.to_path_buf()])
+    } else {
+        Err(MassiveError::Journal(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("journal not found: {}", path.display()),
+        )))
+    }
+}
+
+/// Read journal files in order on a background thread.
+///
+/// A truncated final entry, as left by a crashed recorder, ends its file
+/// with a warning.
+pub(crate) fn read_journal(files: Vec<PathBuf>) -> mpsc::Receiver<JournalEntry> {
+    let (tx, rx) = mpsc::channel(1024);
+    std::thread::spawn(move || {
+        for path in files {
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                Err(e) => {
+                    warn!(path = %path.display(), error = %e, "Failed to open journal file");
+                    continue;
+                }
+            };
+            for line in BufReader::new(MultiGzDecoder::new(BufReader::new(file))).lines() {
+                let entry = line
+                    .map_err(|e| e.to_string())
+                    .and_then(|l| serde_json::from_str(&l).map_err(|e| e.to_string()));
+                match entry {
+                    Ok(entry) => {
+                        if tx.blocking_send(entry).is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => {
+                        warn!(path = %path.display(), error = %e, "Journal file ended early");
+                        break;
+                    }
+                }
+            }
+        }
+    });
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("massive-journal-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    async fn read_all(path: &Path) -> Vec<JournalEntry> {
+        let mut rx = read_journal(resolve(path).unwrap());
+        let mut entries = Vec::new();
+        while let Some(entry) = rx.recv().await {
+            entries.push(entry);
+        }
+        entries
+    }
+
+    async fn wait_written(writer: &JournalWriter, count: u64) {
+        while writer.written() < count {
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_journal_round_trip_with_rotation() {
+        let dir = temp_dir("rotate");
+        let config = RecorderConfig {
+            max_file_bytes: 1,
+            max_files: Some(2),
+            ..RecorderConfig::new(&dir)
+        };
+
+        let writer = JournalWriter::spawn(config, Arc::new(ClientStats::new())).unwrap();
+        for i in 0..3 {
+            writer.record(&format!(r#"[{{"ev":"status","status":"{i}"}}]"#));
+            // Frames queued together share a file, so write one at a time
+            wait_written(&writer, i + 1).await;
+        }
+        writer.finish().await;
+
+        // Each frame fills a file; the oldest file was pruned
+        let files = journal_files(&dir).unwrap();
+        assert_eq!(files.len(), 2);
+
+        let entries = read_all(&dir).await;
+        let frames: Vec<_> = entries.iter().map(|e| e.frame.as_str()).collect();
+        assert_eq!(
+            frames,
+            vec![
+                r#"[{"ev":"status","status":"1"}]"#,
+                r#"[{"ev":"status","status":"2"}]"#
+            ]
+        );
+        assert!(entries[0].ts <= entries[1].ts);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_truncated_journal_keeps_complete_entries() {
+        let dir = temp_dir("truncated");
+        let writer =
+            JournalWriter::spawn(RecorderConfig::new(&dir), Arc::new(ClientStats::new())).unwrap();
+        writer.record("first");
+        writer.record("second");
+        writer.finish().await;
+
+        let path = journal_files(&dir).unwrap().remove(0);
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() - 8]).unwrap();
+
+        let entries = read_all(&path).await;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].frame, "second");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_drops_frames() {
+        let dir = temp_dir("full");
+        let config = RecorderConfig {
+            queue_capacity: 1,
+            ..RecorderConfig::new(&dir)
+        };
+        let stats = Arc::new(ClientStats::new());
+        let writer = JournalWriter::spawn(config, stats.clone()).unwrap();
+        for i in 0..10_000 {
+            writer.record(&i.to_string());
+        }
+        writer.finish().await;
+
+        // Every frame was either written or counted as dropped
+        let entries = read_all(&dir).await;
+        let dropped = stats.snapshot().journal_dropped;
+        assert!(dropped > 0);
+        assert_eq!(entries.len() as u64 + dropped, 10_000);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_missing_journal() {
+        assert!(matches!(
+            resolve(Path::new("/nonexistent/journal.jsonl.gz")),
+            Err(MassiveError::Journal(_))
+        ));
+    }
+}
@@ -0,0 +1,294 @@
+//! Connection lifecycle events.
+//!
+//! The IO task publishes a [`ConnectionEvent`] whenever the connection
+//! changes in a way consumers may need to react to: reconnects, dropped
+//! sockets, authentication, and subscription changes. Consume them as a
+//! stream with [`WsHandle::lifecycle`](crate::ws::WsHandle::lifecycle) or
+//! register a callback with
+//! [`WsHandle::on_lifecycle`](crate::ws::WsHandle::on_lifecycle).
+
+use crate::ws::protocol::Subscription;
+use futures::Stream;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Capacity of the lifecycle broadcast channel.
+///
+/// These events are rare, so a slow listener only misses events after
+/// falling this far behind.
+const LIFECYCLE_CAPACITY: usize = 64;
+
+/// Why a WebSocket connection was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DisconnectReason {
+    /// Nothing was received for longer than
+    /// [`WsConfig::idle_timeout`](crate::config::WsConfig::idle_timeout).
+    IdleTimeout,
+    /// A keepalive ping was not answered before the next one was due.
+    PongTimeout,
+    /// The server sent a close frame or ended the stream.
+    ServerClose,
+    /// The server rejected the API key.
+    AuthFailed,
+    /// A transport or protocol error.
+    Error,
+    /// The connection was closed through [`WsHandle::close`](crate::ws::WsHandle::close)
+    /// or because every handle was dropped.
+    ClientClose,
+}
+
+impl std::fmt::Display for DisconnectReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            DisconnectReason::IdleTimeout => "idle timeout",
+            DisconnectReason::PongTimeout => "pong timeout",
+            DisconnectReason::ServerClose => "server close",
+            DisconnectReason::AuthFailed => "authentication failed",
+            DisconnectReason::Error => "error",
+            DisconnectReason::ClientClose => "client close",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Connection lifecycle event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConnectionEvent {
+    /// The WebSocket handshake completed and authentication was sent.
+    Connected,
+    /// The server accepted the API key.
+    Authenticated,
+    /// Topics were subscribed through
+    /// [`WsHandle::subscribe`](crate::ws::WsHandle::subscribe).
+    Subscribed {
+        /// Topics added
+        topics: Vec<Subscription>,
+    },
+    /// Existing subscriptions were restored after a reconnect, once the
+    /// server answered for each of them. Topics it rejected or did not
+    /// confirm in time are dropped from
+    /// [`WsHandle::subscriptions`](crate::ws::WsHandle::subscriptions).
+    Resubscribed {
+        /// Topics the server accepted
+        topics: Vec<Subscription>,
+    },
+    /// The socket was closed. Unless the reason is
+    /// [`DisconnectReason::ClientClose`], the client reconnects according to
+    /// [`ReconnectConfig`](crate::config::ReconnectConfig).
+    Disconnected {
+        /// Why the connection was closed
+        reason: DisconnectReason,
+    },
+    /// Events missed during a reconnect were recovered over REST and
+    /// delivered on the event stream. Only published when
+    /// [`WsConfig::backfill`](crate::config::WsConfig::backfill) is set.
+    Backfilled {
+        /// Number of events recovered
+        events: usize,
+        /// Topics whose gap could not be fetched
+        failed: Vec<Subscription>,
+        /// Topics whose gap held more than
+        /// [`BackfillConfig::max_events`](crate::config::BackfillConfig::max_events)
+        /// events; only the oldest were recovered
+        truncated: Vec<Subscription>,
+    },
+    /// A reconnect attempt will start after `delay`.
+    Reconnecting {
+        /// Consecutive attempt number, starting at 1 after each
+        /// authenticated session
+        attempt: u32,
+        /// Backoff delay before connecting
+        delay: Duration,
+    },
+    /// Reconnection is disabled or exhausted, or the server rejected the
+    /// credentials on a reconnect; the connection is closed for good.
+    GaveUp {
+        /// Reconnect attempts made
+        attempts: u32,
+    },
+}
+
+/// Stream of connection lifecycle events.
+pub type LifecycleStream = Pin<Box<dyn Stream<Item = ConnectionEvent> + Send>>;
+
+/// Callback registered on an [`EventHub`], such as through
+/// [`WsHandle::on_lifecycle`](crate::ws::WsHandle::on_lifecycle).
+type Callback<E> = Arc<dyn Fn(&E) + Send + Sync>;
+
+/// Event type published through an [`EventHub`].
+pub(crate) trait HubEvent: Clone + Send + 'static {
+    /// Events a stream may fall behind by before it misses some.
+    const CAPACITY: usize;
+
+    /// Event telling a stream that it missed `count` events, or `None` to
+    /// skip them silently.
+    fn lagged(_count: u64) -> Option<Self> {
+        None
+    }
+}
+
+impl HubEvent for ConnectionEvent {
+    const CAPACITY: usize = LIFECYCLE_CAPACITY;
+}
+
+/// Publishes events to streams and callbacks.
+///
+/// Shared between the IO task and every handle. Closing the hub ends all
+/// streams.
+pub(crate) struct EventHub<E> {
+    tx: Mutex<Option<broadcast::Sender<E>>>,
+    callbacks: Mutex<Vec<Callback<E>>>,
+}
+
+/// Hub for connection lifecycle events.
+pub(crate) type LifecycleHub = EventHub<ConnectionEvent>;
+
+impl<E: HubEvent> EventHub<E> {
+    pub fn new() -> Self {
+        Self {
+            tx: Mutex::new(Some(broadcast::channel(E::CAPACITY).0)),
+            callbacks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Deliver an event to every callback and stream.
+    ///
+    /// Callbacks run on the caller's thread, outside the hub's locks, so
+    /// they may use the hub themselves. They delay the publisher, so they
+    /// must not block.
+    pub fn publish(&self, event: E) {
+        let callbacks = lock(&self.callbacks).clone();
+        for callback in &callbacks {
+            callback(&event);
+        }
+        if let Some(tx) = lock(&self.tx).as_ref() {
+            // No listeners is fine
+            let _ = tx.send(event);
+        }
+    }
+
+    /// Stream events published from now on.
+    ///
+    /// Returns an empty stream if the hub is closed.
+    pub fn subscribe(&self) -> Pin<Box<dyn Stream<Item = E> + Send>> {
+        match lock(&self.tx).as_ref() {
+            Some(tx) => event_stream(tx.subscribe()),
+            None => Box::pin(futures::stream::empty()),
+        }
+    }
+
+    /// Register a callback for events published from now on.
+    pub fn add_callback(&self, callback: Callback<E>) {
+        lock(&self.callbacks).push(callback);
+    }
+
+    /// Stop publishing, ending every stream and releasing callbacks.
+    pub fn close(&self) {
+        lock(&self.tx).take();
+        lock(&self.callbacks).clear();
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Turn a broadcast receiver into a stream.
+///
+/// Events missed because the listener lagged are replaced by
+/// [`HubEvent::lagged`], or skipped.
+fn event_stream<E: HubEvent>(rx: broadcast::Receiver<E>) -> Pin<Box<dyn Stream<Item = E> + Send>> {
+    Box::pin(futures::stream::unfold(rx, |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => return Some((event, rx)),
+                Err(broadcast::error::RecvError::Lagged(count)) => {
+                    if let Some(event) = E::lagged(count) {
+                        return Some((event, rx));
+                    }
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_lifecycle_stream_ends_when_closed() {
+        let hub = LifecycleHub::new();
+        let mut stream = hub.subscribe();
+
+        hub.publish(ConnectionEvent::Disconnected {
+            reason: DisconnectReason::PongTimeout,
+        });
+        hub.close();
+
+        assert_eq!(
+            stream.next().await,
+            Some(ConnectionEvent::Disconnected {
+                reason: DisconnectReason::PongTimeout
+            })
+        );
+        assert_eq!(stream.next().await, None);
+        assert_eq!(hub.subscribe().next().await, None);
+    }
+
+    #[test]
+    fn test_lifecycle_callbacks() {
+        let hub = LifecycleHub::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        let seen = count.clone();
+        hub.add_callback(Arc::new(move |event| {
+            if matches!(event, ConnectionEvent::Reconnecting { attempt: 2, .. }) {
+                seen.fetch_add(1, Ordering::Relaxed);
+            }
+        }));
+
+        hub.publish(ConnectionEvent::Reconnecting {
+            attempt: 2,
+            delay: Duration::from_secs(1),
+        });
+        hub.close();
+        hub.publish(ConnectionEvent::GaveUp { attempts: 2 });
+
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_callback_can_use_hub() {
+        let hub = Arc::new(LifecycleHub::new());
+        let count = Arc::new(AtomicUsize::new(0));
+        let (weak, seen) = (Arc::downgrade(&hub), count.clone());
+        hub.add_callback(Arc::new(move |_| {
+            // Would deadlock if callbacks ran under the hub's lock
+            if let Some(hub) = weak.upgrade() {
+                let seen = seen.clone();
+                hub.add_callback(Arc::new(move |_| {
+                    seen.fetch_add(1, Ordering::Relaxed);
+                }));
+            }
+        }));
+
+        hub.publish(ConnectionEvent::GaveUp { attempts: 1 });
+        hub.publish(ConnectionEvent::GaveUp { attempts: 2 });
+
+        // Only the callback added by the first event saw the second
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_disconnect_reason_display() {
+        assert_eq!(DisconnectReason::IdleTimeout.to_string(), "idle timeout");
+        assert_eq!(DisconnectReason::ServerClose.to_string(), "server close");
+    }
+}
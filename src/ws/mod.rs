@@ -4,10 +4,32 @@
 //! via WebSocket connections, with automatic reconnection and
 //! backpressure handling.
 
+mod ack;
+mod backfill;
 mod client;
+mod diagnostics;
+mod dispatch;
+mod filter;
+#[cfg(feature = "record")]
+mod journal;
+mod lifecycle;
 pub mod models;
 mod protocol;
+#[cfg(feature = "record")]
+mod replay;
 
-pub use client::{ConnectionState, WsClient, WsHandle, WsMessageBatch, WsState, WsStats};
+pub use ack::{SubscriptionReport, TopicStatus};
+pub use client::{
+    ConnectionState, WsClient, WsEventStream, WsHandle, WsMessageBatch, WsState, WsStats,
+};
+pub use diagnostics::{Diagnostic, DiagnosticStream};
+pub use dispatch::WsSubscriberStream;
+pub use filter::EventFilter;
+pub use lifecycle::{ConnectionEvent, DisconnectReason, LifecycleStream};
 pub use models::events::WsEvent;
 pub use protocol::Subscription;
+#[cfg(feature = "testing")]
+pub(crate) use protocol::{WsAuthMessage, WsSubscribeMessage};
+#[cfg(feature = "record")]
+#[cfg_attr(docsrs, doc(cfg(feature = "record")))]
+pub use replay::{Pacing, ReplayClient};
@@ -3,82 +3,304 @@
 //! This module defines all event types that can be received from
 //! the Massive WebSocket API.
 
+use crate::config::Market;
+use crate::models::price::{self, Price};
 use crate::util::Symbol;
-use serde::Deserialize;
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{Error as _, IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use serde_json::value::RawValue;
+use smol_str::SmolStr;
 
 /// Unified WebSocket event enum.
 ///
 /// All events received from the WebSocket connection are parsed into
 /// this enum. The event type is determined by the `ev` field in the
 /// JSON message.
-#[derive(Debug, Clone, Deserialize)]
-#[serde(tag = "ev")]
+///
+/// The futures socket reuses the stock event types (`T`, `Q`, `A`, `AM`),
+/// so which variant they map to depends on the [`Market`] the message came
+/// from; see [`parse_ws_message_for`]. Deserializing `WsEvent` directly reads
+/// them as stock events.
+///
+/// `WsEvent` deserializes from `serde_json` only, because
+/// [`WsEvent::Other`] keeps the original JSON.
+#[derive(Debug, Clone)]
 pub enum WsEvent {
     /// Connection/authentication status message.
-    #[serde(rename = "status")]
     Status(WsStatusEvent),
 
     /// Trade event.
-    #[serde(rename = "T")]
     Trade(WsTradeEvent),
 
     /// Quote event (NBBO).
-    #[serde(rename = "Q")]
     Quote(WsQuoteEvent),
 
     /// Second aggregate bar.
-    #[serde(rename = "A")]
     SecondAggregate(WsAggregateEvent),
 
     /// Minute aggregate bar.
-    #[serde(rename = "AM")]
     MinuteAggregate(WsAggregateEvent),
 
     /// Limit Up/Limit Down event.
-    #[serde(rename = "LULD")]
     LimitUpLimitDown(WsLuldEvent),
 
     /// Fair Market Value event.
-    #[serde(rename = "FMV")]
     FairMarketValue(WsFmvEvent),
 
     /// New Order Imbalance event (auctions).
-    #[serde(rename = "NOI")]
     OrderImbalance(WsOrderImbalanceEvent),
 
     /// Index value event.
-    #[serde(rename = "V")]
     IndexValue(WsIndexValueEvent),
 
     /// Crypto trade event.
-    #[serde(rename = "XT")]
     CryptoTrade(WsCryptoTradeEvent),
 
     /// Crypto quote event.
-    #[serde(rename = "XQ")]
     CryptoQuote(WsCryptoQuoteEvent),
 
     /// Crypto aggregate event.
-    #[serde(rename = "XA")]
     CryptoAggregate(WsCryptoAggregateEvent),
 
     /// Crypto L2 book event.
-    #[serde(rename = "XL2")]
     CryptoL2(WsCryptoL2Event),
 
     /// Forex quote event.
-    #[serde(rename = "C")]
     ForexQuote(WsForexQuoteEvent),
 
     /// Forex aggregate event.
-    #[serde(rename = "CA")]
     ForexAggregate(WsForexAggregateEvent),
 
-    /// Unknown event type (forward compatibility).
+    /// Futures trade event.
+    FuturesTrade(WsFuturesTradeEvent),
+
+    /// Futures quote event.
+    FuturesQuote(WsFuturesQuoteEvent),
+
+    /// Futures second aggregate bar.
+    FuturesSecondAggregate(WsFuturesAggregateEvent),
+
+    /// Futures minute aggregate bar.
+    FuturesMinuteAggregate(WsFuturesAggregateEvent),
+
+    /// Event type this version of the crate does not model.
+    ///
+    /// The original JSON is kept, so data from event types added to the API
+    /// can be used before the crate supports them.
+    Other {
+        /// Wire event type (the `ev` field)
+        ev: SmolStr,
+        /// The complete event object as received
+        raw: Box<RawValue>,
+        /// The `sym` or `pair` field, if present
+        symbol: Option<Symbol>,
+    },
+}
+
+impl WsEvent {
+    /// Get the wire event type (the `ev` field), e.g. `"T"` or `"XL2"`.
     ///
-    /// New event types added to the API won't cause parsing failures.
-    #[serde(other)]
-    Unknown,
+    /// For [`WsEvent::Other`], this is the unrecognized type as received.
+    pub fn event_type(&self) -> &str {
+        match self {
+            WsEvent::Status(_) => "status",
+            WsEvent::Trade(_) => "T",
+            WsEvent::Quote(_) => "Q",
+            WsEvent::SecondAggregate(_) => "A",
+            WsEvent::MinuteAggregate(_) => "AM",
+            WsEvent::LimitUpLimitDown(_) => "LULD",
+            WsEvent::FairMarketValue(_) => "FMV",
+            WsEvent::OrderImbalance(_) => "NOI",
+            WsEvent::IndexValue(_) => "V",
+            WsEvent::CryptoTrade(_) => "XT",
+            WsEvent::CryptoQuote(_) => "XQ",
+            WsEvent::CryptoAggregate(_) => "XA",
+            WsEvent::CryptoL2(_) => "XL2",
+            WsEvent::ForexQuote(_) => "C",
+            WsEvent::ForexAggregate(_) => "CA",
+            WsEvent::FuturesTrade(_) => "T",
+            WsEvent::FuturesQuote(_) => "Q",
+            WsEvent::FuturesSecondAggregate(_) => "A",
+            WsEvent::FuturesMinuteAggregate(_) => "AM",
+            WsEvent::Other { ev, .. } => ev,
+        }
+    }
+
+    /// Get the symbol or pair this event refers to.
+    ///
+    /// For [`WsEvent::Other`], this is the `sym` or `pair` field if present.
+    /// Returns `None` for status messages.
+    pub fn symbol(&self) -> Option<&str> {
+        match self {
+            WsEvent::Trade(e) => Some(&e.sym),
+            WsEvent::Quote(e) => Some(&e.sym),
+            WsEvent::SecondAggregate(e) | WsEvent::MinuteAggregate(e) => Some(&e.sym),
+            WsEvent::LimitUpLimitDown(e) => Some(&e.sym),
+            WsEvent::FairMarketValue(e) => Some(&e.sym),
+            WsEvent::OrderImbalance(e) => Some(&e.sym),
+            WsEvent::IndexValue(e) => Some(&e.sym),
+            WsEvent::CryptoTrade(e) => Some(&e.pair),
+            WsEvent::CryptoQuote(e) => Some(&e.pair),
+            WsEvent::CryptoAggregate(e) => Some(&e.pair),
+            WsEvent::CryptoL2(e) => Some(&e.pair),
+            WsEvent::ForexQuote(e) => Some(&e.p),
+            WsEvent::ForexAggregate(e) => Some(&e.pair),
+            WsEvent::FuturesTrade(e) => Some(&e.sym),
+            WsEvent::FuturesQuote(e) => Some(&e.sym),
+            WsEvent::FuturesSecondAggregate(e) | WsEvent::FuturesMinuteAggregate(e) => Some(&e.sym),
+            WsEvent::Other { symbol, .. } => symbol.as_deref(),
+            WsEvent::Status(_) => None,
+        }
+    }
+
+    /// Parse one event object received from `market`.
+    ///
+    /// Unknown event types become [`WsEvent::Other`].
+    ///
+    /// The API sends `ev` first, so the event is read in a single pass.
+    /// Objects with `ev` elsewhere are read twice: once for the tag and once
+    /// for the event.
+    fn from_raw(raw: &RawValue, market: Market) -> Result<Self, serde_json::Error> {
+        let mut de = serde_json::Deserializer::from_str(raw.get());
+        match de.deserialize_map(TaggedVisitor { market })? {
+            Tagged::Known(event) => Ok(event),
+            Tagged::Unknown(ev) => Self::other(ev, raw),
+            Tagged::Unordered => {
+                let tag: EventTag = serde_json::from_str(raw.get())?;
+                let mut de = serde_json::Deserializer::from_str(raw.get());
+                match Self::deserialize_known(market, &tag.ev, &mut de)? {
+                    Some(event) => Ok(event),
+                    None => Self::other(tag.ev, raw),
+                }
+            }
+        }
+    }
+
+    /// Build [`WsEvent::Other`], reading its symbol once.
+    pub(crate) fn other(ev: SmolStr, raw: &RawValue) -> Result<Self, serde_json::Error> {
+        let fields: SymbolFields<'_> = serde_json::from_str(raw.get())?;
+        Ok(WsEvent::Other {
+            ev,
+            symbol: fields.sym.or(fields.pair).map(Symbol::from),
+            raw: raw.to_owned(),
+        })
+    }
+
+    /// Deserialize an event of type `ev` received from `market`, or return
+    /// `None` if the type is not modelled.
+    pub(crate) fn deserialize_known<'de, D>(
+        market: Market,
+        ev: &str,
+        de: D,
+    ) -> Result<Option<Self>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if market == Market::Futures {
+            match ev {
+                "T" => return Ok(Some(WsEvent::FuturesTrade(Deserialize::deserialize(de)?))),
+                "Q" => return Ok(Some(WsEvent::FuturesQuote(Deserialize::deserialize(de)?))),
+                "A" => {
+                    return Ok(Some(WsEvent::FuturesSecondAggregate(
+                        Deserialize::deserialize(de)?,
+                    )))
+                }
+                "AM" => {
+                    return Ok(Some(WsEvent::FuturesMinuteAggregate(
+                        Deserialize::deserialize(de)?,
+                    )))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Some(match ev {
+            "status" => WsEvent::Status(Deserialize::deserialize(de)?),
+            "T" => WsEvent::Trade(Deserialize::deserialize(de)?),
+            "Q" => WsEvent::Quote(Deserialize::deserialize(de)?),
+            "A" => WsEvent::SecondAggregate(Deserialize::deserialize(de)?),
+            "AM" => WsEvent::MinuteAggregate(Deserialize::deserialize(de)?),
+            "LULD" => WsEvent::LimitUpLimitDown(Deserialize::deserialize(de)?),
+            "FMV" => WsEvent::FairMarketValue(Deserialize::deserialize(de)?),
+            "NOI" => WsEvent::OrderImbalance(Deserialize::deserialize(de)?),
+            "V" => WsEvent::IndexValue(Deserialize::deserialize(de)?),
+            "XT" => WsEvent::CryptoTrade(Deserialize::deserialize(de)?),
+            "XQ" => WsEvent::CryptoQuote(Deserialize::deserialize(de)?),
+            "XA" => WsEvent::CryptoAggregate(Deserialize::deserialize(de)?),
+            "XL2" => WsEvent::CryptoL2(Deserialize::deserialize(de)?),
+            "C" => WsEvent::ForexQuote(Deserialize::deserialize(de)?),
+            "CA" => WsEvent::ForexAggregate(Deserialize::deserialize(de)?),
+            _ => return Ok(None),
+        }))
+    }
+}
+
+impl<'de> Deserialize<'de> for WsEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = Box::<RawValue>::deserialize(deserializer)?;
+        WsEvent::from_raw(&raw, Market::Stocks).map_err(D::Error::custom)
+    }
+}
+
+/// The `ev` field of an event object.
+#[derive(Deserialize)]
+struct EventTag {
+    ev: SmolStr,
+}
+
+/// Outcome of reading an event object whose first field is `ev`.
+enum Tagged {
+    Known(WsEvent),
+    Unknown(SmolStr),
+    /// The first field was not `ev`; nothing was read
+    Unordered,
+}
+
+/// Reads the `ev` field, then the rest of the object as that event type.
+struct TaggedVisitor {
+    market: Market,
+}
+
+impl<'de> Visitor<'de> for TaggedVisitor {
+    type Value = Tagged;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("an event object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Tagged, A::Error> {
+        let tagged = match map.next_key::<SmolStr>()? {
+            Some(key) if key == "ev" => {
+                let ev: SmolStr = map.next_value()?;
+                match WsEvent::deserialize_known(
+                    self.market,
+                    &ev,
+                    MapAccessDeserializer::new(&mut map),
+                )? {
+                    Some(event) => Tagged::Known(event),
+                    None => Tagged::Unknown(ev),
+                }
+            }
+            Some(_) => {
+                map.next_value::<IgnoredAny>()?;
+                Tagged::Unordered
+            }
+            None => return Err(A::Error::missing_field("ev")),
+        };
+        // Skip whatever the event did not read
+        while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+        Ok(tagged)
+    }
+}
+
+/// Symbol fields of an unrecognized event.
+#[derive(Deserialize)]
+struct SymbolFields<'a> {
+    #[serde(borrow)]
+    sym: Option<&'a str>,
+    #[serde(borrow)]
+    pair: Option<&'a str>,
 }
 
 /// Status/control message.
@@ -129,7 +351,7 @@ pub struct WsTradeEvent {
     pub z: u8,
 
     /// Trade price.
-    pub p: f64,
+    pub p: Price,
 
     /// Trade size (shares).
     pub s: u64,
@@ -153,8 +375,8 @@ pub struct WsTradeEvent {
 
 impl WsTradeEvent {
     /// Get the trade value (price * size).
-    pub fn value(&self) -> f64 {
-        self.p * self.s as f64
+    pub fn value(&self) -> Price {
+        self.p * price::from_u64(self.s)
     }
 }
 
@@ -170,7 +392,7 @@ pub struct WsQuoteEvent {
     pub bx: u8,
 
     /// Bid price.
-    pub bp: f64,
+    pub bp: Price,
 
     /// Bid size (lots).
     pub bs: u64,
@@ -179,7 +401,7 @@ pub struct WsQuoteEvent {
     pub ax: u8,
 
     /// Ask price.
-    pub ap: f64,
+    pub ap: Price,
 
     /// Ask size (lots).
     /// Note: renamed from "as" which is a Rust keyword.
@@ -195,13 +417,13 @@ pub struct WsQuoteEvent {
 
 impl WsQuoteEvent {
     /// Calculate the bid-ask spread.
-    pub fn spread(&self) -> f64 {
+    pub fn spread(&self) -> Price {
         self.ap - self.bp
     }
 
     /// Calculate the mid price.
-    pub fn mid(&self) -> f64 {
-        (self.bp + self.ap) / 2.0
+    pub fn mid(&self) -> Price {
+        price::mid(self.bp, self.ap)
     }
 }
 
@@ -220,25 +442,25 @@ pub struct WsAggregateEvent {
     pub av: u64,
 
     /// Official open price (day).
-    pub op: f64,
+    pub op: Price,
 
     /// VWAP for this window.
-    pub vw: f64,
+    pub vw: Price,
 
     /// Open price (window).
-    pub o: f64,
+    pub o: Price,
 
     /// Close price (window).
-    pub c: f64,
+    pub c: Price,
 
     /// High price (window).
-    pub h: f64,
+    pub h: Price,
 
     /// Low price (window).
-    pub l: f64,
+    pub l: Price,
 
     /// VWAP today.
-    pub a: f64,
+    pub a: Price,
 
     /// Average trade size.
     pub z: u64,
@@ -256,7 +478,7 @@ pub struct WsAggregateEvent {
 
 impl WsAggregateEvent {
     /// Calculate the bar range (high - low).
-    pub fn range(&self) -> f64 {
+    pub fn range(&self) -> Price {
         self.h - self.l
     }
 
@@ -280,10 +502,10 @@ pub struct WsLuldEvent {
     pub sym: Symbol,
 
     /// Upper price limit.
-    pub high_price: f64,
+    pub high_price: Price,
 
     /// Lower price limit.
-    pub low_price: f64,
+    pub low_price: Price,
 
     /// LULD indicators.
     pub indicators: Vec<i32>,
@@ -304,7 +526,7 @@ pub struct WsFmvEvent {
     pub sym: Symbol,
 
     /// Fair market value price.
-    pub fmv: f64,
+    pub fmv: Price,
 
     /// Timestamp (Unix milliseconds).
     pub t: i64,
@@ -368,13 +590,13 @@ pub struct WsOrderImbalanceEvent {
     pub imbalance_side: Option<ImbalanceSide>,
 
     /// Reference price.
-    pub reference_price: Option<f64>,
+    pub reference_price: Option<Price>,
 
     /// Near indicative price.
-    pub near_price: Option<f64>,
+    pub near_price: Option<Price>,
 
     /// Far indicative price.
-    pub far_price: Option<f64>,
+    pub far_price: Option<Price>,
 }
 
 impl WsOrderImbalanceEvent {
@@ -669,31 +891,215 @@ impl WsForexAggregateEvent {
     }
 }
 
+// ============================================================================
+// Futures Events
+// ============================================================================
+
+/// Futures trade event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WsFuturesTradeEvent {
+    /// Contract ticker (e.g., "ESZ4").
+    pub sym: Symbol,
+
+    /// Trade price.
+    pub p: Price,
+
+    /// Trade size (contracts).
+    pub s: u64,
+
+    /// Timestamp (Unix milliseconds).
+    pub t: i64,
+
+    /// Sequence number.
+    pub q: u64,
+}
+
+impl WsFuturesTradeEvent {
+    /// Calculate the notional trade value (price * size), before the
+    /// contract multiplier.
+    pub fn value(&self) -> Price {
+        self.p * price::from_u64(self.s)
+    }
+}
+
+/// Futures quote event (top of book).
+#[derive(Debug, Clone, Deserialize)]
+pub struct WsFuturesQuoteEvent {
+    /// Contract ticker (e.g., "ESZ4").
+    pub sym: Symbol,
+
+    /// Bid price.
+    pub bp: Price,
+
+    /// Bid size (contracts).
+    pub bs: u64,
+
+    /// Ask price.
+    pub ap: Price,
+
+    /// Ask size (contracts).
+    /// Note: renamed from "as" which is a Rust keyword.
+    #[serde(rename = "as")]
+    pub ask_size: u64,
+
+    /// Timestamp (Unix milliseconds).
+    pub t: i64,
+}
+
+impl WsFuturesQuoteEvent {
+    /// Calculate the bid-ask spread.
+    pub fn spread(&self) -> Price {
+        self.ap - self.bp
+    }
+
+    /// Calculate the mid price.
+    pub fn mid(&self) -> Price {
+        price::mid(self.bp, self.ap)
+    }
+}
+
+/// Futures aggregate bar event (second or minute).
+#[derive(Debug, Clone, Deserialize)]
+pub struct WsFuturesAggregateEvent {
+    /// Contract ticker (e.g., "ESZ4").
+    pub sym: Symbol,
+
+    /// Volume in this window (contracts).
+    pub v: u64,
+
+    /// Open price.
+    pub o: Price,
+
+    /// Close price.
+    pub c: Price,
+
+    /// High price.
+    pub h: Price,
+
+    /// Low price.
+    pub l: Price,
+
+    /// Window start timestamp (Unix milliseconds).
+    pub s: i64,
+
+    /// Window end timestamp (Unix milliseconds).
+    pub e: i64,
+}
+
+impl WsFuturesAggregateEvent {
+    /// Calculate the bar range (high - low).
+    pub fn range(&self) -> Price {
+        self.h - self.l
+    }
+
+    /// Check if bar is bullish (close > open).
+    pub fn is_bullish(&self) -> bool {
+        self.c > self.o
+    }
+}
+
+/// Events parsed from one WebSocket message.
+#[derive(Debug, Default)]
+pub struct ParsedMessage {
+    /// Events that parsed, in message order
+    pub events: Vec<WsEvent>,
+    /// Events that could not be parsed
+    pub errors: Vec<EventParseError>,
+}
+
+/// An event in a WebSocket message that could not be parsed, such as a
+/// known event type with a missing or mistyped field.
+#[derive(Debug)]
+pub struct EventParseError {
+    /// Position of the event in the message
+    pub index: usize,
+    /// Event type, if the `ev` field could be read
+    pub ev: Option<SmolStr>,
+    /// The event JSON as received
+    pub raw: Box<RawValue>,
+    /// Why parsing failed
+    pub error: serde_json::Error,
+}
+
+impl std::fmt::Display for EventParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.ev {
+            Some(ev) => write!(f, "event {} ({}): {}", self.index, ev, self.error),
+            None => write!(f, "event {}: {}", self.index, self.error),
+        }
+    }
+}
+
+impl std::error::Error for EventParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
 /// Parse a WebSocket message (handles both single events and arrays).
 ///
 /// The Massive WebSocket API can send either a single event object or
 /// an array of events in one message. This function handles both cases.
-pub fn parse_ws_message(text: &str) -> Result<Vec<WsEvent>, serde_json::Error> {
+///
+/// Each event is parsed on its own: one malformed event is reported in
+/// [`ParsedMessage::errors`] without losing the others. Only a message that
+/// is not valid JSON fails as a whole.
+///
+/// Event types shared with the futures socket are read as stock events; use
+/// [`parse_ws_message_for`] for messages from [`Market::Futures`].
+pub fn parse_ws_message(text: &str) -> Result<ParsedMessage, serde_json::Error> {
+    parse_ws_message_for(text, Market::Stocks)
+}
+
+/// Parse a WebSocket message received from the socket for `market`.
+///
+/// Same as [`parse_ws_message`], except that on [`Market::Futures`] the
+/// `T`, `Q`, `A`, and `AM` event types become [`WsEvent::FuturesTrade`],
+/// [`WsEvent::FuturesQuote`], [`WsEvent::FuturesSecondAggregate`], and
+/// [`WsEvent::FuturesMinuteAggregate`].
+pub fn parse_ws_message_for(
+    text: &str,
+    market: Market,
+) -> Result<ParsedMessage, serde_json::Error> {
     let trimmed = text.trim();
 
-    if trimmed.starts_with('[') {
+    let elements: Vec<&RawValue> = if trimmed.starts_with('[') {
         // Array of events
-        serde_json::from_str(trimmed)
+        serde_json::from_str(trimmed)?
     } else {
         // Single event
-        let event: WsEvent = serde_json::from_str(trimmed)?;
-        Ok(vec![event])
+        vec![serde_json::from_str(trimmed)?]
+    };
+
+    let mut parsed = ParsedMessage {
+        events: Vec::with_capacity(elements.len()),
+        errors: Vec::new(),
+    };
+    for (index, raw) in elements.into_iter().enumerate() {
+        match WsEvent::from_raw(raw, market) {
+            Ok(event) => parsed.events.push(event),
+            Err(error) => parsed.errors.push(EventParseError {
+                index,
+                ev: serde_json::from_str::<EventTag>(raw.get())
+                    .ok()
+                    .map(|tag| tag.ev),
+                raw: raw.to_owned(),
+                error,
+            }),
+        }
     }
+    Ok(parsed)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::price::px;
 
     #[test]
     fn test_parse_status_event() {
         let json = r#"{"ev":"status","status":"auth_success","message":"authenticated"}"#;
-        let events = parse_ws_message(json).unwrap();
+        let events = parse_ws_message(json).unwrap().events;
 
         assert_eq!(events.len(), 1);
         match &events[0] {
@@ -708,15 +1114,15 @@ mod tests {
     #[test]
     fn test_parse_trade_event() {
         let json = r#"{"ev":"T","sym":"AAPL","x":4,"i":"12345","z":3,"p":150.25,"s":100,"c":[0],"t":1703001234567,"q":12345}"#;
-        let events = parse_ws_message(json).unwrap();
+        let events = parse_ws_message(json).unwrap().events;
 
         assert_eq!(events.len(), 1);
         match &events[0] {
             WsEvent::Trade(trade) => {
                 assert_eq!(trade.sym.as_str(), "AAPL");
-                assert_eq!(trade.p, 150.25);
+                assert_eq!(trade.p, px(150.25));
                 assert_eq!(trade.s, 100);
-                assert_eq!(trade.value(), 15025.0);
+                assert_eq!(trade.value(), px(15025.0));
             }
             _ => panic!("Expected Trade event"),
         }
@@ -725,16 +1131,16 @@ mod tests {
     #[test]
     fn test_parse_quote_event() {
         let json = r#"{"ev":"Q","sym":"AAPL","bx":4,"bp":150.00,"bs":100,"ax":4,"ap":150.10,"as":200,"c":0,"t":1703001234567}"#;
-        let events = parse_ws_message(json).unwrap();
+        let events = parse_ws_message(json).unwrap().events;
 
         assert_eq!(events.len(), 1);
         match &events[0] {
             WsEvent::Quote(quote) => {
                 assert_eq!(quote.sym.as_str(), "AAPL");
-                assert_eq!(quote.bp, 150.00);
-                assert_eq!(quote.ap, 150.10);
-                assert!((quote.spread() - 0.10).abs() < 0.001);
-                assert!((quote.mid() - 150.05).abs() < 0.001);
+                assert_eq!(quote.bp, px(150.00));
+                assert_eq!(quote.ap, px(150.10));
+                assert!((quote.spread() - px(0.10)).abs() < px(0.001));
+                assert!((quote.mid() - px(150.05)).abs() < px(0.001));
             }
             _ => panic!("Expected Quote event"),
         }
@@ -743,17 +1149,17 @@ mod tests {
     #[test]
     fn test_parse_aggregate_event() {
         let json = r#"{"ev":"AM","sym":"AAPL","v":1000,"av":5000000,"op":148.00,"vw":150.50,"o":150.00,"c":151.00,"h":152.00,"l":149.00,"a":150.25,"z":100,"s":1703001200000,"e":1703001260000}"#;
-        let events = parse_ws_message(json).unwrap();
+        let events = parse_ws_message(json).unwrap().events;
 
         assert_eq!(events.len(), 1);
         match &events[0] {
             WsEvent::MinuteAggregate(agg) => {
                 assert_eq!(agg.sym.as_str(), "AAPL");
-                assert_eq!(agg.o, 150.00);
-                assert_eq!(agg.c, 151.00);
+                assert_eq!(agg.o, px(150.00));
+                assert_eq!(agg.c, px(151.00));
                 assert!(agg.is_green());
                 assert!(!agg.is_red());
-                assert!((agg.range() - 3.0).abs() < 0.001);
+                assert_eq!(agg.range(), px(3.0));
             }
             _ => panic!("Expected MinuteAggregate event"),
         }
@@ -762,7 +1168,7 @@ mod tests {
     #[test]
     fn test_parse_array_of_events() {
         let json = r#"[{"ev":"status","status":"connected"},{"ev":"T","sym":"AAPL","x":4,"i":"1","z":3,"p":150.00,"s":100,"t":1703001234567,"q":1}]"#;
-        let events = parse_ws_message(json).unwrap();
+        let events = parse_ws_message(json).unwrap().events;
 
         assert_eq!(events.len(), 2);
         assert!(matches!(&events[0], WsEvent::Status(_)));
@@ -771,24 +1177,86 @@ mod tests {
 
     #[test]
     fn test_parse_unknown_event() {
-        let json = r#"{"ev":"UNKNOWN_TYPE","foo":"bar"}"#;
-        let events = parse_ws_message(json).unwrap();
+        let json = r#"{"ev":"UNKNOWN_TYPE","sym":"ESZ4","foo":"bar"}"#;
+        let events = parse_ws_message(json).unwrap().events;
 
         assert_eq!(events.len(), 1);
-        assert!(matches!(&events[0], WsEvent::Unknown));
+        match &events[0] {
+            WsEvent::Other { ev, raw, symbol } => {
+                assert_eq!(ev, "UNKNOWN_TYPE");
+                assert_eq!(raw.get(), json);
+                assert_eq!(symbol.as_deref(), Some("ESZ4"));
+            }
+            _ => panic!("Expected Other event"),
+        }
+        assert_eq!(events[0].event_type(), "UNKNOWN_TYPE");
+        assert_eq!(events[0].symbol(), Some("ESZ4"));
+    }
+
+    #[test]
+    fn test_parse_tag_not_first() {
+        let json = r#"[{"sym":"AAPL","ev":"FMV","fmv":150.5,"t":1},{"pair":"BTC-USD","ev":"NEW"},{"sym":"AAPL"}]"#;
+        let parsed = parse_ws_message(json).unwrap();
+
+        assert_eq!(parsed.events.len(), 2);
+        assert!(matches!(&parsed.events[0], WsEvent::FairMarketValue(e) if e.fmv == px(150.5)));
+        assert_eq!(parsed.events[1].event_type(), "NEW");
+        assert_eq!(parsed.events[1].symbol(), Some("BTC-USD"));
+        assert_eq!(parsed.errors.len(), 1);
+        assert!(parsed.errors[0]
+            .error
+            .to_string()
+            .contains("missing field `ev`"));
+    }
+
+    #[test]
+    fn test_parse_reports_bad_elements() {
+        let json = r#"[{"ev":"T","sym":"AAPL","x":4,"i":"1","z":3,"p":"oops","s":100,"t":1,"q":1},{"ev":"status","status":"connected"},42]"#;
+        let parsed = parse_ws_message(json).unwrap();
+
+        assert_eq!(parsed.events.len(), 1);
+        assert!(matches!(&parsed.events[0], WsEvent::Status(_)));
+        assert_eq!(parsed.errors.len(), 2);
+        assert_eq!(parsed.errors[0].index, 0);
+        assert_eq!(parsed.errors[0].ev.as_deref(), Some("T"));
+        assert!(parsed.errors[0].raw.get().contains("oops"));
+        assert_eq!(parsed.errors[1].index, 2);
+        assert_eq!(parsed.errors[1].ev, None);
+
+        assert!(parse_ws_message("[{").is_err());
+    }
+
+    #[test]
+    fn test_deserialize_event_directly() {
+        let event: WsEvent = serde_json::from_str(r#"{"ev":"NEW","x":1}"#).unwrap();
+        assert_eq!(event.event_type(), "NEW");
+        assert!(serde_json::from_str::<WsEvent>(r#"{"ev":"T"}"#).is_err());
+    }
+
+    #[test]
+    fn test_event_type_and_symbol() {
+        let json = r#"[{"ev":"status","status":"connected"},{"ev":"T","sym":"AAPL","x":4,"i":"1","z":3,"p":150.00,"s":100,"t":1703001234567,"q":1},{"ev":"XL2","pair":"BTC-USD","b":[],"a":[],"t":1703001234567}]"#;
+        let events = parse_ws_message(json).unwrap().events;
+
+        assert_eq!(events[0].event_type(), "status");
+        assert_eq!(events[0].symbol(), None);
+        assert_eq!(events[1].event_type(), "T");
+        assert_eq!(events[1].symbol(), Some("AAPL"));
+        assert_eq!(events[2].event_type(), "XL2");
+        assert_eq!(events[2].symbol(), Some("BTC-USD"));
     }
 
     #[test]
     fn test_parse_luld_event() {
         let json = r#"{"ev":"LULD","sym":"AAPL","high_price":155.00,"low_price":145.00,"indicators":[1,2],"tape":3,"t":1703001234567}"#;
-        let events = parse_ws_message(json).unwrap();
+        let events = parse_ws_message(json).unwrap().events;
 
         assert_eq!(events.len(), 1);
         match &events[0] {
             WsEvent::LimitUpLimitDown(luld) => {
                 assert_eq!(luld.sym.as_str(), "AAPL");
-                assert_eq!(luld.high_price, 155.00);
-                assert_eq!(luld.low_price, 145.00);
+                assert_eq!(luld.high_price, px(155.00));
+                assert_eq!(luld.low_price, px(145.00));
             }
             _ => panic!("Expected LULD event"),
         }
@@ -797,15 +1265,72 @@ mod tests {
     #[test]
     fn test_parse_fmv_event() {
         let json = r#"{"ev":"FMV","sym":"AAPL","fmv":150.50,"t":1703001234567}"#;
-        let events = parse_ws_message(json).unwrap();
+        let events = parse_ws_message(json).unwrap().events;
 
         assert_eq!(events.len(), 1);
         match &events[0] {
             WsEvent::FairMarketValue(fmv) => {
                 assert_eq!(fmv.sym.as_str(), "AAPL");
-                assert_eq!(fmv.fmv, 150.50);
+                assert_eq!(fmv.fmv, px(150.50));
             }
             _ => panic!("Expected FMV event"),
         }
     }
+
+    #[test]
+    fn test_parse_futures_events() {
+        let json = r#"[{"ev":"T","sym":"ESZ4","p":6000.25,"s":2,"t":1703001234567,"q":42},{"ev":"Q","sym":"ESZ4","bp":6000.00,"bs":10,"ap":6000.50,"as":12,"t":1703001234568},{"ev":"AM","sym":"ESZ4","v":1500,"o":5999.00,"c":6001.00,"h":6002.50,"l":5998.25,"s":1703001180000,"e":1703001240000}]"#;
+        let events = parse_ws_message_for(json, Market::Futures).unwrap().events;
+
+        assert_eq!(events.len(), 3);
+        match &events[0] {
+            WsEvent::FuturesTrade(trade) => {
+                assert_eq!(trade.sym.as_str(), "ESZ4");
+                assert_eq!(trade.q, 42);
+                assert_eq!(trade.p, px(6000.25));
+                assert_eq!(trade.value(), px(12000.5));
+            }
+            _ => panic!("Expected FuturesTrade event"),
+        }
+        match &events[1] {
+            WsEvent::FuturesQuote(quote) => {
+                assert_eq!(quote.ask_size, 12);
+                assert_eq!(quote.spread(), px(0.5));
+                assert_eq!(quote.mid(), px(6000.25));
+            }
+            _ => panic!("Expected FuturesQuote event"),
+        }
+        match &events[2] {
+            WsEvent::FuturesMinuteAggregate(agg) => {
+                assert_eq!(agg.v, 1500);
+                assert!(agg.is_bullish());
+                assert_eq!(agg.range(), px(4.25));
+            }
+            _ => panic!("Expected FuturesMinuteAggregate event"),
+        }
+        assert_eq!(events[0].event_type(), "T");
+        assert_eq!(events[2].symbol(), Some("ESZ4"));
+    }
+
+    #[test]
+    fn test_parse_market_selects_variant() {
+        let json = r#"{"ev":"A","sym":"ESZ4","v":3,"o":1.0,"c":1.0,"h":1.0,"l":1.0,"s":1,"e":2}"#;
+        let futures = parse_ws_message_for(json, Market::Futures).unwrap();
+        assert!(matches!(
+            &futures.events[0],
+            WsEvent::FuturesSecondAggregate(_)
+        ));
+
+        // The same payload is missing stock aggregate fields
+        let stocks = parse_ws_message(json).unwrap();
+        assert!(stocks.events.is_empty());
+        assert_eq!(stocks.errors.len(), 1);
+
+        // Other event types are unaffected by the market
+        let status = r#"{"ev":"status","status":"connected"}"#;
+        let events = parse_ws_message_for(status, Market::Futures)
+            .unwrap()
+            .events;
+        assert!(matches!(&events[0], WsEvent::Status(_)));
+    }
 }
@@ -3,7 +3,7 @@
 //! This module defines the message formats used for WebSocket
 //! communication, including authentication and subscription messages.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 
 /// Subscription topic for WebSocket streams.
@@ -201,6 +201,33 @@ impl Subscription {
         Self(SmolStr::new_static("V.I:*"))
     }
 
+    // ========================================================================
+    // Futures (futures socket, `Market::Futures`)
+    // ========================================================================
+
+    /// Futures trade subscription: `T.{ticker}` (e.g., `T.ESZ4`)
+    ///
+    /// Futures topics share their names with stock topics and are only valid
+    /// on a connection configured with [`Market::Futures`](crate::config::Market::Futures).
+    pub fn futures_trade(ticker: &str) -> Self {
+        Self(SmolStr::new(format!("T.{}", ticker)))
+    }
+
+    /// Futures quote subscription: `Q.{ticker}`
+    pub fn futures_quote(ticker: &str) -> Self {
+        Self(SmolStr::new(format!("Q.{}", ticker)))
+    }
+
+    /// Futures minute aggregate subscription: `AM.{ticker}`
+    pub fn futures_minute_agg(ticker: &str) -> Self {
+        Self(SmolStr::new(format!("AM.{}", ticker)))
+    }
+
+    /// Futures second aggregate subscription: `A.{ticker}`
+    pub fn futures_second_agg(ticker: &str) -> Self {
+        Self(SmolStr::new(format!("A.{}", ticker)))
+    }
+
     // ========================================================================
     // Utility Methods
     // ========================================================================
@@ -224,6 +251,12 @@ impl std::fmt::Display for Subscription {
     }
 }
 
+impl AsRef<str> for Subscription {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
 impl From<&str> for Subscription {
     fn from(s: &str) -> Self {
         Self::raw(s)
@@ -236,8 +269,13 @@ impl From<String> for Subscription {
     }
 }
 
+/// Maximum number of topics sent in one subscribe or unsubscribe frame.
+///
+/// Longer topic lists are split across several frames.
+pub(crate) const MAX_TOPICS_PER_FRAME: usize = 500;
+
 /// Authentication message sent to the WebSocket server.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct WsAuthMessage {
     /// Action type (always "auth")
     pub action: String,
@@ -256,7 +294,7 @@ impl WsAuthMessage {
 }
 
 /// Subscribe/unsubscribe message sent to the WebSocket server.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct WsSubscribeMessage {
     /// Action type ("subscribe" or "unsubscribe")
     pub action: String,
@@ -316,6 +354,14 @@ mod tests {
         assert_eq!(minute.as_str(), "AM.TSLA");
     }
 
+    #[test]
+    fn test_subscription_futures() {
+        assert_eq!(Subscription::futures_trade("ESZ4").as_str(), "T.ESZ4");
+        assert_eq!(Subscription::futures_quote("ESZ4").as_str(), "Q.ESZ4");
+        assert_eq!(Subscription::futures_minute_agg("ESZ4").as_str(), "AM.ESZ4");
+        assert_eq!(Subscription::futures_second_agg("ESZ4").as_str(), "A.ESZ4");
+    }
+
     #[test]
     fn test_subscription_wildcard() {
         assert_eq!(Subscription::all_trades().as_str(), "T.*");
@@ -0,0 +1,389 @@
+//! Replay of recorded WebSocket sessions.
+//!
+//! [`ReplayClient`] reads a journal written by
+//! [`WsClient::with_recorder`](crate::ws::WsClient::with_recorder) and
+//! delivers its frames through the same [`WsHandle`] and [`WsEventStream`]
+//! as a live connection, so strategies can be tested against recorded
+//! market data without changes.
+
+use crate::config::{DispatchConfig, Market};
+use crate::error::MassiveError;
+use crate::metrics::ClientStats;
+use crate::ws::ack::{SubscriptionReport, TopicStatus};
+use crate::ws::client::{
+    ConnectionState, WsCommand, WsEventStream, WsHandle, WsMessageBatch, WsState,
+};
+use crate::ws::diagnostics::{Diagnostic, DiagnosticHub};
+use crate::ws::dispatch::Dispatcher;
+use crate::ws::journal::{self, JournalEntry};
+use crate::ws::lifecycle::{ConnectionEvent, DisconnectReason, LifecycleHub};
+use crate::ws::models::events::{parse_ws_message_for, WsEvent};
+use crate::ws::protocol::Subscription;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tokio::sync::{mpsc, watch};
+use tokio::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// How fast a journal is replayed.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Pacing {
+    /// Keep the recorded gaps between frames.
+    #[default]
+    RealTime,
+    /// Divide the recorded gaps between frames by a factor; `Accelerated(10.0)`
+    /// replays ten times faster than recorded.
+    Accelerated(f64),
+    /// Deliver frames as fast as the consumer takes them.
+    ///
+    /// Replay waits while a subscriber queue is full instead of dropping
+    /// batches.
+    AsFastAsPossible,
+}
+
+impl Pacing {
+    /// Speed factor relative to the recording, or `None` if unpaced.
+    fn speed(self) -> Option<f64> {
+        match self {
+            Pacing::RealTime => Some(1.0),
+            Pacing::Accelerated(factor) => Some(factor),
+            Pacing::AsFastAsPossible => None,
+        }
+    }
+}
+
+/// Replays a recorded session as if it were a live connection.
+///
+/// The handle starts connected and authenticated. Subscribe and unsubscribe
+/// calls are accepted immediately but do not filter the replay: every
+/// recorded frame is delivered. The event stream and
+/// [`WsHandle::lifecycle`] streams end after the last frame.
+///
+/// # Example
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use massive_rs::ws::{Pacing, ReplayClient};
+///
+/// # async fn example() -> Result<(), massive_rs::MassiveError> {
+/// let (_handle, mut stream) = ReplayClient::new("journals")
+///     .with_pacing(Pacing::Accelerated(10.0))
+///     .connect()
+///     .await?;
+///
+/// while let Some(batch) = stream.next().await {
+///     for event in batch?.events {
+///         println!("{:?}", event);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReplayClient {
+    path: PathBuf,
+    pacing: Pacing,
+    dispatch: DispatchConfig,
+    market: Market,
+}
+
+impl ReplayClient {
+    /// Replay a journal file, or every journal file in a directory in
+    /// chronological order.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            pacing: Pacing::default(),
+            dispatch: DispatchConfig::default(),
+            market: Market::default(),
+        }
+    }
+
+    /// Set the replay speed.
+    pub fn with_pacing(mut self, pacing: Pacing) -> Self {
+        self.pacing = pacing;
+        self
+    }
+
+    /// Set the dispatch configuration used for the event streams.
+    pub fn with_dispatch(mut self, dispatch: DispatchConfig) -> Self {
+        self.dispatch = dispatch;
+        self
+    }
+
+    /// Set the market the journal was recorded from.
+    ///
+    /// Futures journals need [`Market::Futures`] so that their events parse
+    /// as futures events.
+    pub fn with_market(mut self, market: Market) -> Self {
+        self.market = market;
+        self
+    }
+
+    /// Start replaying.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the journal cannot be found or the accelerated
+    /// pacing factor is not a positive number.
+    pub async fn connect(&self) -> Result<(WsHandle, WsEventStream), MassiveError> {
+        if let Pacing::Accelerated(factor) = self.pacing {
+            if !(factor.is_finite() && factor > 0.0) {
+                return Err(MassiveError::InvalidArgument(
+                    "accelerated pacing factor must be positive",
+                ));
+            }
+        }
+        let files = journal::resolve(&self.path)?;
+
+        let (cmd_tx, cmd_rx) = mpsc::channel::<WsCommand>(32);
+        let (state_tx, state_rx) = watch::channel(ConnectionState::Connected);
+        let lifecycle = Arc::new(LifecycleHub::new());
+        let diagnostics = Arc::new(DiagnosticHub::new());
+        let dispatcher = Arc::new(Dispatcher::new(self.dispatch.clone()));
+        let stream = dispatcher.add_subscriber();
+        let state = Arc::new(WsState::new(Arc::new(ClientStats::new())));
+        state.authenticated.store(true, Ordering::Release);
+
+        let replay = Replay {
+            entries: journal::read_journal(files),
+            cmd_rx,
+            dispatcher: dispatcher.clone(),
+            state: state.clone(),
+            state_tx,
+            lifecycle: lifecycle.clone(),
+            diagnostics: diagnostics.clone(),
+            pacing: self.pacing,
+            market: self.market,
+            capacity: self.dispatch.capacity,
+        };
+        tokio::spawn(replay.run());
+
+        let handle = WsHandle {
+            cmd_tx,
+            state,
+            dispatcher,
+            state_rx,
+            lifecycle,
+            diagnostics,
+        };
+        Ok((handle, Box::pin(stream)))
+    }
+}
+
+/// Replay task state.
+struct Replay {
+    entries: mpsc::Receiver<JournalEntry>,
+    cmd_rx: mpsc::Receiver<WsCommand>,
+    dispatcher: Arc<Dispatcher>,
+    state: Arc<WsState>,
+    state_tx: watch::Sender<ConnectionState>,
+    lifecycle: Arc<LifecycleHub>,
+    diagnostics: Arc<DiagnosticHub>,
+    pacing: Pacing,
+    market: Market,
+    capacity: usize,
+}
+
+impl Replay {
+    async fn run(mut self) {
+        let reason = self.replay().await;
+        debug!(%reason, "Replay finished");
+        self.state.shutdown.store(true, Ordering::Release);
+        let _ = self.state_tx.send(ConnectionState::Disconnected);
+        self.lifecycle
+            .publish(ConnectionEvent::Disconnected { reason });
+        self.dispatcher.close();
+        self.diagnostics.close();
+        self.lifecycle.close();
+    }
+
+    /// Deliver frames until the journal ends or the handle closes.
+    async fn replay(&mut self) -> DisconnectReason {
+        // Recorded time and wall-clock time of the first frame
+        let mut origin: Option<(i64, Instant)> = None;
+
+        loop {
+            let entry = tokio::select! {
+                entry = self.entries.recv() => match entry {
+                    Some(entry) => entry,
+                    None => return DisconnectReason::ServerClose,
+                },
+                cmd = self.cmd_rx.recv() => match self.command(cmd) {
+                    Some(reason) => return reason,
+                    None => continue,
+                },
+            };
+
+            if let Some(speed) = self.pacing.speed() {
+                let (first_ts, start) = *origin.get_or_insert((entry.ts, Instant::now()));
+                let offset = entry.ts.saturating_sub(first_ts).max(0) as f64 / speed;
+                let due = start + Duration::from_nanos(offset as u64);
+                if let Some(reason) = self.wait_until(due).await {
+                    return reason;
+                }
+            } else if let Some(reason) = self.wait_for_room().await {
+                return reason;
+            }
+
+            if !self.deliver(entry) {
+                return DisconnectReason::ClientClose;
+            }
+        }
+    }
+
+    /// Sleep until `due`, answering commands meanwhile.
+    async fn wait_until(&mut self, due: Instant) -> Option<DisconnectReason> {
+        loop {
+            tokio::select! {
+                () = tokio::time::sleep_until(due) => return None,
+                cmd = self.cmd_rx.recv() => {
+                    if let Some(reason) = self.command(cmd) {
+                        return Some(reason);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Wait until every subscriber queue has room, answering commands
+    /// meanwhile.
+    async fn wait_for_room(&mut self) -> Option<DisconnectReason> {
+        while self.dispatcher.queue_depth() >= self.capacity {
+            if let Some(reason) = self
+                .wait_until(Instant::now() + Duration::from_millis(1))
+                .await
+            {
+                return Some(reason);
+            }
+        }
+        None
+    }
+
+    /// Answer a command. Returns the reason to stop, if any.
+    fn command(&mut self, cmd: Option<WsCommand>) -> Option<DisconnectReason> {
+        match cmd {
+            Some(WsCommand::Subscribe(topics, reply)) => {
+                for topic in &topics {
+                    self.state.subscriptions.insert(topic.clone());
+                }
+                if !topics.is_empty() {
+                    self.lifecycle.publish(ConnectionEvent::Subscribed {
+                        topics: topics.clone(),
+                    });
+                }
+                let _ = reply.send(Ok(accept_all(topics)));
+                None
+            }
+            Some(WsCommand::Unsubscribe(topics, reply)) => {
+                for topic in &topics {
+                    self.state.subscriptions.remove(topic);
+                }
+                let _ = reply.send(Ok(accept_all(topics)));
+                None
+            }
+            Some(WsCommand::Close(reply)) => {
+                let _ = reply.send(());
+                Some(DisconnectReason::ClientClose)
+            }
+            None => Some(DisconnectReason::ClientClose),
+        }
+    }
+
+    /// Parse and dispatch one frame. Returns `false` if the consumer is gone.
+    fn deliver(&self, entry: JournalEntry) -> bool {
+        let received_ms = (entry.ts / 1_000_000).max(0) as u64;
+        self.state
+            .last_message_time
+            .store(received_ms, Ordering::Release);
+        self.state.message_count.fetch_add(1, Ordering::AcqRel);
+        self.state
+            .stats
+            .inc_bytes_received(entry.frame.len() as u64);
+
+        let events = match parse_ws_message_for(&entry.frame, self.market) {
+            Ok(parsed) => {
+                for error in &parsed.errors {
+                    self.report(Diagnostic::invalid_event(error));
+                }
+                parsed.events
+            }
+            Err(e) => {
+                self.report(Diagnostic::MalformedFrame {
+                    raw: entry.frame,
+                    error: e.to_string(),
+                });
+                return true;
+            }
+        };
+        if events.is_empty() {
+            return true;
+        }
+        self.state.stats.inc_messages_received(events.len() as u64);
+
+        for event in &events {
+            if let WsEvent::Status(status) = event {
+                if status.is_auth_success() {
+                    self.lifecycle.publish(ConnectionEvent::Authenticated);
+                } else if let Some(diagnostic) = Diagnostic::unknown_status(status, &entry.frame) {
+                    self.report(diagnostic);
+                }
+            }
+        }
+
+        let batch = WsMessageBatch {
+            events,
+            received_at: Instant::now(),
+            latency_hint_ns: None,
+            backfilled: false,
+        };
+        match self.dispatcher.dispatch(batch) {
+            Ok(dropped) => {
+                if dropped > 0 {
+                    self.state.stats.inc_messages_dropped(dropped as u64);
+                }
+                true
+            }
+            Err(()) => false,
+        }
+    }
+
+    /// Count a diagnostic and publish it to diagnostics streams.
+    fn report(&self, diagnostic: Diagnostic) {
+        self.state.stats.inc_parse_errors();
+        warn!(?diagnostic, "Recorded data could not be interpreted");
+        self.diagnostics.publish(diagnostic);
+    }
+}
+
+/// Report accepting every topic.
+fn accept_all(topics: Vec<Subscription>) -> SubscriptionReport {
+    SubscriptionReport {
+        results: topics
+            .into_iter()
+            .map(|topic| (topic, TopicStatus::Accepted))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pacing_speed() {
+        assert_eq!(Pacing::RealTime.speed(), Some(1.0));
+        assert_eq!(Pacing::Accelerated(4.0).speed(), Some(4.0));
+        assert_eq!(Pacing::AsFastAsPossible.speed(), None);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_acceleration_rejected() {
+        let result = ReplayClient::new("journals")
+            .with_pacing(Pacing::Accelerated(0.0))
+            .connect()
+            .await;
+        assert!(matches!(result, Err(MassiveError::InvalidArgument(_))));
+    }
+}
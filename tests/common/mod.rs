@@ -8,6 +8,7 @@
 use massive_rs::auth::ApiKey;
 use massive_rs::config::RestConfig;
 use massive_rs::rest::RestClient;
+use massive_rs::Price;
 
 #[cfg(feature = "ws")]
 use massive_rs::config::WsConfig;
@@ -152,3 +153,10 @@ pub fn init_test_logging() {
         .with_test_writer()
         .try_init();
 }
+
+/// Build a price from a float literal, exactly as written.
+///
+/// Lets assertions work whether or not the `decimal` feature is enabled.
+pub fn px(value: f64) -> Price {
+    value.to_string().parse().expect("valid price literal")
+}
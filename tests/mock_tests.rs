@@ -7,13 +7,15 @@
 //! Run with: `cargo test --test mock_tests`
 
 use massive_rs::auth::{ApiKey, AuthMode};
-use massive_rs::config::{PaginationMode, RestConfig};
+use massive_rs::config::{PaginationMode, PrefetchConfig, RestConfig, RetryPolicy};
 use massive_rs::error::MassiveError;
 use massive_rs::rest::endpoints::{
     GetAggsRequest, GetDailyOpenCloseRequest, GetLastQuoteRequest, GetLastTradeRequest,
-    GetPreviousCloseRequest, GetTickerDetailsRequest, GetTickersRequest, Timespan,
+    GetPreviousCloseRequest, GetTickerDetailsRequest, GetTickersRequest, GetTradesRequest,
+    Timespan,
 };
-use massive_rs::rest::RestClient;
+use massive_rs::rest::{RestClient, ShardConfig};
+use massive_rs::Price;
 use url::Url;
 use wiremock::matchers::{header, method, path, path_regex, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -22,6 +24,11 @@ use wiremock::{Mock, MockServer, ResponseTemplate};
 // Test Helper Functions
 // ============================================================================
 
+/// Build a price from a float literal, exactly as written.
+fn px(value: f64) -> Price {
+    value.to_string().parse().expect("valid price literal")
+}
+
 /// Create a REST client configured for the mock server.
 fn create_mock_client(mock_server: &MockServer) -> RestClient {
     let config = RestConfig {
@@ -104,10 +111,10 @@ async fn test_mock_aggregates_success() {
     assert_eq!(aggs.status, Some("OK".to_string()));
     assert_eq!(aggs.ticker, Some("AAPL".to_string()));
     assert_eq!(aggs.results.len(), 2);
-    assert_eq!(aggs.results[0].open, 150.0);
-    assert_eq!(aggs.results[0].high, 155.0);
-    assert_eq!(aggs.results[0].low, 148.0);
-    assert_eq!(aggs.results[0].close, 153.0);
+    assert_eq!(aggs.results[0].open, px(150.0));
+    assert_eq!(aggs.results[0].high, px(155.0));
+    assert_eq!(aggs.results[0].low, px(148.0));
+    assert_eq!(aggs.results[0].close, px(153.0));
 }
 
 /// Test successful previous close response
@@ -144,7 +151,7 @@ async fn test_mock_previous_close_success() {
     let prev = response.expect("Should parse previous close");
     assert_eq!(prev.status, Some("OK".to_string()));
     assert_eq!(prev.results.len(), 1);
-    assert_eq!(prev.results[0].close, 153.0);
+    assert_eq!(prev.results[0].close, px(153.0));
 }
 
 /// Test successful daily open/close response
@@ -179,10 +186,10 @@ async fn test_mock_daily_open_close_success() {
     let daily = response.expect("Should parse daily open/close");
     assert_eq!(daily.status, "OK");
     assert_eq!(daily.symbol, "AAPL");
-    assert_eq!(daily.open, 150.0);
-    assert_eq!(daily.close, 153.0);
-    assert_eq!(daily.after_hours, Some(153.50));
-    assert_eq!(daily.pre_market, Some(149.50));
+    assert_eq!(daily.open, px(150.0));
+    assert_eq!(daily.close, px(153.0));
+    assert_eq!(daily.after_hours, Some(px(153.50)));
+    assert_eq!(daily.pre_market, Some(px(149.50)));
 }
 
 /// Test successful last trade response
@@ -218,7 +225,7 @@ async fn test_mock_last_trade_success() {
     let last_trade = response.expect("Should parse last trade");
     assert_eq!(last_trade.status, Some("OK".to_string()));
     let trade = last_trade.results.expect("Should have trade result");
-    assert_eq!(trade.price, 153.25);
+    assert_eq!(trade.price, px(153.25));
     assert_eq!(trade.size, 100);
     assert_eq!(trade.conditions, vec![0, 12]);
 }
@@ -258,12 +265,12 @@ async fn test_mock_last_quote_success() {
     let last_quote = response.expect("Should parse last quote");
     assert_eq!(last_quote.status, Some("OK".to_string()));
     let quote = last_quote.results.expect("Should have quote result");
-    assert_eq!(quote.ask_price, 153.50);
-    assert_eq!(quote.bid_price, 153.40);
+    assert_eq!(quote.ask_price, px(153.50));
+    assert_eq!(quote.bid_price, px(153.40));
 
     // Test helper methods
-    assert!((quote.spread() - 0.10).abs() < 0.001);
-    assert!((quote.mid_price() - 153.45).abs() < 0.001);
+    assert!((quote.spread() - px(0.10)).abs() < px(0.001));
+    assert!((quote.mid_price() - px(153.45)).abs() < px(0.001));
     assert!(!quote.is_crossed());
     assert!(!quote.is_locked());
 }
@@ -445,6 +452,61 @@ async fn test_mock_rate_limit_error() {
     }
 }
 
+/// Test that a 429 is retried after the Retry-After delay
+#[tokio::test]
+async fn test_mock_rate_limit_retry_after() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+
+    let body = r#"{"status": "OK", "results": {"T": "AAPL", "p": 150.0, "s": 100, "t": 1703001234567, "x": 4}}"#;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_mock_client(&mock_server);
+    let start = std::time::Instant::now();
+    let response = client.execute(GetLastTradeRequest::new("AAPL")).await;
+
+    assert!(
+        response.is_ok(),
+        "Expected retry to succeed: {:?}",
+        response
+    );
+    assert!(start.elapsed() >= std::time::Duration::from_secs(1));
+    assert_eq!(client.stats().rate_limits, 1);
+    assert_eq!(client.stats().retries, 1);
+}
+
+/// Test that retries stop when the attempts are exhausted
+#[tokio::test]
+async fn test_mock_retry_attempts_exhausted() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let config = RestConfig::new("test-api-key")
+        .with_base_url(Url::parse(&mock_server.uri()).unwrap())
+        .with_retry_policy(RetryPolicy::default().with_max_attempts(2));
+    let client = RestClient::new(config).unwrap();
+
+    let response = client.execute(GetLastTradeRequest::new("AAPL")).await;
+    match response {
+        Err(MassiveError::HttpStatus { status, .. }) => assert_eq!(status, 503),
+        other => panic!("Expected HttpStatus error, got: {:?}", other),
+    }
+    assert_eq!(client.stats().retries, 1);
+}
+
 /// Test handling of 500 Internal Server Error
 #[tokio::test]
 async fn test_mock_server_error() {
@@ -523,6 +585,78 @@ async fn test_mock_query_param_auth() {
     assert!(response.is_ok(), "Query param auth should succeed");
 }
 
+/// Test that a next_url API key is not sent in bearer mode
+#[tokio::test]
+async fn test_mock_next_url_api_key_stripped_in_bearer_mode() {
+    use futures::StreamExt;
+
+    let mock_server = MockServer::start().await;
+    let page1_json = format!(
+        r#"{{
+        "status": "OK",
+        "results": [{{"o": 150.0, "h": 155.0, "l": 148.0, "c": 153.0, "v": 1000000, "t": 1703001234567}}],
+        "next_url": "{}/v2/aggs/ticker/AAPL/range/1/day/2024-01-01/2024-01-31?cursor=page2&apiKey=leaked-key"
+    }}"#,
+        mock_server.uri()
+    );
+    let page2_json = r#"{
+        "status": "OK",
+        "results": [{"o": 153.0, "h": 158.0, "l": 152.0, "c": 156.0, "v": 1200000, "t": 1703087634567}]
+    }"#;
+
+    Mock::given(method("GET"))
+        .and(query_param("cursor", "page2"))
+        .and(header("Authorization", "Bearer test-api-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(page2_json))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"/v2/aggs/ticker/AAPL/range/.*"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(page1_json))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_mock_client(&mock_server);
+    let request = GetAggsRequest::new("AAPL")
+        .multiplier(1)
+        .timespan(Timespan::Day)
+        .from("2024-01-01")
+        .to("2024-01-31");
+    let bars: Vec<_> = client.stream(request).collect().await;
+    assert_eq!(bars.len(), 2);
+    assert!(bars.iter().all(|b| b.is_ok()));
+
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(requests
+        .iter()
+        .all(|r| !r.url.query_pairs().any(|(k, _)| k == "apiKey")));
+}
+
+/// Test that transport errors do not reveal a query param API key
+#[tokio::test]
+async fn test_mock_transport_error_redacts_api_key() {
+    // Nothing listens on the port of a dropped listener
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let config = RestConfig::new("secret-api-key")
+        .with_base_url(Url::parse(&format!("http://{}", addr)).unwrap())
+        .with_auth_mode(AuthMode::QueryParam)
+        .with_retry_policy(RetryPolicy::none());
+    let client = RestClient::new(config).unwrap();
+
+    let err = client
+        .execute(GetLastTradeRequest::new("AAPL"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, MassiveError::Transport(_)));
+    let message = format!("{} {:?}", err, err);
+    assert!(!message.contains("secret-api-key"), "{}", message);
+    assert!(message.contains("apiKey=***"), "{}", message);
+}
+
 // ============================================================================
 // Response Parsing Tests
 // ============================================================================
@@ -640,7 +774,7 @@ async fn test_mock_extra_fields() {
 
     // Should succeed - extra fields should be ignored
     let last_trade = response.expect("Should handle extra fields");
-    assert_eq!(last_trade.results.unwrap().price, 153.25);
+    assert_eq!(last_trade.results.unwrap().price, px(153.25));
 }
 
 // ============================================================================
@@ -702,6 +836,606 @@ async fn test_mock_pagination() {
     assert!(aggs.next_url.is_some());
 }
 
+/// Test that pagination and clones queue behind the shared rate limiter
+#[tokio::test]
+async fn test_mock_rate_limiter_paces_requests() {
+    use futures::StreamExt;
+    use massive_rs::config::RateLimitConfig;
+
+    let mock_server = MockServer::start().await;
+    let mock_uri = mock_server.uri();
+
+    let page1_json = format!(
+        r#"{{
+        "status": "OK",
+        "results": [{{"o": 150.0, "h": 155.0, "l": 148.0, "c": 153.0, "v": 1000000, "t": 1703001234567}}],
+        "next_url": "{}/v2/aggs/ticker/AAPL/range/1/day/2024-01-01/2024-01-31?cursor=page2"
+    }}"#,
+        mock_uri
+    );
+    let page2_json = r#"{
+        "status": "OK",
+        "results": [{"o": 153.0, "h": 158.0, "l": 152.0, "c": 156.0, "v": 1200000, "t": 1703087634567}]
+    }"#;
+
+    Mock::given(method("GET"))
+        .and(query_param("cursor", "page2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(page2_json))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"/v2/aggs/ticker/AAPL/range/.*"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(page1_json))
+        .mount(&mock_server)
+        .await;
+
+    let config = RestConfig::new("test-api-key")
+        .with_base_url(Url::parse(&mock_uri).unwrap())
+        .with_rate_limit(RateLimitConfig::per_second(10).with_burst(1));
+    let client = RestClient::new(config).unwrap();
+    let request = GetAggsRequest::new("AAPL")
+        .multiplier(1)
+        .timespan(Timespan::Day)
+        .from("2024-01-01")
+        .to("2024-01-31");
+
+    let start = std::time::Instant::now();
+    let bars: Vec<_> = client.stream(request.clone()).collect().await;
+    assert_eq!(bars.len(), 2);
+    client.clone().execute(request).await.unwrap();
+
+    // Three requests at 10/s with no burst take at least 200ms
+    assert!(start.elapsed() >= std::time::Duration::from_millis(200));
+    assert_eq!(client.stats().rate_limits, 0);
+}
+
+/// Bar JSON with open price `o`
+fn bar_json(o: u32) -> String {
+    format!(r#"{{"o": {o}.0, "h": {o}.0, "l": {o}.0, "c": {o}.0, "v": 100, "t": 1703001234567}}"#)
+}
+
+/// Mount three pages of bars: opens 1-2, 3-4, and 5
+async fn mount_three_pages(mock_server: &MockServer) {
+    let uri = mock_server.uri();
+    let page = |bars: &[u32], next: Option<&str>| {
+        let results: Vec<String> = bars.iter().map(|&o| bar_json(o)).collect();
+        let next_url = next.map_or(String::new(), |cursor| {
+            format!(
+                r#", "next_url": "{uri}/v2/aggs/ticker/AAPL/range/1/day/2024-01-01/2024-01-31?cursor={cursor}&apiKey=leaked-key""#
+            )
+        });
+        format!(
+            r#"{{"status": "OK", "resultsCount": {}, "results": [{}]{}}}"#,
+            results.len(),
+            results.join(","),
+            next_url
+        )
+    };
+    let respond = |id: &str, body: String| {
+        ResponseTemplate::new(200)
+            .insert_header("X-Request-Id", id)
+            .set_body_string(body)
+    };
+
+    Mock::given(method("GET"))
+        .and(query_param("cursor", "page2"))
+        .respond_with(respond("req-2", page(&[3, 4], Some("page3"))))
+        .mount(mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(query_param("cursor", "page3"))
+        .respond_with(respond("req-3", page(&[5], None)))
+        .mount(mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"/v2/aggs/ticker/AAPL/range/.*"))
+        .respond_with(respond("req-1", page(&[1, 2], Some("page2"))))
+        .mount(mock_server)
+        .await;
+}
+
+fn aggs_request() -> GetAggsRequest {
+    GetAggsRequest::new("AAPL")
+        .multiplier(1)
+        .timespan(Timespan::Day)
+        .from("2024-01-01")
+        .to("2024-01-31")
+}
+
+/// Test resuming a stream from a serialized mid-page checkpoint
+#[tokio::test]
+async fn test_mock_pagination_resume_from_checkpoint() {
+    use futures::StreamExt;
+    use massive_rs::rest::PageCheckpoint;
+
+    let mock_server = MockServer::start().await;
+    mount_three_pages(&mock_server).await;
+    let client = create_mock_client(&mock_server);
+
+    let mut stream = client.stream(aggs_request());
+    assert_eq!(stream.checkpoint(), PageCheckpoint::default());
+    let mut opens = Vec::new();
+    for _ in 0..3 {
+        opens.push(stream.next().await.unwrap().unwrap().open);
+    }
+    assert_eq!(opens, vec![px(1.0), px(2.0), px(3.0)]);
+
+    let checkpoint = stream.checkpoint();
+    assert_eq!(checkpoint.page_offset, 1);
+    assert_eq!(checkpoint.items_yielded, 3);
+    assert!(!checkpoint.finished);
+    let page_url = checkpoint.page_url.as_deref().unwrap();
+    assert!(page_url.contains("cursor=page2"));
+    assert!(!page_url.contains("apiKey"));
+    drop(stream);
+
+    let json = serde_json::to_string(&checkpoint).unwrap();
+    assert!(!json.contains("leaked-key"));
+    let restored: PageCheckpoint = serde_json::from_str(&json).unwrap();
+    let mut resumed = client.resume_stream(aggs_request(), restored);
+    let mut rest = Vec::new();
+    while let Some(bar) = resumed.next().await {
+        rest.push(bar.unwrap().open);
+    }
+    assert_eq!(rest, vec![px(4.0), px(5.0)]);
+
+    let done = resumed.checkpoint();
+    assert!(done.finished);
+    assert_eq!(done.items_yielded, 5);
+    let mut finished = client.resume_stream(aggs_request(), done);
+    assert!(finished.next().await.is_none());
+}
+
+/// Test that a checkpoint taken after a failed page retries that page
+#[tokio::test]
+async fn test_mock_pagination_resume_after_error() {
+    use futures::StreamExt;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("cursor", "page2"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    mount_three_pages(&mock_server).await;
+
+    let config = RestConfig {
+        base_url: Url::parse(&mock_server.uri()).unwrap(),
+        retry_policy: RetryPolicy::none(),
+        ..RestConfig::new("test-api-key")
+    };
+    let client = RestClient::new(config).unwrap();
+
+    let mut stream = client.stream(aggs_request());
+    assert_eq!(stream.next().await.unwrap().unwrap().open, px(1.0));
+    assert_eq!(stream.next().await.unwrap().unwrap().open, px(2.0));
+    assert!(stream.next().await.unwrap().is_err());
+    assert!(stream.next().await.is_none());
+
+    let checkpoint = stream.checkpoint();
+    assert!(!checkpoint.finished);
+    assert_eq!(checkpoint.page_offset, 0);
+    assert!(checkpoint
+        .page_url
+        .as_deref()
+        .unwrap()
+        .contains("cursor=page2"));
+
+    let rest: Vec<Price> = client
+        .resume_stream(aggs_request(), checkpoint)
+        .map(|bar| bar.unwrap().open)
+        .collect()
+        .await;
+    assert_eq!(rest, vec![px(3.0), px(4.0), px(5.0)]);
+}
+
+/// Test streaming whole pages with their HTTP metadata
+#[tokio::test]
+async fn test_mock_stream_pages_metadata() {
+    use futures::StreamExt;
+
+    let mock_server = MockServer::start().await;
+    mount_three_pages(&mock_server).await;
+    let client = create_mock_client(&mock_server);
+
+    let pages: Vec<_> = client
+        .stream_pages(aggs_request())
+        .map(|page| page.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(pages.len(), 3);
+    for (i, page) in pages.iter().enumerate() {
+        assert_eq!(page.meta.index, i as u64);
+        assert_eq!(page.meta.status, 200);
+        assert_eq!(page.meta.request_id, Some(format!("req-{}", i + 1)));
+        assert!(!page.meta.url.contains("apiKey"));
+        assert_eq!(page.response.status.as_deref(), Some("OK"));
+    }
+    assert!(pages[0]
+        .meta
+        .url
+        .contains("/v2/aggs/ticker/AAPL/range/1/day"));
+    assert!(pages[1].meta.url.contains("cursor=page2"));
+    let counts: Vec<_> = pages.iter().map(|p| p.response.results_count).collect();
+    assert_eq!(counts, vec![Some(2), Some(2), Some(1)]);
+    assert!(pages[2].response.next_url.is_none());
+}
+
+/// Test MaxPages mode for both item and page streams
+#[tokio::test]
+async fn test_mock_pagination_max_pages() {
+    use futures::StreamExt;
+
+    let mock_server = MockServer::start().await;
+    mount_three_pages(&mock_server).await;
+    let client = create_mock_client(&mock_server);
+
+    let bars = client
+        .stream_with_mode(aggs_request(), PaginationMode::MaxPages(2))
+        .collect_all()
+        .await
+        .unwrap();
+    assert_eq!(bars.len(), 4);
+
+    let pages: Vec<_> = client
+        .stream_pages_with_mode(aggs_request(), PaginationMode::MaxPages(1))
+        .collect()
+        .await;
+    assert_eq!(pages.len(), 1);
+    assert!(pages[0].as_ref().unwrap().response.next_url.is_some());
+}
+
+/// Test that a page stream under MaxItems ends after the page reaching the limit
+#[tokio::test]
+async fn test_mock_stream_pages_max_items() {
+    use futures::StreamExt;
+
+    let mock_server = MockServer::start().await;
+    mount_three_pages(&mock_server).await;
+    let client = create_mock_client(&mock_server);
+
+    let pages: Vec<_> = client
+        .stream_pages_with_mode(aggs_request(), PaginationMode::MaxItems(3))
+        .collect()
+        .await;
+    assert_eq!(pages.len(), 2);
+}
+
+/// Test that Deadline mode stops requesting pages and can be resumed
+#[tokio::test]
+async fn test_mock_pagination_deadline() {
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    let mock_server = MockServer::start().await;
+    mount_three_pages(&mock_server).await;
+    let client = create_mock_client(&mock_server);
+
+    let mut stream = client.stream_with_mode(
+        aggs_request(),
+        PaginationMode::Deadline(Duration::from_millis(200)),
+    );
+    assert_eq!(stream.next().await.unwrap().unwrap().open, px(1.0));
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    // The rest of the fetched page is still yielded
+    assert_eq!(stream.next().await.unwrap().unwrap().open, px(2.0));
+    assert!(stream.next().await.is_none());
+
+    let checkpoint = stream.checkpoint();
+    assert!(!checkpoint.finished);
+    assert!(checkpoint
+        .page_url
+        .as_deref()
+        .unwrap()
+        .contains("cursor=page2"));
+
+    let rest = client
+        .resume_stream(aggs_request(), checkpoint)
+        .collect_all()
+        .await
+        .unwrap();
+    let opens: Vec<Price> = rest.iter().map(|bar| bar.open).collect();
+    assert_eq!(opens, vec![px(3.0), px(4.0), px(5.0)]);
+
+    let pages: Vec<_> = client
+        .stream_pages_with_mode(aggs_request(), PaginationMode::Deadline(Duration::ZERO))
+        .collect()
+        .await;
+    assert!(pages.is_empty());
+}
+
+fn create_prefetch_client(mock_server: &MockServer, prefetch: PrefetchConfig) -> RestClient {
+    let config = RestConfig {
+        base_url: Url::parse(&mock_server.uri()).unwrap(),
+        retry_policy: RetryPolicy::none(),
+        ..RestConfig::new("test-api-key").with_prefetch(prefetch)
+    };
+    RestClient::new(config).unwrap()
+}
+
+/// Test that pages are requested ahead of the consumer, up to the depth
+#[tokio::test]
+async fn test_mock_prefetch_fetches_ahead() {
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    for (pages, expected_requests) in [(1, 2), (2, 3)] {
+        let mock_server = MockServer::start().await;
+        mount_three_pages(&mock_server).await;
+        let client = create_prefetch_client(&mock_server, PrefetchConfig::new(pages));
+
+        // Each item polled lets the next page request start
+        let mut stream = client.stream(aggs_request());
+        assert_eq!(stream.next().await.unwrap().unwrap().open, px(1.0));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(stream.next().await.unwrap().unwrap().open, px(2.0));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), expected_requests, "depth {pages}");
+
+        // The checkpoint still tracks the consumer, not the prefetched pages
+        let checkpoint = stream.checkpoint();
+        assert!(checkpoint
+            .page_url
+            .as_deref()
+            .unwrap()
+            .contains("cursor=page2"));
+        assert_eq!(checkpoint.page_offset, 0);
+        assert_eq!(checkpoint.items_yielded, 2);
+
+        let rest: Vec<Price> = stream.map(|bar| bar.unwrap().open).collect().await;
+        assert_eq!(rest, vec![px(3.0), px(4.0), px(5.0)]);
+    }
+}
+
+/// Test that prefetching pauses at the buffered item limit
+#[tokio::test]
+async fn test_mock_prefetch_item_limit() {
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    let mock_server = MockServer::start().await;
+    mount_three_pages(&mock_server).await;
+    let prefetch = PrefetchConfig::new(2).with_max_buffered_items(2);
+    let client = create_prefetch_client(&mock_server, prefetch);
+
+    let mut stream = client.stream(aggs_request());
+    stream.next().await.unwrap().unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    stream.next().await.unwrap().unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    // The two items of page 2 reach the limit, so page 3 waits
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+
+    let bars = stream.collect::<Vec<_>>().await;
+    assert_eq!(bars.len(), 3);
+}
+
+/// Test that an error on a prefetched page arrives after the earlier items
+#[tokio::test]
+async fn test_mock_prefetch_error_in_order() {
+    use futures::StreamExt;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("cursor", "page2"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    mount_three_pages(&mock_server).await;
+    let client = create_prefetch_client(&mock_server, PrefetchConfig::new(2));
+
+    let mut stream = client.stream(aggs_request());
+    assert_eq!(stream.next().await.unwrap().unwrap().open, px(1.0));
+    assert_eq!(stream.next().await.unwrap().unwrap().open, px(2.0));
+    assert!(stream.next().await.unwrap().is_err());
+    assert!(stream.next().await.is_none());
+
+    let checkpoint = stream.checkpoint();
+    assert!(checkpoint
+        .page_url
+        .as_deref()
+        .unwrap()
+        .contains("cursor=page2"));
+}
+
+/// Test that a page stream fetches the next page while the consumer holds one
+#[tokio::test]
+async fn test_mock_prefetch_stream_pages() {
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    let mock_server = MockServer::start().await;
+    mount_three_pages(&mock_server).await;
+    let client = create_prefetch_client(&mock_server, PrefetchConfig::new(1));
+
+    let mut pages = client.stream_pages(aggs_request());
+    let first = pages.next().await.unwrap().unwrap();
+    assert_eq!(first.meta.index, 0);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+
+    let rest: Vec<u64> = pages.map(|page| page.unwrap().meta.index).collect().await;
+    assert_eq!(rest, vec![1, 2]);
+}
+
+/// Mount one page of trades for the sub-range starting at `gte`
+async fn mount_trades(mock_server: &MockServer, gte: i64, timestamps: &[i64], next: Option<&str>) {
+    let results: Vec<_> = timestamps
+        .iter()
+        .map(|&t| serde_json::json!({"price": 150.0, "size": 100, "sip_timestamp": t}))
+        .collect();
+    let next_url =
+        next.map(|cursor| format!("{}/v3/trades/AAPL?cursor={cursor}", mock_server.uri()));
+    Mock::given(method("GET"))
+        .and(path("/v3/trades/AAPL"))
+        .and(query_param("timestamp.gte", gte.to_string()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(
+            serde_json::json!({"status": "OK", "results": results, "next_url": next_url}),
+        ))
+        .mount(mock_server)
+        .await;
+}
+
+/// Test that sharded sub-ranges are merged in timestamp order
+#[tokio::test]
+async fn test_mock_sharded_trades_in_timestamp_order() {
+    use futures::StreamExt;
+
+    let mock_server = MockServer::start().await;
+    mount_trades(&mock_server, 1000, &[1010, 1020], Some("more")).await;
+    mount_trades(&mock_server, 1100, &[1110, 1120], None).await;
+    mount_trades(&mock_server, 1200, &[1210, 1220], None).await;
+    mount_trades(&mock_server, 1300, &[1310, 1320], None).await;
+    Mock::given(method("GET"))
+        .and(query_param("cursor", "more"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "OK",
+            "results": [{"price": 150.0, "size": 100, "sip_timestamp": 1050}]
+        })))
+        .mount(&mock_server)
+        .await;
+    let client = create_mock_client(&mock_server);
+
+    let request = GetTradesRequest::new("AAPL")
+        .timestamp_gte("1000")
+        .timestamp_lt("1400");
+    let stream = client
+        .stream_sharded(request, ShardConfig::new(4).with_concurrency(2))
+        .unwrap();
+    assert_eq!(stream.shard_count(), 4);
+
+    let timestamps: Vec<i64> = stream
+        .map(|trade| trade.unwrap().sip_timestamp.unwrap())
+        .collect()
+        .await;
+    assert_eq!(
+        timestamps,
+        vec![1010, 1020, 1050, 1110, 1120, 1210, 1220, 1310, 1320]
+    );
+
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 5);
+    for request in requests
+        .iter()
+        .filter(|r| r.url.query().is_some_and(|q| q.contains("timestamp")))
+    {
+        let query: Vec<(String, String)> = request.url.query_pairs().into_owned().collect();
+        let has = |key: &str, value: &str| query.iter().any(|(k, v)| k == key && v == value);
+        assert!(has("order", "asc"));
+        assert!(has("sort", "timestamp"));
+        let gte: i64 = query
+            .iter()
+            .find(|(k, _)| k == "timestamp.gte")
+            .unwrap()
+            .1
+            .parse()
+            .unwrap();
+        assert!(has("timestamp.lt", &(gte + 100).to_string()));
+    }
+}
+
+/// Test that aggregate ranges are split to stay under 50,000 bars per request
+#[tokio::test]
+async fn test_mock_sharded_aggs_split_at_bar_limit() {
+    use futures::StreamExt;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"/v2/aggs/ticker/AAPL/range/1/minute/.*"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(r#"{"status": "OK", "results": []}"#),
+        )
+        .mount(&mock_server)
+        .await;
+    let client = create_mock_client(&mock_server);
+
+    // 100 days of minute bars is 144,000 bars
+    let request = GetAggsRequest::new("AAPL")
+        .timespan(Timespan::Minute)
+        .from("2024-01-01")
+        .to("2024-04-09");
+    let stream = client.stream_sharded(request, ShardConfig::new(1)).unwrap();
+    assert_eq!(stream.shard_count(), 3);
+    assert_eq!(stream.count().await, 0);
+
+    // Date bounds stay dates, split on whole days of at most 34 days
+    let mut ranges: Vec<(String, String)> = mock_server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| {
+            let parts: Vec<&str> = r.url.path().rsplit('/').take(2).collect();
+            (parts[1].to_string(), parts[0].to_string())
+        })
+        .collect();
+    ranges.sort();
+    assert_eq!(
+        ranges,
+        [
+            ("2024-01-01", "2024-02-03"),
+            ("2024-02-04", "2024-03-08"),
+            ("2024-03-09", "2024-04-09"),
+        ]
+        .map(|(from, to)| (from.to_string(), to.to_string()))
+    );
+}
+
+/// Test that a failing sub-range ends the sharded stream after earlier items
+#[tokio::test]
+async fn test_mock_sharded_error_stops_stream() {
+    use futures::StreamExt;
+
+    let mock_server = MockServer::start().await;
+    mount_trades(&mock_server, 1000, &[1010], None).await;
+    Mock::given(method("GET"))
+        .and(query_param("timestamp.gte", "1100"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock_server)
+        .await;
+    mount_trades(&mock_server, 1200, &[1210], None).await;
+    let config = RestConfig {
+        base_url: Url::parse(&mock_server.uri()).unwrap(),
+        retry_policy: RetryPolicy::none(),
+        ..RestConfig::new("test-api-key")
+    };
+    let client = RestClient::new(config).unwrap();
+
+    let request = GetTradesRequest::new("AAPL")
+        .timestamp_gte("1000")
+        .timestamp_lt("1300");
+    let mut stream = client.stream_sharded(request, ShardConfig::new(3)).unwrap();
+    assert_eq!(
+        stream.next().await.unwrap().unwrap().sip_timestamp,
+        Some(1010)
+    );
+    assert!(stream.next().await.unwrap().is_err());
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_mock_sharded_needs_bounded_range() {
+    let mock_server = MockServer::start().await;
+    let client = create_mock_client(&mock_server);
+
+    let request = GetTradesRequest::new("AAPL").timestamp_gte("2024-01-01");
+    assert!(matches!(
+        client.stream_sharded(request.clone(), ShardConfig::new(4)),
+        Err(MassiveError::InvalidArgument(_))
+    ));
+    let request = request.timestamp_lt("2024-01-02");
+    assert!(matches!(
+        client.stream_sharded(request, ShardConfig::new(4).with_concurrency(0)),
+        Err(MassiveError::InvalidArgument(_))
+    ));
+}
+
 // ============================================================================
 // Request Building Tests
 // ============================================================================
@@ -828,22 +1562,22 @@ fn test_aggregate_bar_methods() {
 
     let bar = AggregateBar {
         ticker: None,
-        open: 100.0,
-        high: 110.0,
-        low: 95.0,
-        close: 105.0,
-        volume: 1000000.0,
-        vwap: Some(102.5),
+        open: px(100.0),
+        high: px(110.0),
+        low: px(95.0),
+        close: px(105.0),
+        volume: Some(1000000.0),
+        vwap: Some(px(102.5)),
         timestamp: 1703001234567,
         transactions: Some(5000),
         otc: false,
     };
 
     // Test range
-    assert_eq!(bar.range(), 15.0);
+    assert_eq!(bar.range(), px(15.0));
 
     // Test body
-    assert_eq!(bar.body(), 5.0);
+    assert_eq!(bar.body(), px(5.0));
 
     // Test bullish/bearish
     assert!(bar.is_bullish());
@@ -852,26 +1586,26 @@ fn test_aggregate_bar_methods() {
     // Test doji
     let doji = AggregateBar {
         ticker: None,
-        open: 100.0,
-        high: 101.0,
-        low: 99.0,
-        close: 100.0,
-        volume: 1000.0,
+        open: px(100.0),
+        high: px(101.0),
+        low: px(99.0),
+        close: px(100.0),
+        volume: Some(1000.0),
         vwap: None,
         timestamp: 1703001234567,
         transactions: None,
         otc: false,
     };
-    assert!(doji.is_doji(0.01));
+    assert!(doji.is_doji(px(0.01)));
 
     // Test bearish bar
     let bearish = AggregateBar {
         ticker: None,
-        open: 105.0,
-        high: 110.0,
-        low: 95.0,
-        close: 100.0,
-        volume: 1000000.0,
+        open: px(105.0),
+        high: px(110.0),
+        low: px(95.0),
+        close: px(100.0),
+        volume: Some(1000000.0),
         vwap: None,
         timestamp: 1703001234567,
         transactions: None,
@@ -889,10 +1623,10 @@ fn test_quote_methods() {
     let quote = Quote {
         ticker: None,
         ask_exchange: Some(4),
-        ask_price: 100.10,
+        ask_price: px(100.10),
         ask_size: 100,
         bid_exchange: Some(7),
-        bid_price: 100.00,
+        bid_price: px(100.00),
         bid_size: 200,
         conditions: vec![],
         sip_timestamp: None,
@@ -902,23 +1636,23 @@ fn test_quote_methods() {
         tape: None,
     };
 
-    assert!((quote.spread() - 0.10).abs() < 0.001);
-    assert!((quote.mid_price() - 100.05).abs() < 0.001);
+    assert!((quote.spread() - px(0.10)).abs() < px(0.001));
+    assert!((quote.mid_price() - px(100.05)).abs() < px(0.001));
     assert!(!quote.is_crossed());
     assert!(!quote.is_locked());
 
     // Test crossed market
     let crossed = Quote {
-        ask_price: 99.0,
-        bid_price: 100.0,
+        ask_price: px(99.0),
+        bid_price: px(100.0),
         ..quote.clone()
     };
     assert!(crossed.is_crossed());
 
     // Test locked market (need same prices)
     let locked = Quote {
-        ask_price: 100.0,
-        bid_price: 100.0,
+        ask_price: px(100.0),
+        bid_price: px(100.0),
         ..quote
     };
     assert!(locked.is_locked());
@@ -934,7 +1668,7 @@ fn test_trade_value() {
         id: Some("test-trade".to_string()),
         conditions: vec![],
         exchange: Some(4),
-        price: 150.50,
+        price: px(150.50),
         sip_timestamp: None,
         participant_timestamp: None,
         trf_timestamp: None,
@@ -944,7 +1678,7 @@ fn test_trade_value() {
         reporting_facility: None,
     };
 
-    assert_eq!(trade.value(), 15050.0);
+    assert_eq!(trade.value(), px(15050.0));
 }
 
 // ============================================================================
@@ -985,7 +1719,7 @@ fn test_rest_config_defaults() {
     assert_eq!(config.connect_timeout, std::time::Duration::from_secs(10));
     assert_eq!(config.request_timeout, std::time::Duration::from_secs(30));
     assert_eq!(config.pagination, PaginationMode::Auto);
-    assert_eq!(config.max_retries, 3);
+    assert_eq!(config.retry_policy.max_attempts, 3);
     assert!(!config.trace);
 }
 
@@ -1007,3 +1741,334 @@ fn test_rest_config_builder() {
     assert!(config.trace);
     assert_eq!(config.user_agent, Some("my-app/1.0".to_string()));
 }
+
+// ============================================================================
+// Metrics Tests
+// ============================================================================
+
+/// A recorded metric: name, value, and owned tags.
+type RecordedMetric = (&'static str, u64, Vec<(String, String)>);
+
+/// Metrics sink that records counter and timing names with their tags.
+#[derive(Default)]
+struct RecordingMetrics {
+    events: std::sync::Mutex<Vec<RecordedMetric>>,
+}
+
+impl RecordingMetrics {
+    fn total(&self, name: &str) -> u64 {
+        let events = self.events.lock().unwrap();
+        events.iter().filter(|e| e.0 == name).map(|e| e.1).sum()
+    }
+
+    fn tags(&self, name: &str) -> Vec<Vec<(String, String)>> {
+        let events = self.events.lock().unwrap();
+        events
+            .iter()
+            .filter(|e| e.0 == name)
+            .map(|e| e.2.clone())
+            .collect()
+    }
+
+    fn record(&self, name: &'static str, value: u64, tags: &[(&'static str, &str)]) {
+        let tags = tags
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        self.events.lock().unwrap().push((name, value, tags));
+    }
+}
+
+impl massive_rs::metrics::MetricsSink for RecordingMetrics {
+    fn counter(&self, name: &'static str, value: u64, tags: &[(&'static str, &str)]) {
+        self.record(name, value, tags);
+    }
+
+    fn gauge(&self, name: &'static str, value: i64, tags: &[(&'static str, &str)]) {
+        self.record(name, value as u64, tags);
+    }
+
+    fn histogram(&self, name: &'static str, _value: f64, tags: &[(&'static str, &str)]) {
+        self.record(name, 1, tags);
+    }
+}
+
+/// Test that retries, latency, and bytes are reported to the sink and stats
+#[tokio::test]
+async fn test_mock_metrics_retry_and_latency() {
+    use massive_rs::metrics::names;
+    use std::sync::Arc;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+
+    let body = r#"{"status": "OK", "results": {"T": "AAPL", "p": 150.0, "s": 100, "t": 1703001234567, "x": 4}}"#;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(&mock_server)
+        .await;
+
+    let metrics = Arc::new(RecordingMetrics::default());
+    let config = RestConfig::new("test-api-key")
+        .with_base_url(Url::parse(&mock_server.uri()).unwrap())
+        .with_metrics(metrics.clone());
+    let client = RestClient::new(config).unwrap();
+
+    client
+        .execute(GetLastTradeRequest::new("AAPL"))
+        .await
+        .expect("retry should succeed");
+
+    let stats = client.stats();
+    assert_eq!(stats.requests_sent, 2);
+    assert_eq!(stats.retries, 1);
+    assert_eq!(stats.bytes_received, body.len() as u64);
+    assert_eq!(stats.request_errors, 0);
+
+    assert_eq!(metrics.total(names::REST_RETRIES), 1);
+    assert_eq!(metrics.total(names::REST_REQUEST_LATENCY), 2);
+    assert_eq!(metrics.total(names::REST_BYTES_RECEIVED), body.len() as u64);
+
+    let tags = metrics.tags(names::REST_REQUESTS);
+    assert_eq!(tags.len(), 2);
+    assert!(tags[0].contains(&("status".into(), "503".into())));
+    assert!(tags[1].contains(&("status".into(), "200".into())));
+    assert!(tags[1].contains(&("endpoint".into(), "/v2/last/trade/{ticker}".into())));
+}
+
+/// Test that 429 responses are counted as rate limits
+#[tokio::test]
+async fn test_mock_metrics_rate_limited() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429))
+        .mount(&mock_server)
+        .await;
+
+    let config = RestConfig::new("test-api-key")
+        .with_base_url(Url::parse(&mock_server.uri()).unwrap())
+        .with_retry_policy(RetryPolicy::none());
+    let client = RestClient::new(config).unwrap();
+    let _ = client.execute(GetLastTradeRequest::new("AAPL")).await;
+    let _ = client
+        .clone()
+        .execute(GetLastTradeRequest::new("MSFT"))
+        .await;
+
+    // Counters are shared across clones
+    let stats = client.stats();
+    assert_eq!(stats.rate_limits, 2);
+    assert_eq!(stats.requests_sent, 2);
+}
+
+#[tokio::test]
+async fn test_mock_priority_overtakes_pagination() {
+    use futures::StreamExt;
+    use massive_rs::rest::RequestPriority;
+    use std::time::Duration;
+
+    let mock_server = MockServer::start().await;
+
+    let bars_json = r#"{
+        "status": "OK",
+        "results": [{"o": 150.0, "h": 155.0, "l": 148.0, "c": 153.0, "v": 1000000, "t": 1703001234567}]
+    }"#;
+    let quote_json = r#"{
+        "status": "OK",
+        "results": {"X": 4, "P": 153.50, "S": 200, "x": 7, "p": 153.40, "s": 300, "t": 1703001234567890123}
+    }"#;
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"/v2/aggs/ticker/.*"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(bars_json)
+                .set_delay(Duration::from_millis(200)),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"/v2/last/nbbo/.*"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(quote_json))
+        .mount(&mock_server)
+        .await;
+
+    let config = RestConfig::new("test-api-key")
+        .with_base_url(Url::parse(&mock_server.uri()).unwrap())
+        .with_max_in_flight(1);
+    let client = RestClient::new(config).unwrap();
+    let aggs = |ticker: &str| {
+        GetAggsRequest::new(ticker)
+            .multiplier(1)
+            .timespan(Timespan::Day)
+            .from("2024-01-01")
+            .to("2024-01-31")
+    };
+
+    // Occupy the only slot, then queue a backfill ahead of a quote
+    let busy = tokio::spawn({
+        let client = client.clone();
+        let request = aggs("MSFT");
+        async move { client.execute(request).await }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let backfill = tokio::spawn({
+        let client = client.clone();
+        let request = aggs("AAPL");
+        async move { client.stream(request).collect::<Vec<_>>().await }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let quote = client
+        .execute_with_priority(GetLastQuoteRequest::new("AAPL"), RequestPriority::High)
+        .await
+        .unwrap();
+    assert!(quote.results.is_some());
+
+    busy.await.unwrap().unwrap();
+    assert_eq!(backfill.await.unwrap().len(), 1);
+
+    let paths: Vec<_> = mock_server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| r.url.path().to_string())
+        .collect();
+    assert!(paths[0].contains("/MSFT/"));
+    assert!(paths[1].starts_with("/v2/last/nbbo/"));
+    assert!(paths[2].contains("/AAPL/"));
+}
+
+#[tokio::test]
+async fn test_mock_retry_sleep_releases_slot() {
+    use std::time::{Duration, Instant};
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"/v2/last/trade/.*"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    let trade_json = r#"{"status": "OK", "results": {"T": "AAPL", "p": 150.0, "s": 100, "t": 1703001234567, "x": 4}}"#;
+    Mock::given(method("GET"))
+        .and(path_regex(r"/v2/last/trade/.*"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(trade_json))
+        .mount(&mock_server)
+        .await;
+    let quote_json = r#"{
+        "status": "OK",
+        "results": {"X": 4, "P": 153.50, "S": 200, "x": 7, "p": 153.40, "s": 300, "t": 1703001234567890123}
+    }"#;
+    Mock::given(method("GET"))
+        .and(path_regex(r"/v2/last/nbbo/.*"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(quote_json))
+        .mount(&mock_server)
+        .await;
+
+    let config = RestConfig::new("test-api-key")
+        .with_base_url(Url::parse(&mock_server.uri()).unwrap())
+        .with_max_in_flight(1);
+    let client = RestClient::new(config).unwrap();
+
+    let start = Instant::now();
+    let retried = tokio::spawn({
+        let client = client.clone();
+        async move { client.execute(GetLastTradeRequest::new("AAPL")).await }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // The slot is free while the trade request sleeps on Retry-After
+    client
+        .execute(GetLastQuoteRequest::new("AAPL"))
+        .await
+        .unwrap();
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    retried.await.unwrap().unwrap();
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_mock_max_in_flight_zero_rejected() {
+    let config = RestConfig::new("test-api-key").with_max_in_flight(0);
+    assert!(matches!(
+        RestClient::new(config),
+        Err(MassiveError::InvalidArgument(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_mock_prefetch_zero_rejected() {
+    for prefetch in [
+        PrefetchConfig::new(0),
+        PrefetchConfig::new(1).with_max_buffered_items(0),
+    ] {
+        let config = RestConfig::new("test-api-key").with_prefetch(prefetch);
+        assert!(matches!(
+            RestClient::new(config),
+            Err(MassiveError::InvalidArgument(_))
+        ));
+    }
+}
+
+#[cfg(feature = "blocking")]
+#[tokio::test]
+async fn test_mock_blocking_client_pagination() {
+    let mock_server = MockServer::start().await;
+    let mock_uri = mock_server.uri();
+
+    let page1_json = format!(
+        r#"{{
+        "status": "OK",
+        "results": [{{"o": 150.0, "h": 155.0, "l": 148.0, "c": 153.0, "v": 1000000, "t": 1703001234567}}],
+        "next_url": "{}/v2/aggs/ticker/AAPL/range/1/day/2024-01-01/2024-01-31?cursor=page2"
+    }}"#,
+        mock_uri
+    );
+    let page2_json = r#"{
+        "status": "OK",
+        "results": [{"o": 153.0, "h": 158.0, "l": 152.0, "c": 156.0, "v": 1200000, "t": 1703087634567}]
+    }"#;
+
+    Mock::given(method("GET"))
+        .and(query_param("cursor", "page2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(page2_json))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"/v2/aggs/ticker/AAPL/range/.*"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(page1_json))
+        .mount(&mock_server)
+        .await;
+
+    // The blocking client must run outside the async runtime
+    let closes = std::thread::spawn(move || {
+        let config = RestConfig::new("test-api-key").with_base_url(Url::parse(&mock_uri).unwrap());
+        let client = massive_rs::blocking::RestClient::new(config).unwrap();
+        let request = GetAggsRequest::new("AAPL")
+            .multiplier(1)
+            .timespan(Timespan::Day)
+            .from("2024-01-01")
+            .to("2024-01-31");
+
+        let first = client.execute(request.clone()).unwrap();
+        assert_eq!(first.results.len(), 1);
+
+        let bars = client.stream(request).collect_all().unwrap();
+        assert_eq!(client.stats().requests_sent, 3);
+        bars.iter().map(|bar| bar.close).collect::<Vec<_>>()
+    });
+    let closes = tokio::task::spawn_blocking(move || closes.join().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(closes, vec![px(153.0), px(156.0)]);
+}
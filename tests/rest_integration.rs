@@ -8,7 +8,7 @@
 mod common;
 
 use common::{
-    create_rest_client, has_api_key, test_date, test_date_range, TEST_TICKER, TEST_TICKER_ALT,
+    create_rest_client, has_api_key, px, test_date, test_date_range, TEST_TICKER, TEST_TICKER_ALT,
 };
 use futures::StreamExt;
 use massive_rs::config::PaginationMode;
@@ -60,10 +60,10 @@ async fn test_get_aggregates() {
 
             // Verify first bar has valid OHLCV data
             let first = &aggs.results[0];
-            assert!(first.open > 0.0, "Open price should be positive");
-            assert!(first.high > 0.0, "High price should be positive");
-            assert!(first.low > 0.0, "Low price should be positive");
-            assert!(first.close > 0.0, "Close price should be positive");
+            assert!(first.open > px(0.0), "Open price should be positive");
+            assert!(first.high > px(0.0), "High price should be positive");
+            assert!(first.low > px(0.0), "Low price should be positive");
+            assert!(first.close > px(0.0), "Close price should be positive");
             assert!(first.high >= first.low, "High should be >= Low");
             assert!(first.high >= first.open, "High should be >= Open");
             assert!(first.high >= first.close, "High should be >= Close");
@@ -144,7 +144,7 @@ async fn test_get_previous_close() {
 
             if !prev_close.results.is_empty() {
                 let bar = &prev_close.results[0];
-                assert!(bar.close > 0.0, "Close price should be positive");
+                assert!(bar.close > px(0.0), "Close price should be positive");
                 println!("Previous close for {}: ${:.2}", TEST_TICKER, bar.close);
             }
         }
@@ -176,8 +176,8 @@ async fn test_get_daily_open_close() {
         Ok(daily) => {
             assert_eq!(daily.status, "OK", "Expected OK status");
             assert_eq!(daily.symbol, TEST_TICKER, "Symbol should match");
-            assert!(daily.open > 0.0, "Open price should be positive");
-            assert!(daily.close > 0.0, "Close price should be positive");
+            assert!(daily.open > px(0.0), "Open price should be positive");
+            assert!(daily.close > px(0.0), "Close price should be positive");
             assert!(daily.high >= daily.low, "High should be >= Low");
 
             println!(
@@ -232,12 +232,15 @@ async fn test_get_trades() {
 
             if !trades.results.is_empty() {
                 let first_trade = &trades.results[0];
-                assert!(first_trade.price > 0.0, "Trade price should be positive");
+                assert!(
+                    first_trade.price > px(0.0),
+                    "Trade price should be positive"
+                );
                 assert!(first_trade.size > 0, "Trade size should be positive");
 
                 // Test the value() method
                 let value = first_trade.value();
-                assert!(value > 0.0, "Trade value should be positive");
+                assert!(value > px(0.0), "Trade value should be positive");
 
                 println!(
                     "Fetched {} trades, first trade: ${:.2} x {} (value: ${:.2})",
@@ -277,7 +280,7 @@ async fn test_get_last_trade() {
             );
 
             if let Some(trade) = last_trade.results {
-                assert!(trade.price > 0.0, "Trade price should be positive");
+                assert!(trade.price > px(0.0), "Trade price should be positive");
                 println!("Last trade for {}: ${:.2}", TEST_TICKER, trade.price);
             }
         }
@@ -320,11 +323,11 @@ async fn test_get_quotes() {
             if !quotes.results.is_empty() {
                 let first_quote = &quotes.results[0];
                 assert!(
-                    first_quote.bid_price >= 0.0,
+                    first_quote.bid_price >= px(0.0),
                     "Bid price should be non-negative"
                 );
                 assert!(
-                    first_quote.ask_price >= 0.0,
+                    first_quote.ask_price >= px(0.0),
                     "Ask price should be non-negative"
                 );
 
@@ -342,9 +345,10 @@ async fn test_get_quotes() {
                 );
 
                 // Verify helper method calculations
-                if first_quote.ask_price > 0.0 && first_quote.bid_price > 0.0 {
+                if first_quote.ask_price > px(0.0) && first_quote.bid_price > px(0.0) {
                     assert!(
-                        (mid - (first_quote.bid_price + first_quote.ask_price) / 2.0).abs() < 0.001,
+                        (mid - (first_quote.bid_price + first_quote.ask_price) / px(2.0)).abs()
+                            < px(0.001),
                         "Mid price calculation should be correct"
                     );
                 }
@@ -379,8 +383,14 @@ async fn test_get_last_quote() {
             );
 
             if let Some(quote) = last_quote.results {
-                assert!(quote.bid_price >= 0.0, "Bid price should be non-negative");
-                assert!(quote.ask_price >= 0.0, "Ask price should be non-negative");
+                assert!(
+                    quote.bid_price >= px(0.0),
+                    "Bid price should be non-negative"
+                );
+                assert!(
+                    quote.ask_price >= px(0.0),
+                    "Ask price should be non-negative"
+                );
 
                 println!(
                     "Last quote for {}: Bid=${:.2}, Ask=${:.2}",
@@ -453,8 +463,8 @@ async fn test_get_ticker_snapshot() {
                 assert_eq!(ticker_snapshot.ticker, TEST_TICKER, "Ticker should match");
 
                 if let Some(day) = ticker_snapshot.day {
-                    assert!(day.open > 0.0, "Open should be positive");
-                    assert!(day.close > 0.0, "Close should be positive");
+                    assert!(day.open > px(0.0), "Open should be positive");
+                    assert!(day.close > px(0.0), "Close should be positive");
                     println!(
                         "Snapshot for {}: Open=${:.2}, Close=${:.2}",
                         TEST_TICKER, day.open, day.close
@@ -920,8 +930,8 @@ async fn test_aggregate_bar_methods() {
                 i, range, body, is_bullish, is_bearish
             );
 
-            assert!(range >= 0.0, "Range should be non-negative");
-            assert!(body.abs() <= range + 0.001, "Body should be <= range");
+            assert!(range >= px(0.0), "Range should be non-negative");
+            assert!(body.abs() <= range + px(0.001), "Body should be <= range");
 
             // A bar should be bullish XOR bearish (or neither if doji)
             assert!(
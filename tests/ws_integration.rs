@@ -124,10 +124,15 @@ async fn test_ws_subscribe_trade() {
 
     // Subscribe to trades
     let subscription = Subscription::trade(TEST_TICKER);
-    let result = timeout(WS_SHORT_TIMEOUT, handle.subscribe(&[subscription.clone()])).await;
+    let result = timeout(
+        WS_SHORT_TIMEOUT,
+        handle.subscribe(std::slice::from_ref(&subscription)),
+    )
+    .await;
 
     match result {
-        Ok(Ok(())) => {
+        Ok(Ok(report)) => {
+            assert!(report.is_success(), "Topics not confirmed: {:?}", report);
             let subs = handle.subscriptions();
             assert!(
                 subs.contains(&subscription),
@@ -165,10 +170,15 @@ async fn test_ws_subscribe_quote() {
         .expect("Connection failed");
 
     let subscription = Subscription::quote(TEST_TICKER);
-    let result = timeout(WS_SHORT_TIMEOUT, handle.subscribe(&[subscription.clone()])).await;
+    let result = timeout(
+        WS_SHORT_TIMEOUT,
+        handle.subscribe(std::slice::from_ref(&subscription)),
+    )
+    .await;
 
     match result {
-        Ok(Ok(())) => {
+        Ok(Ok(report)) => {
+            assert!(report.is_success(), "Topics not confirmed: {:?}", report);
             let subs = handle.subscriptions();
             assert!(
                 subs.contains(&subscription),
@@ -206,10 +216,15 @@ async fn test_ws_subscribe_minute_agg() {
         .expect("Connection failed");
 
     let subscription = Subscription::minute_agg(TEST_TICKER);
-    let result = timeout(WS_SHORT_TIMEOUT, handle.subscribe(&[subscription.clone()])).await;
+    let result = timeout(
+        WS_SHORT_TIMEOUT,
+        handle.subscribe(std::slice::from_ref(&subscription)),
+    )
+    .await;
 
     match result {
-        Ok(Ok(())) => {
+        Ok(Ok(report)) => {
+            assert!(report.is_success(), "Topics not confirmed: {:?}", report);
             let subs = handle.subscriptions();
             assert!(
                 subs.contains(&subscription),
@@ -259,7 +274,8 @@ async fn test_ws_subscribe_multiple_tickers() {
     let result = timeout(WS_SHORT_TIMEOUT, handle.subscribe(&subscriptions)).await;
 
     match result {
-        Ok(Ok(())) => {
+        Ok(Ok(report)) => {
+            assert!(report.is_success(), "Topics not confirmed: {:?}", report);
             let subs = handle.subscriptions();
             for sub in &subscriptions {
                 assert!(subs.contains(sub), "Subscription {} should be tracked", sub);
@@ -300,10 +316,15 @@ async fn test_ws_subscribe_wildcard() {
 
     // Subscribe to all trades
     let subscription = Subscription::all_trades();
-    let result = timeout(WS_SHORT_TIMEOUT, handle.subscribe(&[subscription.clone()])).await;
+    let result = timeout(
+        WS_SHORT_TIMEOUT,
+        handle.subscribe(std::slice::from_ref(&subscription)),
+    )
+    .await;
 
     match result {
-        Ok(Ok(())) => {
+        Ok(Ok(report)) => {
+            assert!(report.is_success(), "Topics not confirmed: {:?}", report);
             println!("Successfully subscribed to all trades (wildcard)");
         }
         Ok(Err(e)) => {
@@ -342,10 +363,13 @@ async fn test_ws_unsubscribe() {
 
     // Subscribe first
     let subscription = Subscription::trade(TEST_TICKER);
-    timeout(WS_SHORT_TIMEOUT, handle.subscribe(&[subscription.clone()]))
-        .await
-        .expect("Subscribe timed out")
-        .expect("Subscribe failed");
+    timeout(
+        WS_SHORT_TIMEOUT,
+        handle.subscribe(std::slice::from_ref(&subscription)),
+    )
+    .await
+    .expect("Subscribe timed out")
+    .expect("Subscribe failed");
 
     assert!(
         handle.subscriptions().contains(&subscription),
@@ -355,12 +379,13 @@ async fn test_ws_unsubscribe() {
     // Unsubscribe
     let result = timeout(
         WS_SHORT_TIMEOUT,
-        handle.unsubscribe(&[subscription.clone()]),
+        handle.unsubscribe(std::slice::from_ref(&subscription)),
     )
     .await;
 
     match result {
-        Ok(Ok(())) => {
+        Ok(Ok(report)) => {
+            assert!(report.is_success(), "Topics not confirmed: {:?}", report);
             assert!(
                 !handle.subscriptions().contains(&subscription),
                 "Should be unsubscribed"
@@ -401,10 +426,13 @@ async fn test_ws_subscribe_unsubscribe_cycle() {
     // Subscribe -> Unsubscribe -> Subscribe -> Unsubscribe
     for i in 0..3 {
         // Subscribe
-        timeout(WS_SHORT_TIMEOUT, handle.subscribe(&[subscription.clone()]))
-            .await
-            .expect("Subscribe timed out")
-            .expect("Subscribe failed");
+        timeout(
+            WS_SHORT_TIMEOUT,
+            handle.subscribe(std::slice::from_ref(&subscription)),
+        )
+        .await
+        .expect("Subscribe timed out")
+        .expect("Subscribe failed");
 
         assert!(
             handle.subscriptions().contains(&subscription),
@@ -415,7 +443,7 @@ async fn test_ws_subscribe_unsubscribe_cycle() {
         // Unsubscribe
         timeout(
             WS_SHORT_TIMEOUT,
-            handle.unsubscribe(&[subscription.clone()]),
+            handle.unsubscribe(std::slice::from_ref(&subscription)),
         )
         .await
         .expect("Unsubscribe timed out")
@@ -695,6 +723,8 @@ async fn test_ws_custom_configuration() {
 
     use massive_rs::auth::ApiKey;
     use massive_rs::config::{DispatchConfig, Feed, WsConfig};
+    use massive_rs::metrics::NoopMetrics;
+    use std::sync::Arc;
 
     let api_key = common::get_api_key();
 
@@ -702,10 +732,12 @@ async fn test_ws_custom_configuration() {
     let config = WsConfig {
         feed: Feed::Delayed,
         market: Market::Stocks,
+        base_url: None,
         api_key: ApiKey::new(&api_key),
         connect_timeout: Duration::from_secs(20),
         idle_timeout: Duration::from_secs(60),
         ping_interval: Duration::from_secs(30),
+        subscribe_timeout: Duration::from_secs(10),
         reconnect: ReconnectConfig {
             enabled: true,
             max_retries: Some(2),
@@ -718,6 +750,8 @@ async fn test_ws_custom_configuration() {
             overflow: OverflowPolicy::DropNewest,
             ..Default::default()
         },
+        metrics: Arc::new(NoopMetrics),
+        backfill: None,
     };
 
     let client = WsClient::new(config).expect("Failed to create WebSocket client");
@@ -838,7 +872,7 @@ async fn test_ws_handle_clone() {
     let subscription = Subscription::trade(TEST_TICKER);
     timeout(
         WS_SHORT_TIMEOUT,
-        handle_clone.subscribe(&[subscription.clone()]),
+        handle_clone.subscribe(std::slice::from_ref(&subscription)),
     )
     .await
     .expect("Subscribe timed out")
@@ -941,8 +975,8 @@ fn test_connection_state_enum() {
     assert_eq!(connecting, ConnectionState::Connecting);
     assert_ne!(connecting, connected);
 
-    // Test clone
-    let cloned = connected.clone();
+    // Test copy
+    let cloned = connected;
     assert_eq!(connected, cloned);
 
     println!("ConnectionState enum tests passed");
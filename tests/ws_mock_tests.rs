@@ -0,0 +1,1026 @@
+//! Mock-based WebSocket Tests
+//!
+//! This module runs the WebSocket client against a local tungstenite
+//! server, testing connection handling without requiring a real API key.
+//! Backfill tests also serve the REST API with wiremock.
+//!
+//! Run with: `cargo test --test ws_mock_tests`
+
+#![cfg(feature = "ws")]
+
+use futures::{SinkExt, StreamExt};
+use massive_rs::auth::ApiKey;
+use massive_rs::config::{
+    BackfillConfig, DispatchConfig, FanoutMode, ReconnectConfig, RestConfig, WsConfig,
+};
+use massive_rs::error::{MassiveError, WsError};
+use massive_rs::rest::RestClient;
+use massive_rs::ws::models::events::WsTradeEvent;
+use massive_rs::ws::{
+    ConnectionEvent, Diagnostic, DisconnectReason, Subscription, TopicStatus, WsClient, WsEvent,
+    WsMessageBatch,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::time::timeout;
+use tokio_tungstenite::tungstenite::Message;
+use url::Url;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Timeout for mock WebSocket operations
+const MOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+// ============================================================================
+// Test Helper Functions
+// ============================================================================
+
+/// Bind a local listener and return it with its `ws://` base URL.
+async fn bind_local() -> (TcpListener, Url) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("local addr");
+    let url = Url::parse(&format!("ws://{}", addr)).expect("valid URL");
+    (listener, url)
+}
+
+/// Send a `status` event for each topic of a subscribe or unsubscribe
+/// frame. Topics containing `DENIED` are rejected.
+async fn ack_topics<S>(ws: &mut S, frame: &serde_json::Value)
+where
+    S: futures::Sink<Message> + Unpin,
+    S::Error: std::fmt::Debug,
+{
+    let action = frame["action"].as_str().unwrap();
+    let statuses: Vec<String> = frame["params"]
+        .as_str()
+        .unwrap()
+        .split(',')
+        .map(|topic| {
+            if topic.contains("DENIED") {
+                format!(
+                    r#"{{"ev":"status","status":"error","message":"not authorized to access {topic}"}}"#
+                )
+            } else {
+                format!(r#"{{"ev":"status","status":"success","message":"{action}d to: {topic}"}}"#)
+            }
+        })
+        .collect();
+    ws.send(Message::Text(format!("[{}]", statuses.join(","))))
+        .await
+        .unwrap();
+}
+
+/// Read the next text frame, skipping pings and other control frames.
+async fn next_text<S>(ws: &mut S) -> String
+where
+    S: futures::Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+{
+    loop {
+        if let Message::Text(text) = ws.next().await.expect("stream ended").expect("read") {
+            return text.to_string();
+        }
+    }
+}
+
+/// Accept one connection, complete the auth handshake, acknowledge each
+/// subscribe frame, and echo it back as a trade event for the first topic.
+///
+/// Returns the request path the client connected to.
+#[allow(clippy::result_large_err)]
+async fn serve_one(listener: &TcpListener) -> String {
+    let (tcp, _) = listener.accept().await.expect("accept");
+    let mut path = String::new();
+    let mut ws = tokio_tungstenite::accept_hdr_async(
+        tcp,
+        |req: &tokio_tungstenite::tungstenite::handshake::server::Request,
+         resp: tokio_tungstenite::tungstenite::handshake::server::Response| {
+            path = req.uri().path().to_string();
+            Ok(resp)
+        },
+    )
+    .await
+    .expect("handshake");
+
+    ws.send(Message::Text(
+        r#"[{"ev":"status","status":"connected","message":"Connected Successfully"}]"#.into(),
+    ))
+    .await
+    .unwrap();
+
+    while let Some(Ok(msg)) = ws.next().await {
+        let Message::Text(text) = msg else { continue };
+        let frame: serde_json::Value = serde_json::from_str(&text).unwrap();
+        match frame["action"].as_str() {
+            Some("auth") => {
+                ws.send(Message::Text(
+                    r#"[{"ev":"status","status":"auth_success","message":"authenticated"}]"#.into(),
+                ))
+                .await
+                .unwrap();
+            }
+            Some("unsubscribe") => ack_topics(&mut ws, &frame).await,
+            Some("subscribe") => {
+                ack_topics(&mut ws, &frame).await;
+                let sym = frame["params"]
+                    .as_str()
+                    .and_then(|p| p.split(',').next())
+                    .and_then(|t| t.strip_prefix("T."))
+                    .unwrap_or("AAPL")
+                    .to_string();
+                let trade = format!(
+                    r#"[{{"ev":"T","sym":"{}","x":4,"i":"1","z":3,"p":150.25,"s":100,"t":1703001234567,"q":1}}]"#,
+                    sym
+                );
+                ws.send(Message::Text(trade)).await.unwrap();
+            }
+            _ => {}
+        }
+    }
+
+    path
+}
+
+/// Accept one connection and complete the auth handshake, then return the
+/// socket without reading from it, so pings go unanswered.
+async fn serve_silent(
+    listener: &TcpListener,
+) -> tokio_tungstenite::WebSocketStream<tokio::net::TcpStream> {
+    let (tcp, _) = listener.accept().await.expect("accept");
+    let mut ws = tokio_tungstenite::accept_async(tcp)
+        .await
+        .expect("handshake");
+
+    while let Some(Ok(msg)) = ws.next().await {
+        if matches!(&msg, Message::Text(text) if text.contains("\"auth\"")) {
+            ws.send(Message::Text(
+                r#"[{"ev":"status","status":"auth_success","message":"authenticated"}]"#.into(),
+            ))
+            .await
+            .unwrap();
+            break;
+        }
+    }
+    ws
+}
+
+/// Wait for the first trade event on a stream.
+async fn next_trade<S>(stream: &mut S) -> WsTradeEvent
+where
+    S: futures::Stream<Item = Result<WsMessageBatch, MassiveError>> + Unpin,
+{
+    timeout(MOCK_TIMEOUT, async {
+        while let Some(batch) = stream.next().await {
+            for event in batch.expect("batch").events {
+                if let WsEvent::Trade(t) = event {
+                    return t;
+                }
+            }
+        }
+        panic!("stream ended before trade");
+    })
+    .await
+    .expect("no trade received")
+}
+
+/// Create a WebSocket configuration pointing at the local server.
+fn mock_config(base_url: Url) -> WsConfig {
+    WsConfig {
+        reconnect: ReconnectConfig::disabled(),
+        ..WsConfig::new("test-api-key").with_base_url(base_url)
+    }
+}
+
+// ============================================================================
+// Connection Tests
+// ============================================================================
+
+/// Test connecting to a local `ws://` server via `WsConfig::with_base_url`
+#[tokio::test]
+async fn test_mock_ws_connect_custom_url() {
+    let (listener, url) = bind_local().await;
+    let server = tokio::spawn(async move { serve_one(&listener).await });
+
+    let client = WsClient::new(mock_config(url)).expect("client");
+    let (handle, mut stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+    assert!(handle.is_authenticated());
+
+    handle
+        .subscribe(&[Subscription::trade("MSFT")])
+        .await
+        .expect("subscribe");
+
+    let trade = next_trade(&mut stream).await;
+    assert_eq!(trade.sym.as_str(), "MSFT");
+
+    // connected + auth_success + subscribed + trade
+    let stats = handle.client_stats();
+    assert_eq!(stats.messages_received, 4);
+    assert!(stats.bytes_received > 0);
+    assert_eq!(stats.parse_errors, 0);
+    assert_eq!(client.stats().messages_received, 4);
+
+    handle.close().await.unwrap();
+    let path = timeout(MOCK_TIMEOUT, server).await.unwrap().unwrap();
+    assert_eq!(path, "/stocks");
+}
+
+// ============================================================================
+// Fanout Tests
+// ============================================================================
+
+/// Test that broadcast mode delivers every batch to each stream
+#[tokio::test]
+async fn test_mock_ws_broadcast_streams() {
+    let (listener, url) = bind_local().await;
+    let server = tokio::spawn(async move { serve_one(&listener).await });
+
+    let config = WsConfig {
+        dispatch: DispatchConfig {
+            fanout: FanoutMode::Broadcast,
+            ..Default::default()
+        },
+        ..mock_config(url)
+    };
+    let client = WsClient::new(config).expect("client");
+    let (handle, mut primary) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+
+    let mut recorder = handle.subscribe_stream().expect("broadcast stream");
+    let mut risk = handle.clone().subscribe_stream().expect("broadcast stream");
+    assert_eq!(handle.stats().stream_count, 3);
+
+    handle
+        .subscribe(&[Subscription::trade("AAPL")])
+        .await
+        .expect("subscribe");
+
+    assert_eq!(next_trade(&mut primary).await.sym.as_str(), "AAPL");
+    assert_eq!(next_trade(&mut recorder).await.sym.as_str(), "AAPL");
+    assert_eq!(next_trade(&mut risk).await.sym.as_str(), "AAPL");
+    assert_eq!(recorder.dropped_count(), 0);
+
+    handle.close().await.unwrap();
+    timeout(MOCK_TIMEOUT, server).await.unwrap().unwrap();
+}
+
+/// Test that a filtered stream only receives matching events
+#[tokio::test]
+async fn test_mock_ws_filtered_stream() {
+    let (listener, url) = bind_local().await;
+    let server = tokio::spawn(async move { serve_one(&listener).await });
+
+    let config = WsConfig {
+        dispatch: DispatchConfig {
+            fanout: FanoutMode::Broadcast,
+            ..Default::default()
+        },
+        ..mock_config(url)
+    };
+    let client = WsClient::new(config).expect("client");
+    let (handle, mut primary) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+
+    let mut aapl = handle
+        .subscribe_filtered("T.AAPL")
+        .expect("filtered stream");
+
+    handle
+        .subscribe(&[Subscription::trade("MSFT")])
+        .await
+        .expect("subscribe");
+    assert_eq!(next_trade(&mut primary).await.sym.as_str(), "MSFT");
+
+    handle
+        .subscribe(&[Subscription::trade("AAPL")])
+        .await
+        .expect("subscribe");
+    assert_eq!(next_trade(&mut primary).await.sym.as_str(), "AAPL");
+
+    // The MSFT batch was never queued for the filtered stream
+    assert_eq!(next_trade(&mut aapl).await.sym.as_str(), "AAPL");
+    assert_eq!(aapl.queued(), 0);
+
+    handle.close().await.unwrap();
+    timeout(MOCK_TIMEOUT, server).await.unwrap().unwrap();
+}
+
+// ============================================================================
+// Liveness Tests
+// ============================================================================
+
+/// Connect with reconnects enabled and wait for the first disconnect.
+async fn first_disconnect(config: WsConfig, listener: TcpListener) -> DisconnectReason {
+    let server = tokio::spawn(async move {
+        let first = serve_silent(&listener).await;
+        // The client reconnects after closing the dead connection
+        let second = serve_silent(&listener).await;
+        (first, second)
+    });
+
+    let client = WsClient::new(config).expect("client");
+    let (handle, _stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+    let mut lifecycle = handle.lifecycle();
+
+    let event = timeout(MOCK_TIMEOUT, lifecycle.next())
+        .await
+        .expect("no lifecycle event")
+        .expect("lifecycle stream ended");
+    timeout(MOCK_TIMEOUT, server)
+        .await
+        .expect("client did not reconnect")
+        .unwrap();
+    assert_eq!(handle.stats().reconnect_count, 1);
+
+    handle.close().await.unwrap();
+    match event {
+        ConnectionEvent::Disconnected { reason } => reason,
+        other => panic!("Expected disconnect, got {other:?}"),
+    }
+}
+
+/// Test that an unanswered ping closes the connection and reconnects
+#[tokio::test]
+async fn test_mock_ws_pong_timeout_reconnects() {
+    let (listener, url) = bind_local().await;
+    let config = WsConfig {
+        ping_interval: Duration::from_millis(100),
+        idle_timeout: Duration::from_secs(30),
+        reconnect: ReconnectConfig {
+            initial_delay: Duration::from_millis(10),
+            ..Default::default()
+        },
+        ..WsConfig::new("test-api-key").with_base_url(url)
+    };
+
+    assert_eq!(
+        first_disconnect(config, listener).await,
+        DisconnectReason::PongTimeout
+    );
+}
+
+/// Test that a silent connection is closed after idle_timeout
+#[tokio::test]
+async fn test_mock_ws_idle_timeout_reconnects() {
+    let (listener, url) = bind_local().await;
+    let config = WsConfig {
+        ping_interval: Duration::from_secs(30),
+        idle_timeout: Duration::from_millis(200),
+        reconnect: ReconnectConfig {
+            initial_delay: Duration::from_millis(10),
+            ..Default::default()
+        },
+        ..WsConfig::new("test-api-key").with_base_url(url)
+    };
+
+    assert_eq!(
+        first_disconnect(config, listener).await,
+        DisconnectReason::IdleTimeout
+    );
+}
+
+// ============================================================================
+// Lifecycle Tests
+// ============================================================================
+
+/// Test the lifecycle events of a server close, reconnect, and resubscribe
+#[tokio::test]
+async fn test_mock_ws_lifecycle_reconnect() {
+    let (listener, url) = bind_local().await;
+    let server = tokio::spawn(async move {
+        // Close the first connection once the client subscribes
+        let mut first = serve_silent(&listener).await;
+        let frame: serde_json::Value = serde_json::from_str(&next_text(&mut first).await).unwrap();
+        assert_eq!(frame["params"], "T.AAPL,T.MSFT");
+        ack_topics(&mut first, &frame).await;
+        first.send(Message::Close(None)).await.unwrap();
+
+        // The second connection must carry the resubscribe frame; the
+        // server no longer accepts T.MSFT
+        let mut second = serve_silent(&listener).await;
+        let frame = next_text(&mut second).await;
+        assert!(frame.contains("T.AAPL") && frame.contains("T.MSFT"));
+        second
+            .send(Message::Text(
+                r#"[{"ev":"status","status":"success","message":"subscribed to: T.AAPL"},{"ev":"status","status":"error","message":"not authorized to access T.MSFT"}]"#
+                    .into(),
+            ))
+            .await
+            .unwrap();
+        second
+    });
+
+    let config = WsConfig {
+        reconnect: ReconnectConfig {
+            initial_delay: Duration::from_millis(10),
+            ..Default::default()
+        },
+        ..WsConfig::new("test-api-key").with_base_url(url)
+    };
+    let client = WsClient::new(config).expect("client");
+    let (handle, _stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorded = seen.clone();
+    handle.on_lifecycle(move |event| recorded.lock().unwrap().push(event.clone()));
+    let mut lifecycle = handle.lifecycle();
+
+    let topics = vec![Subscription::trade("AAPL"), Subscription::trade("MSFT")];
+    handle.subscribe(&topics).await.expect("subscribe");
+
+    let expected = vec![
+        ConnectionEvent::Subscribed { topics },
+        ConnectionEvent::Disconnected {
+            reason: DisconnectReason::ServerClose,
+        },
+        ConnectionEvent::Reconnecting {
+            attempt: 1,
+            delay: Duration::from_millis(10),
+        },
+        ConnectionEvent::Connected,
+        ConnectionEvent::Authenticated,
+        ConnectionEvent::Resubscribed {
+            topics: vec![Subscription::trade("AAPL")],
+        },
+    ];
+    let mut events = Vec::new();
+    for _ in 0..expected.len() {
+        let event = timeout(MOCK_TIMEOUT, lifecycle.next())
+            .await
+            .expect("no lifecycle event")
+            .expect("lifecycle stream ended");
+        events.push(event);
+    }
+    assert_eq!(events, expected);
+    assert_eq!(*seen.lock().unwrap(), expected);
+    // The rejected topic is no longer restored on later reconnects
+    assert_eq!(handle.subscriptions(), vec![Subscription::trade("AAPL")]);
+
+    let _server_ws = timeout(MOCK_TIMEOUT, server).await.unwrap().unwrap();
+    handle.close().await.unwrap();
+    assert_eq!(
+        timeout(MOCK_TIMEOUT, lifecycle.next()).await.unwrap(),
+        Some(ConnectionEvent::Disconnected {
+            reason: DisconnectReason::ClientClose
+        })
+    );
+    assert_eq!(timeout(MOCK_TIMEOUT, lifecycle.next()).await.unwrap(), None);
+}
+
+/// Test that the reconnect attempt count restarts after every authenticated
+/// session, so `max_retries` limits consecutive failures only
+#[tokio::test]
+async fn test_mock_ws_reconnect_attempts_reset() {
+    let (listener, url) = bind_local().await;
+    let (listening_tx, listening_rx) = tokio::sync::oneshot::channel();
+    let server = tokio::spawn(async move {
+        let first = serve_silent(&listener).await;
+        // Drop the first session once the test watches the lifecycle
+        listening_rx.await.unwrap();
+        drop(first);
+        drop(serve_silent(&listener).await);
+        serve_silent(&listener).await
+    });
+
+    let config = WsConfig {
+        reconnect: ReconnectConfig {
+            initial_delay: Duration::from_millis(10),
+            max_retries: Some(2),
+            ..Default::default()
+        },
+        ..WsConfig::new("test-api-key").with_base_url(url)
+    };
+    let client = WsClient::new(config).expect("client");
+    let (handle, _stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+    let mut lifecycle = handle.lifecycle();
+    listening_tx.send(()).unwrap();
+
+    let mut attempts = Vec::new();
+    let mut sessions = 0;
+    while sessions < 2 {
+        match timeout(MOCK_TIMEOUT, lifecycle.next())
+            .await
+            .expect("no lifecycle event")
+            .expect("lifecycle stream ended")
+        {
+            ConnectionEvent::Reconnecting { attempt, .. } => attempts.push(attempt),
+            ConnectionEvent::Authenticated => sessions += 1,
+            ConnectionEvent::GaveUp { attempts } => panic!("Gave up after {attempts} attempts"),
+            _ => {}
+        }
+    }
+    assert_eq!(attempts, vec![1, 1]);
+    assert_eq!(handle.stats().reconnect_count, 2);
+
+    let _server_ws = timeout(MOCK_TIMEOUT, server).await.unwrap().unwrap();
+    handle.close().await.unwrap();
+}
+
+/// Test that rejected credentials on a reconnect end the connection instead
+/// of reconnecting forever
+#[tokio::test]
+async fn test_mock_ws_reconnect_auth_rejected() {
+    use massive_rs::ws::ConnectionState;
+
+    let (listener, url) = bind_local().await;
+    let server = tokio::spawn(async move {
+        let first = serve_silent(&listener).await;
+        drop(first);
+
+        let (tcp, _) = listener.accept().await.expect("accept");
+        let mut second = tokio_tungstenite::accept_async(tcp)
+            .await
+            .expect("handshake");
+        second.next().await;
+        second
+            .send(Message::Text(
+                r#"[{"ev":"status","status":"auth_failed","message":"authentication failed"}]"#
+                    .into(),
+            ))
+            .await
+            .unwrap();
+        second
+    });
+
+    let config = WsConfig {
+        reconnect: ReconnectConfig {
+            initial_delay: Duration::from_millis(10),
+            ..Default::default()
+        },
+        ..WsConfig::new("test-api-key").with_base_url(url)
+    };
+    let client = WsClient::new(config).expect("client");
+    let (handle, _stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+    let mut lifecycle = handle.lifecycle();
+
+    let mut events = Vec::new();
+    while let Some(event) = timeout(MOCK_TIMEOUT, lifecycle.next())
+        .await
+        .expect("no lifecycle event")
+    {
+        events.push(event);
+    }
+    assert_eq!(
+        events[events.len() - 2..],
+        [
+            ConnectionEvent::Disconnected {
+                reason: DisconnectReason::AuthFailed,
+            },
+            ConnectionEvent::GaveUp { attempts: 1 },
+        ]
+    );
+    assert_eq!(handle.connection_state(), ConnectionState::Disconnected);
+    let _server_ws = timeout(MOCK_TIMEOUT, server).await.unwrap().unwrap();
+}
+
+/// Test that a subscribe interrupted by a dropped connection fails with a
+/// retryable error while the client reconnects
+#[tokio::test]
+async fn test_mock_ws_subscribe_interrupted_by_disconnect() {
+    let (listener, url) = bind_local().await;
+    let server = tokio::spawn(async move {
+        // Drop the connection instead of answering the subscribe frame
+        let mut first = serve_silent(&listener).await;
+        assert!(next_text(&mut first).await.contains("T.AAPL"));
+        first.send(Message::Close(None)).await.unwrap();
+
+        let mut second = serve_silent(&listener).await;
+        let frame: serde_json::Value = serde_json::from_str(&next_text(&mut second).await).unwrap();
+        ack_topics(&mut second, &frame).await;
+        second
+    });
+
+    let config = WsConfig {
+        reconnect: ReconnectConfig {
+            initial_delay: Duration::from_millis(10),
+            ..Default::default()
+        },
+        ..WsConfig::new("test-api-key").with_base_url(url)
+    };
+    let client = WsClient::new(config).expect("client");
+    let (handle, _stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+
+    let result = timeout(
+        MOCK_TIMEOUT,
+        handle.subscribe(&[Subscription::trade("AAPL")]),
+    )
+    .await
+    .expect("subscribe did not complete");
+    assert!(
+        matches!(&result, Err(MassiveError::Ws(e)) if matches!(**e, WsError::Disconnected)),
+        "unexpected result: {result:?}"
+    );
+
+    // Retrying on the new connection succeeds
+    let report = timeout(
+        MOCK_TIMEOUT,
+        handle.subscribe(&[Subscription::trade("AAPL")]),
+    )
+    .await
+    .expect("retry did not complete")
+    .expect("retry failed");
+    assert!(report.is_success());
+
+    let _server_ws = timeout(MOCK_TIMEOUT, server).await.unwrap().unwrap();
+    handle.close().await.unwrap();
+}
+
+/// Test that connect fails promptly when the server rejects the API key
+#[tokio::test]
+async fn test_mock_ws_auth_rejected() {
+    let (listener, url) = bind_local().await;
+    let server = tokio::spawn(async move {
+        let (tcp, _) = listener.accept().await.expect("accept");
+        let mut ws = tokio_tungstenite::accept_async(tcp)
+            .await
+            .expect("handshake");
+        ws.next().await;
+        ws.send(Message::Text(
+            r#"[{"ev":"status","status":"auth_failed","message":"authentication failed"}]"#.into(),
+        ))
+        .await
+        .unwrap();
+    });
+
+    let client = WsClient::new(mock_config(url)).expect("client");
+    let result = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out");
+    assert!(matches!(result, Err(MassiveError::Ws(e)) if matches!(*e, WsError::AuthFailed(_))));
+    server.await.unwrap();
+}
+
+// ============================================================================
+// Subscription Tests
+// ============================================================================
+
+/// Test that only topics confirmed by the server are recorded
+#[tokio::test]
+async fn test_mock_ws_subscribe_acknowledged() {
+    let (listener, url) = bind_local().await;
+    let server = tokio::spawn(async move { serve_one(&listener).await });
+
+    let client = WsClient::new(mock_config(url)).expect("client");
+    let (handle, _stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+
+    let report = handle
+        .subscribe(&[Subscription::trade("AAPL"), Subscription::quote("DENIED")])
+        .await
+        .expect("subscribe");
+    assert!(!report.is_success());
+    assert_eq!(report.results[0].1, TopicStatus::Accepted);
+    assert_eq!(
+        report.results[1].1,
+        TopicStatus::Rejected("not authorized to access Q.DENIED".into())
+    );
+    assert!(matches!(
+        report.into_result(),
+        Err(MassiveError::Ws(e)) if matches!(*e, WsError::SubscriptionFailed(_))
+    ));
+    assert_eq!(handle.subscriptions(), vec![Subscription::trade("AAPL")]);
+
+    let report = handle
+        .unsubscribe(&[Subscription::trade("AAPL")])
+        .await
+        .expect("unsubscribe");
+    assert!(report.is_success());
+    assert!(handle.subscriptions().is_empty());
+
+    handle.close().await.unwrap();
+    timeout(MOCK_TIMEOUT, server).await.unwrap().unwrap();
+}
+
+/// Test that large topic lists are split across frames and unanswered
+/// topics time out
+#[tokio::test]
+async fn test_mock_ws_subscribe_chunked_with_timeout() {
+    let (listener, url) = bind_local().await;
+    let server = tokio::spawn(async move {
+        let mut ws = serve_silent(&listener).await;
+        let mut frames = 0;
+        let mut topics = 0;
+        while topics < 1200 {
+            let mut frame: serde_json::Value =
+                serde_json::from_str(&next_text(&mut ws).await).unwrap();
+            frames += 1;
+            topics += frame["params"].as_str().unwrap().split(',').count();
+            // Never confirm T.SLOW
+            let params = frame["params"].as_str().unwrap().replace(",T.SLOW", "");
+            frame["params"] = params.into();
+            ack_topics(&mut ws, &frame).await;
+        }
+        (frames, ws)
+    });
+
+    let config = WsConfig {
+        subscribe_timeout: Duration::from_millis(200),
+        ..mock_config(url)
+    };
+    let client = WsClient::new(config).expect("client");
+    let (handle, _stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+
+    let mut topics: Vec<_> = (0..1199)
+        .map(|i| Subscription::trade(&format!("S{i}")))
+        .collect();
+    topics.push(Subscription::trade("SLOW"));
+    let report = timeout(MOCK_TIMEOUT, handle.subscribe(&topics))
+        .await
+        .expect("subscribe timed out")
+        .expect("subscribe");
+
+    assert_eq!(report.accepted().count(), 1199);
+    assert_eq!(
+        report.failed().collect::<Vec<_>>(),
+        vec![(&Subscription::trade("SLOW"), &TopicStatus::TimedOut)]
+    );
+    assert_eq!(handle.subscriptions().len(), 1199);
+
+    let (frames, _ws) = timeout(MOCK_TIMEOUT, server).await.unwrap().unwrap();
+    assert_eq!(frames, 3);
+    handle.close().await.unwrap();
+}
+
+// ============================================================================
+// Backfill Tests
+// ============================================================================
+
+/// Test that trades missed during a reconnect are fetched over REST and
+/// delivered in a backfilled batch
+#[tokio::test]
+async fn test_mock_ws_backfill_after_reconnect() {
+    let last_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64
+        - 1_000;
+    let last_ns = last_ms * 1_000_000;
+
+    // The REST API returns the trade already seen live and two missed ones,
+    // one of them in the same millisecond
+    let rest_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/trades/AAPL"))
+        .and(query_param("timestamp.gte", last_ns.to_string()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "OK",
+            "results": [
+                {"id": "5", "price": 150.0, "size": 100, "sip_timestamp": last_ns, "sequence_number": 5},
+                {"id": "6", "price": 150.1, "size": 200, "sip_timestamp": last_ns, "sequence_number": 6},
+                {"id": "7", "price": 150.2, "size": 300, "sip_timestamp": last_ns + 10_000_000, "sequence_number": 7}
+            ]
+        })))
+        .expect(1)
+        .mount(&rest_server)
+        .await;
+
+    let (listener, url) = bind_local().await;
+    let server = tokio::spawn(async move {
+        // Deliver one live trade, then drop the connection
+        let mut first = serve_silent(&listener).await;
+        let frame: serde_json::Value = serde_json::from_str(&next_text(&mut first).await).unwrap();
+        ack_topics(&mut first, &frame).await;
+        first
+            .send(Message::Text(format!(
+                r#"[{{"ev":"T","sym":"AAPL","x":4,"i":"5","z":3,"p":150.0,"s":100,"t":{last_ms},"q":5}}]"#
+            )))
+            .await
+            .unwrap();
+        first.send(Message::Close(None)).await.unwrap();
+
+        // Backfill starts once the restored subscription is confirmed
+        let mut second = serve_silent(&listener).await;
+        let frame: serde_json::Value = serde_json::from_str(&next_text(&mut second).await).unwrap();
+        assert_eq!(frame["params"], "T.AAPL");
+        ack_topics(&mut second, &frame).await;
+        second
+    });
+
+    let rest = RestClient::new(RestConfig {
+        base_url: Url::parse(&rest_server.uri()).unwrap(),
+        api_key: ApiKey::new("test-api-key"),
+        ..Default::default()
+    })
+    .expect("rest client");
+    let config = WsConfig {
+        reconnect: ReconnectConfig {
+            initial_delay: Duration::from_millis(10),
+            ..Default::default()
+        },
+        ..WsConfig::new("test-api-key")
+            .with_base_url(url)
+            .with_backfill(BackfillConfig::new(rest))
+    };
+    let client = WsClient::new(config).expect("client");
+    let (handle, mut stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+    let mut lifecycle = handle.lifecycle();
+
+    handle
+        .subscribe(&[Subscription::trade("AAPL")])
+        .await
+        .expect("subscribe");
+
+    let mut live = Vec::new();
+    let backfilled = loop {
+        let batch = timeout(MOCK_TIMEOUT, stream.next())
+            .await
+            .expect("no backfilled batch")
+            .expect("stream ended")
+            .expect("stream error");
+        let trades = batch.events.iter().filter_map(|e| match e {
+            WsEvent::Trade(t) => Some(t.q),
+            _ => None,
+        });
+        if batch.backfilled {
+            break trades.collect::<Vec<_>>();
+        }
+        live.extend(trades);
+    };
+    assert_eq!(live, vec![5]);
+    assert_eq!(backfilled, vec![6, 7]);
+
+    loop {
+        let event = timeout(MOCK_TIMEOUT, lifecycle.next())
+            .await
+            .expect("no backfill event")
+            .expect("lifecycle stream ended");
+        if let ConnectionEvent::Backfilled {
+            events,
+            failed,
+            truncated,
+        } = event
+        {
+            assert_eq!(events, 2);
+            assert!(failed.is_empty());
+            assert!(truncated.is_empty());
+            break;
+        }
+    }
+
+    let _server_ws = timeout(MOCK_TIMEOUT, server).await.unwrap().unwrap();
+    handle.close().await.unwrap();
+}
+
+// ============================================================================
+// Diagnostics Tests
+// ============================================================================
+
+/// Test that uninterpretable frames and events are reported as diagnostics
+#[tokio::test]
+async fn test_mock_ws_diagnostics() {
+    let (listener, url) = bind_local().await;
+    let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+    let server = tokio::spawn(async move {
+        let mut ws = serve_silent(&listener).await;
+        // Wait until the client listens for diagnostics
+        release_rx.await.unwrap();
+        for frame in [
+            Message::Text("not json".into()),
+            Message::Binary(vec![1, 2, 3]),
+            Message::Text(r#"[{"ev":"status","status":"max_connections","message":"Too many"}]"#.into()),
+            Message::Text(
+                r#"[{"ev":"Q","sym":"AAPL"},{"ev":"T","sym":"AAPL","x":4,"i":"1","z":3,"p":1.0,"s":1,"t":1,"q":7}]"#
+                    .into(),
+            ),
+        ] {
+            ws.send(frame).await.unwrap();
+        }
+        ws
+    });
+
+    let client = WsClient::new(mock_config(url)).expect("client");
+    let (handle, mut stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+    let mut diagnostics = handle.diagnostics();
+    release_tx.send(()).unwrap();
+
+    // The valid trade in the last frame is still delivered
+    assert_eq!(next_trade(&mut stream).await.q, 7);
+
+    let received: Vec<Diagnostic> = timeout(MOCK_TIMEOUT, diagnostics.by_ref().take(4).collect())
+        .await
+        .expect("missing diagnostics");
+    assert!(matches!(&received[0], Diagnostic::MalformedFrame { raw, .. } if raw == "not json"));
+    assert_eq!(
+        received[1],
+        Diagnostic::BinaryFrame {
+            data: vec![1, 2, 3]
+        }
+    );
+    assert!(matches!(
+        &received[2],
+        Diagnostic::UnknownStatus { status, message, .. }
+            if status == "max_connections" && message.as_deref() == Some("Too many")
+    ));
+    assert!(matches!(
+        &received[3],
+        Diagnostic::InvalidEvent { index: 0, ev: Some(ev), raw, .. }
+            if ev == "Q" && raw == r#"{"ev":"Q","sym":"AAPL"}"#
+    ));
+    assert_eq!(handle.client_stats().parse_errors, 4);
+
+    let _server_ws = timeout(MOCK_TIMEOUT, server).await.unwrap().unwrap();
+    handle.close().await.unwrap();
+    assert_eq!(
+        timeout(MOCK_TIMEOUT, diagnostics.next()).await.unwrap(),
+        None
+    );
+}
+
+// ============================================================================
+// Recording Tests
+// ============================================================================
+
+/// Test that a recorded session replays the same frames and then ends
+#[cfg(feature = "record")]
+#[tokio::test]
+async fn test_mock_ws_record_and_replay() {
+    use massive_rs::config::RecorderConfig;
+    use massive_rs::ws::{ConnectionState, Pacing, ReplayClient};
+
+    let dir = std::env::temp_dir().join(format!("massive-ws-replay-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let (listener, url) = bind_local().await;
+    let server = tokio::spawn(async move { serve_one(&listener).await });
+
+    let client = WsClient::new(mock_config(url))
+        .expect("client")
+        .with_recorder(RecorderConfig::new(&dir));
+    let (handle, mut stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+    handle
+        .subscribe(&[Subscription::trade("MSFT")])
+        .await
+        .expect("subscribe");
+    assert_eq!(next_trade(&mut stream).await.sym.as_str(), "MSFT");
+
+    // The journal is complete once the lifecycle stream ends
+    let mut lifecycle = handle.lifecycle();
+    handle.close().await.unwrap();
+    timeout(MOCK_TIMEOUT, async {
+        while lifecycle.next().await.is_some() {}
+    })
+    .await
+    .expect("lifecycle stream did not end");
+    timeout(MOCK_TIMEOUT, server).await.unwrap().unwrap();
+
+    let (replay, mut replayed) = ReplayClient::new(&dir)
+        .with_pacing(Pacing::AsFastAsPossible)
+        .connect()
+        .await
+        .expect("replay");
+    assert!(replay.is_authenticated());
+
+    let events: Vec<WsEvent> = timeout(MOCK_TIMEOUT, async {
+        let mut events = Vec::new();
+        while let Some(batch) = replayed.next().await {
+            events.extend(batch.expect("batch").events);
+        }
+        events
+    })
+    .await
+    .expect("replay did not end");
+
+    // connected + auth_success + subscribed + trade
+    assert_eq!(events.len(), 4);
+    assert!(matches!(&events[3], WsEvent::Trade(t) if t.sym.as_str() == "MSFT"));
+    assert_eq!(replay.connection_state(), ConnectionState::Disconnected);
+    assert_eq!(replay.client_stats().messages_received, 4);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
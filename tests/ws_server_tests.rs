@@ -0,0 +1,331 @@
+//! Mock Server WebSocket Tests
+//!
+//! This module runs the WebSocket client against `testing::MockWsServer`,
+//! covering reconnection, subscription confirmations, and backpressure
+//! deterministically without requiring a real API key.
+//!
+//! Run with: `cargo test --test ws_server_tests --features testing`
+
+#![cfg(feature = "testing")]
+
+use futures::StreamExt;
+use massive_rs::config::{DispatchConfig, Market, OverflowPolicy, ReconnectConfig, WsConfig};
+use massive_rs::error::{MassiveError, WsError};
+use massive_rs::testing::MockWsServer;
+use massive_rs::ws::{
+    ConnectionEvent, DisconnectReason, Subscription, TopicStatus, WsClient, WsEvent,
+};
+use std::time::Duration;
+use tokio::time::timeout;
+
+/// Timeout for mock server operations
+const MOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Trade event in wire format
+fn trade(sym: &str, seq: u64) -> serde_json::Value {
+    serde_json::json!({
+        "ev": "T", "sym": sym, "x": 4, "i": seq.to_string(), "z": 3,
+        "p": 150.25, "s": 100, "t": 1703001234567_i64, "q": seq
+    })
+}
+
+/// Client configuration with fast reconnects
+fn reconnecting_config(server: &MockWsServer) -> WsConfig {
+    WsConfig {
+        reconnect: ReconnectConfig {
+            initial_delay: Duration::from_millis(10),
+            ..Default::default()
+        },
+        ..server.config("test-api-key")
+    }
+}
+
+// ============================================================================
+// Protocol Tests
+// ============================================================================
+
+/// Test that subscriptions are confirmed and pushed events are delivered
+#[tokio::test]
+async fn test_server_subscribe_and_push() {
+    let server = MockWsServer::start().await.expect("server");
+    let client = WsClient::new(server.config("test-api-key")).expect("client");
+    let (handle, mut stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+
+    let denied = Subscription::trade("DENIED");
+    server.deny_topic(&denied);
+    let report = handle
+        .subscribe(&[Subscription::trade("AAPL"), denied.clone()])
+        .await
+        .expect("subscribe");
+    assert_eq!(report.results[0].1, TopicStatus::Accepted);
+    assert!(matches!(report.results[1].1, TopicStatus::Rejected(_)));
+    assert_eq!(server.subscriptions(), vec![Subscription::trade("AAPL")]);
+
+    server.push_events(&[trade("AAPL", 1), trade("AAPL", 2)]);
+    let trades = timeout(MOCK_TIMEOUT, async {
+        loop {
+            let batch = stream.next().await.expect("stream ended").expect("batch");
+            let trades: Vec<u64> = batch
+                .events
+                .iter()
+                .filter_map(|e| match e {
+                    WsEvent::Trade(t) => Some(t.q),
+                    _ => None,
+                })
+                .collect();
+            if !trades.is_empty() {
+                return trades;
+            }
+        }
+    })
+    .await
+    .expect("no trades");
+    assert_eq!(trades, vec![1, 2]);
+
+    handle
+        .unsubscribe(&[Subscription::trade("AAPL")])
+        .await
+        .expect("unsubscribe");
+    assert!(server.subscriptions().is_empty());
+    handle.close().await.unwrap();
+}
+
+/// Test that a malformed event does not drop the rest of its frame
+#[tokio::test]
+async fn test_server_partial_frame_delivered() {
+    let server = MockWsServer::start().await.expect("server");
+    let client = WsClient::new(server.config("test-api-key")).expect("client");
+    let (handle, mut stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+
+    server.push_events(&[
+        serde_json::json!({"ev": "T", "sym": "AAPL", "p": "not a price"}),
+        trade("AAPL", 2),
+        serde_json::json!({"ev": "FUT", "sym": "ESZ4", "p": 6000.25}),
+    ]);
+    let events = timeout(MOCK_TIMEOUT, async {
+        loop {
+            let batch = stream.next().await.expect("stream ended").expect("batch");
+            if batch.events.iter().any(|e| matches!(e, WsEvent::Trade(_))) {
+                return batch.events;
+            }
+        }
+    })
+    .await
+    .expect("no trades");
+
+    assert_eq!(events.len(), 2);
+    assert!(matches!(&events[0], WsEvent::Trade(t) if t.q == 2));
+    match &events[1] {
+        WsEvent::Other { ev, raw, .. } => {
+            assert_eq!(ev, "FUT");
+            assert!(raw.get().contains("6000.25"));
+        }
+        other => panic!("expected Other, got {:?}", other),
+    }
+    assert_eq!(handle.client_stats().parse_errors, 1);
+    handle.close().await.unwrap();
+}
+
+/// Test that events on a futures connection parse as futures events
+#[tokio::test]
+async fn test_server_futures_events() {
+    let server = MockWsServer::start().await.expect("server");
+    let config = server.config("test-api-key").with_market(Market::Futures);
+    let client = WsClient::new(config).expect("client");
+    let (handle, mut stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+
+    let topic = Subscription::futures_trade("ESZ4");
+    handle
+        .subscribe(std::slice::from_ref(&topic))
+        .await
+        .expect("subscribe");
+    assert_eq!(server.subscriptions(), vec![topic]);
+
+    server.push_events(&[serde_json::json!({
+        "ev": "T", "sym": "ESZ4", "p": 6000.25, "s": 2, "t": 1703001234567_i64, "q": 42
+    })]);
+    let event = timeout(MOCK_TIMEOUT, async {
+        loop {
+            let batch = stream.next().await.expect("stream ended").expect("batch");
+            if let Some(event) = batch.events.into_iter().find(|e| e.event_type() == "T") {
+                return event;
+            }
+        }
+    })
+    .await
+    .expect("no futures trade");
+    assert!(matches!(event, WsEvent::FuturesTrade(t) if t.q == 42 && t.sym == "ESZ4"));
+    assert_eq!(handle.client_stats().parse_errors, 0);
+    handle.close().await.unwrap();
+}
+
+/// Test that a rejected API key fails the connect call
+#[tokio::test]
+async fn test_server_auth_failure() {
+    let server = MockWsServer::start().await.expect("server");
+    server.reject_auth(true);
+
+    let client = WsClient::new(server.config("bad-key")).expect("client");
+    let result = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out");
+    match result {
+        Err(MassiveError::Ws(e)) => assert!(matches!(*e, WsError::AuthFailed(_))),
+        other => panic!("expected auth failure, got {:?}", other.map(|_| ())),
+    }
+}
+
+// ============================================================================
+// Reconnection Tests
+// ============================================================================
+
+/// Test that a server disconnect reconnects and restores subscriptions
+#[tokio::test]
+async fn test_server_disconnect_resubscribes() {
+    let server = MockWsServer::start().await.expect("server");
+    let client = WsClient::new(reconnecting_config(&server)).expect("client");
+    let (handle, mut stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+    let mut lifecycle = handle.lifecycle();
+
+    let topic = Subscription::trade("MSFT");
+    handle
+        .subscribe(std::slice::from_ref(&topic))
+        .await
+        .expect("subscribe");
+
+    server.disconnect();
+    timeout(MOCK_TIMEOUT, async {
+        server.wait_for_authentications(2).await;
+        server.wait_for_subscription(&topic).await;
+    })
+    .await
+    .expect("client did not resubscribe");
+    assert_eq!(server.connection_count(), 2);
+
+    let mut reasons = Vec::new();
+    timeout(MOCK_TIMEOUT, async {
+        while let Some(event) = lifecycle.next().await {
+            match event {
+                ConnectionEvent::Disconnected { reason } => reasons.push(reason),
+                ConnectionEvent::Resubscribed { topics } => {
+                    assert_eq!(topics, vec![topic.clone()]);
+                    break;
+                }
+                _ => {}
+            }
+        }
+    })
+    .await
+    .expect("no resubscribe event");
+    assert_eq!(reasons, vec![DisconnectReason::ServerClose]);
+
+    // Events pushed on the new connection still reach the original stream
+    server.push_events(&[trade("MSFT", 9)]);
+    let seq = timeout(MOCK_TIMEOUT, async {
+        while let Some(batch) = stream.next().await {
+            for event in batch.expect("batch").events {
+                if let WsEvent::Trade(t) = event {
+                    return t.q;
+                }
+            }
+        }
+        panic!("stream ended");
+    })
+    .await
+    .expect("no trade after reconnect");
+    assert_eq!(seq, 9);
+
+    handle.close().await.unwrap();
+}
+
+/// Test that a stalled server triggers a pong timeout and reconnect
+#[tokio::test]
+async fn test_server_stall_pong_timeout() {
+    let server = MockWsServer::start().await.expect("server");
+    let config = WsConfig {
+        ping_interval: Duration::from_millis(50),
+        ..reconnecting_config(&server)
+    };
+    let client = WsClient::new(config).expect("client");
+    let (handle, _stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+    let mut lifecycle = handle.lifecycle();
+
+    server.stall(true);
+    let reason = timeout(MOCK_TIMEOUT, async {
+        loop {
+            if let Some(ConnectionEvent::Disconnected { reason }) = lifecycle.next().await {
+                return reason;
+            }
+        }
+    })
+    .await
+    .expect("no disconnect");
+    assert_eq!(reason, DisconnectReason::PongTimeout);
+
+    server.stall(false);
+    timeout(MOCK_TIMEOUT, server.wait_for_authentications(2))
+        .await
+        .expect("client did not reconnect");
+    handle.close().await.unwrap();
+}
+
+// ============================================================================
+// Backpressure Tests
+// ============================================================================
+
+/// Test that a flood of events overflows a slow consumer's queue
+#[tokio::test]
+async fn test_server_flood_drops_oldest() {
+    let server = MockWsServer::start().await.expect("server");
+    let config = WsConfig {
+        dispatch: DispatchConfig {
+            capacity: 4,
+            overflow: OverflowPolicy::DropOldest,
+            ..Default::default()
+        },
+        ..server.config("test-api-key")
+    };
+    let client = WsClient::new(config).expect("client");
+    let (handle, mut stream) = timeout(MOCK_TIMEOUT, client.connect())
+        .await
+        .expect("connect timed out")
+        .expect("connect failed");
+
+    // The connected and auth_success batches are already queued
+    let frame = serde_json::Value::from(vec![trade("AAPL", 1)]).to_string();
+    server.flood(&frame, 20);
+
+    timeout(MOCK_TIMEOUT, async {
+        while handle.stats().message_count < 22 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("flood not received");
+    assert_eq!(handle.stats().dropped_count, 18);
+
+    // The newest batches survive
+    let mut remaining = 0;
+    while let Ok(Some(batch)) = timeout(Duration::from_millis(50), stream.next()).await {
+        assert!(matches!(batch.expect("batch").events[0], WsEvent::Trade(_)));
+        remaining += 1;
+    }
+    assert_eq!(remaining, 4);
+
+    handle.close().await.unwrap();
+}